@@ -0,0 +1,104 @@
+use std::{path::PathBuf, sync::Arc};
+
+use log::{error, info};
+
+use iracehud_lib::db::{DATABASE, Database};
+use iracehud_lib::pipeline::{Pipeline, SourceMode};
+use iracehud_lib::settings::file_settings_provider::FileSettingsProvider;
+use iracehud_lib::settings::network_settings::NetworkSettings;
+use iracehud_lib::settings::settings_provider::SETTINGS_PROVIDER;
+use iracehud_lib::websocket::control_handler::ControlHandler;
+use iracehud_lib::websocket::server::{WS_SERVER, WebSocketServer};
+use iracehud_lib::websocket::static_files::DirStaticFiles;
+
+const DEFAULT_DATA_DIR: &str = "iracehud_data";
+
+fn arg_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == name)
+        .and_then(|idx| args.get(idx + 1))
+        .map(String::as_str)
+}
+
+/// Runs the telemetry pipeline and the WebSocket server without any windows, e.g. for a
+/// browser source in OBS or a second PC
+///
+/// Bind address, port and token come from `<data-dir>/network.json` unless given, clients find
+/// them in `<data-dir>/discovery.json`. With `--frontend-dir` the built frontend is served over
+/// HTTP on the same port, e.g. `/stream/relative` for a browser source
+///
+/// `iracehud-server [--data-dir <path>] [--bind <addr>] [--token <token>] [--frontend-dir <path>]
+/// [--force-active] [--replay <capture or .ibt path> | --demo [config path]]
+/// [--replay-speed <multiplier|step>]`
+#[tokio::main]
+async fn main() -> eyre::Result<()> {
+    let _ = color_eyre::install();
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
+        .filter_module("tungstenite", log::LevelFilter::Warn)
+        .filter_module("tokio_tungstenite", log::LevelFilter::Warn)
+        .filter_module("sqlx::query", log::LevelFilter::Warn)
+        .init();
+
+    let args: Vec<String> = std::env::args().collect();
+    let data_dir = PathBuf::from(arg_value(&args, "--data-dir").unwrap_or(DEFAULT_DATA_DIR));
+
+    info!("Using data directory {}", data_dir.display());
+
+    let network_settings = NetworkSettings::load(&data_dir);
+    let bind_addr = arg_value(&args, "--bind")
+        .map(str::to_string)
+        .unwrap_or_else(|| network_settings.bind_addr());
+    let token = arg_value(&args, "--token").unwrap_or(&network_settings.token);
+
+    if DATABASE.set(Database::new(&data_dir).await).is_err() {
+        error!("Database already initialized");
+    }
+    if SETTINGS_PROVIDER
+        .set(Box::new(FileSettingsProvider::new(
+            data_dir.join("settings"),
+        )))
+        .is_err()
+    {
+        error!("Settings provider already initialized");
+    }
+
+    // Clients subscribe to the events they need over the WebSocket
+    let pipeline = Arc::new(Pipeline::default());
+    if args.iter().any(|arg| arg == "--force-active") {
+        pipeline.toggle_force_active().await;
+    }
+
+    let mut server = WebSocketServer::new()
+        .with_control_handler(ControlHandler::new(pipeline.clone()))
+        .with_token(token)
+        .with_discovery_file(data_dir.join("discovery.json"));
+    if let Some(frontend_dir) = arg_value(&args, "--frontend-dir") {
+        info!("Serving frontend from {}", frontend_dir);
+        server = server.with_static_files(DirStaticFiles::new(PathBuf::from(frontend_dir)));
+    }
+    let server_clone = server.clone();
+    let _ = WS_SERVER
+        .set(server)
+        .map_err(|err| error!("Failed to set WebSocket server: {:?}", err));
+    tokio::spawn(async move {
+        server_clone.run(&bind_addr).await;
+    });
+
+    let mode = SourceMode::from_args(&args)?;
+    let run = async {
+        pipeline.run(mode).await?;
+        info!("Telemetry source finished");
+        eyre::Ok(())
+    };
+
+    tokio::select! {
+        result = run => result,
+        _ = tokio::signal::ctrl_c() => {
+            info!("Shutting down");
+            if let Some(recorder) = pipeline.recorder.lock().await.take() {
+                recorder.finish()?;
+            }
+            Ok(())
+        }
+    }
+}
@@ -1,19 +1,19 @@
 use sqlx::{Pool, Sqlite, SqlitePool};
-use std::{env, fs};
-use tauri::{AppHandle, Manager};
+use std::sync::OnceLock;
+use std::{env, fs, path::Path};
+
+pub static DATABASE: OnceLock<Database> = OnceLock::new();
 
 pub struct Database {
     pub pool: Pool<Sqlite>,
 }
 
 impl Database {
-    pub async fn new(app_handle: &AppHandle) -> Self {
-        let app_dir = app_handle.path().app_data_dir().unwrap();
-
-        // Ensure the app directory exists
-        let _ = fs::create_dir_all(&app_dir);
+    pub async fn new(data_dir: &Path) -> Self {
+        // Ensure the data directory exists
+        let _ = fs::create_dir_all(data_dir);
 
-        let db_path = app_dir.join("iracehud.db");
+        let db_path = data_dir.join("iracehud.db");
 
         // Set the DATABASE_URL environment variable to point to this SQLite file
         unsafe { env::set_var("DATABASE_URL", format!("sqlite://{}", db_path.display())) };
@@ -30,7 +30,3 @@ impl Database {
         Self { pool }
     }
 }
-
-// State management for Tauri
-#[allow(dead_code)]
-pub struct DatabaseState(pub Pool<Sqlite>);
@@ -5,10 +5,13 @@ use strum_macros::{Display, EnumIter, EnumString};
 
 use crate::session::session_data::SessionData;
 use crate::telemetry::active::Active;
+use crate::telemetry::class_standings::ClassStandings;
 use crate::telemetry::current_time::CurrentTime;
 use crate::telemetry::delta_best_time::DeltaBestTime;
 use crate::telemetry::delta_last_time::DeltaLastTime;
 use crate::telemetry::fastest_lap::FastestLap;
+use crate::telemetry::flags::Flags;
+use crate::telemetry::fuel::Fuel;
 use crate::telemetry::gap_next::GapNext;
 use crate::telemetry::gap_prev::GapPrev;
 use crate::telemetry::gear::Gear;
@@ -22,22 +25,29 @@ use crate::telemetry::laps_total::LapsTotal;
 use crate::telemetry::player_car_class::PlayerCarClass;
 use crate::telemetry::player_lap_times::PlayerLapTimes;
 use crate::telemetry::position::Position;
+use crate::telemetry::position_history::PositionHistory;
 use crate::telemetry::positions_total::PositionsTotal;
 use crate::telemetry::proximity::Proximity;
+use crate::telemetry::qualify_standings::QualifyStandings;
 use crate::telemetry::race_laps::RaceLaps;
+use crate::telemetry::rating_projection::RatingProjection;
 use crate::telemetry::relative::Relative;
 use crate::telemetry::rpm::Rpm;
+use crate::telemetry::sector_times::SectorTimes;
 use crate::telemetry::session_state::SessionState;
 use crate::telemetry::session_time::SessionTime;
 use crate::telemetry::session_time_total::SessionTimeTotal;
 use crate::telemetry::session_type::SessionType;
+use crate::telemetry::spectating::Spectating;
 use crate::telemetry::speed::Speed;
+use crate::telemetry::spotter::Spotter;
 use crate::telemetry::standings::Standings;
 use crate::telemetry::strength_of_field::StrengthOfField;
 use crate::telemetry::telemetry_graph::TelemetryGraph;
 use crate::telemetry::telemetry_reference::TelemetryReference;
 use crate::telemetry::track_id::TrackId;
 use crate::telemetry::track_map::TrackMap;
+use crate::telemetry::weather::Weather;
 
 #[enum_dispatch(TelemetryEvent)]
 pub trait EmittableEvent {
@@ -91,9 +101,12 @@ erased_serde::serialize_trait_object!(EmittableValue);
 #[enum_dispatch]
 pub enum TelemetryEvent {
     Active(Active),
+    ClassStandings(ClassStandings),
     CurrentTime(CurrentTime),
     DeltaLastTime(DeltaLastTime),
     DeltaBestTime(DeltaBestTime),
+    Flags(Flags),
+    Fuel(Fuel),
     GapNext(GapNext),
     GapPrev(GapPrev),
     Gear(Gear),
@@ -106,21 +119,28 @@ pub enum TelemetryEvent {
     LapsTotal(LapsTotal),
     PlayerLapTimes(PlayerLapTimes),
     Position(Position),
+    PositionHistory(PositionHistory),
     PositionsTotal(PositionsTotal),
     Proximity(Proximity),
+    QualifyStandings(QualifyStandings),
     RaceLaps(RaceLaps),
+    RatingProjection(RatingProjection),
     Relative(Relative),
     Rpm(Rpm),
+    SectorTimes(SectorTimes),
     SessionState(SessionState),
     SessionTime(SessionTime),
     SessionTimeTotal(SessionTimeTotal),
+    Spectating(Spectating),
     Speed(Speed),
+    Spotter(Spotter),
     Standings(Standings),
     StrengthOfField(StrengthOfField),
     TelemetryGraph(TelemetryGraph),
     TelemetryReference(TelemetryReference),
     TrackId(TrackId),
     TrackMap(TrackMap),
+    Weather(Weather),
     PlayerCarClass(PlayerCarClass),
     FastestLap(FastestLap),
     SessionType(SessionType),
@@ -1,6 +1,6 @@
 use log::error;
 
-use crate::WS_SERVER;
+use crate::websocket::server::WS_SERVER;
 
 use super::ws_event::WsEvent;
 
@@ -1,16 +1,16 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap},
+    net::SocketAddr,
     str::FromStr,
 };
 
-use eyre::Result;
+use eyre::{Result, eyre};
 use log::{error, info};
-use strum::IntoEnumIterator;
 
 use crate::{
-    WS_SERVER,
     emitter::emittable_event::{EmittableEvent, EmittableValue, TelemetryEvent},
     session::session_data::SessionData,
+    websocket::server::WS_SERVER,
 };
 
 use super::ws_event::WsEvent;
@@ -26,11 +26,18 @@ pub enum TelemetryRecordingState {
     Finished,
 }
 
+struct LatestEvent {
+    value: Box<dyn EmittableValue>,
+    /// Bumped on every change so clients can tell whether they have seen the value
+    version: u64,
+}
+
 #[derive(Default)]
 pub struct TelemetryEmitter {
-    latest_events: HashMap<String, Box<dyn EmittableValue>>,
-    registered_events: HashSet<String>,
-    forced_events: HashSet<String>,
+    latest_events: HashMap<String, LatestEvent>,
+    /// Subscribed events of every WebSocket client with the version last sent to it,
+    /// `None` until the client gets the current value
+    subscriptions: HashMap<SocketAddr, HashMap<String, Option<u64>>>,
     recording_state: TelemetryRecordingState,
     recording_last_lap_dist: u32,
 }
@@ -40,7 +47,9 @@ impl TelemetryEmitter {
         let mut should_start_recording = false;
         let mut should_record = false;
         let mut should_stop_recording = false;
-        if self.recording_state == TelemetryRecordingState::WaitingForStart
+        if self.recording_state == TelemetryRecordingState::InProgress && !session.is_on_track {
+            // paused while in the garage or being towed, the lap continues once back in the car
+        } else if self.recording_state == TelemetryRecordingState::WaitingForStart
             && session.lap_dist <= TELEMETRY_RECORDING_START_DISTANCE
         {
             self.recording_state = TelemetryRecordingState::InProgress;
@@ -65,7 +74,12 @@ impl TelemetryEmitter {
                 return Ok(());
             }
         };
-        for event in &self.registered_events {
+        let subscribed_events = self
+            .subscriptions
+            .values()
+            .flat_map(|events| events.keys().cloned())
+            .collect::<BTreeSet<String>>();
+        for event in &subscribed_events {
             let telemetry_event = TelemetryEvent::from_str(event).ok();
             if telemetry_event.is_none() {
                 continue;
@@ -75,23 +89,45 @@ impl TelemetryEmitter {
                 continue;
             }
             let event_data = telemetry_event.get_event(session);
-            let latest_value = self.latest_events.get(event);
 
-            let should_emit = telemetry_event.is_forced()
-                || self.forced_events.contains(event)
-                || latest_value.is_none()
-                || !latest_value.is_some_and(|v| v.equals(event_data.as_ref()));
-
-            if should_emit {
-                // Emit via WebSocket if available
+            let version = match self.latest_events.get_mut(event) {
+                Some(latest)
+                    if !telemetry_event.is_forced() && latest.value.equals(event_data.as_ref()) =>
+                {
+                    latest.version
+                }
+                Some(latest) => {
+                    latest.value = event_data;
+                    latest.version += 1;
+                    latest.version
+                }
+                None => {
+                    self.latest_events.insert(
+                        event.to_string(),
+                        LatestEvent {
+                            value: event_data,
+                            version: 0,
+                        },
+                    );
+                    0
+                }
+            };
+
+            let mut receivers = Vec::new();
+            for (client, events) in self.subscriptions.iter_mut() {
+                if let Some(sent_version) = events.get_mut(event)
+                    && *sent_version != Some(version)
+                {
+                    *sent_version = Some(version);
+                    receivers.push(*client);
+                }
+            }
+            if !receivers.is_empty() {
                 let ws_event = WsEvent {
                     event: event.as_str(),
-                    data: event_data.as_ref(),
+                    data: self.latest_events[event].value.as_ref(),
                 };
-                ws_server.broadcast(&ws_event);
-
-                self.latest_events.insert(event.to_string(), event_data);
-                self.forced_events.remove(event);
+                ws_server.send_to(&receivers, &ws_event);
             }
 
             if should_start_recording {
@@ -107,31 +143,50 @@ impl TelemetryEmitter {
         Ok(())
     }
 
-    pub fn register(&mut self, event: &str) {
+    /// The client gets the current value on the next emit even if it hasn't changed
+    pub fn subscribe(&mut self, client: SocketAddr, event: &str) -> Result<()> {
         if TelemetryEvent::from_str(event).is_err() {
-            error!("Event {} is not supported", event);
-            return;
+            return Err(eyre!("Event {} is not supported", event));
         }
-        self.registered_events.insert(event.to_owned());
-        self.forced_events.insert(event.to_owned());
+        self.subscriptions
+            .entry(client)
+            .or_default()
+            .insert(event.to_owned(), None);
+        Ok(())
     }
 
-    pub fn unregister(&mut self, event: &str) {
-        match self.registered_events.remove(event) {
-            true => {
-                self.latest_events.remove(event);
-                self.forced_events.remove(event);
-            }
-            false => {
-                error!("Event {} is not registered", event);
-            }
+    pub fn unsubscribe(&mut self, client: SocketAddr, event: &str) -> Result<()> {
+        let subscribed = self
+            .subscriptions
+            .get_mut(&client)
+            .is_some_and(|events| events.remove(event).is_some());
+        if !subscribed {
+            return Err(eyre!("Event {} is not subscribed", event));
         }
+        self.remove_unsubscribed_events();
+        Ok(())
+    }
+
+    pub fn remove_client(&mut self, client: SocketAddr) {
+        self.subscriptions.remove(&client);
+        self.remove_unsubscribed_events();
+    }
+
+    fn remove_unsubscribed_events(&mut self) {
+        let subscriptions = &self.subscriptions;
+        self.latest_events.retain(|event, _| {
+            subscriptions
+                .values()
+                .any(|events| events.contains_key(event))
+        });
     }
 
     pub fn reset(&mut self) {
         self.latest_events.clear();
-        for event in TelemetryEvent::iter() {
-            self.forced_events.insert(event.to_string());
+        for events in self.subscriptions.values_mut() {
+            events
+                .values_mut()
+                .for_each(|sent_version| *sent_version = None);
         }
     }
 
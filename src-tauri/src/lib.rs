@@ -0,0 +1,11 @@
+pub mod db;
+pub mod emitter;
+#[cfg(feature = "gui")]
+pub mod overlay_manager;
+pub mod pipeline;
+pub mod session;
+pub mod settings;
+pub mod source;
+pub mod telemetry;
+pub mod util;
+pub mod websocket;
@@ -1,21 +1,12 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-pub mod db;
-pub mod emitter;
-pub mod overlay_manager;
-pub mod session;
-pub mod settings;
-pub mod telemetry;
-pub mod util;
-pub mod websocket;
-
-use eyre::{OptionExt, Result};
-use log::{debug, error, info, warn};
-use simetry::iracing::Client;
-use std::{backtrace::Backtrace, sync::OnceLock, time::Duration};
+use eyre::Result;
+use futures_util::future::BoxFuture;
+use log::{error, info, warn};
+use std::{backtrace::Backtrace, sync::Arc};
 use tauri::{
-    Manager, async_runtime,
+    Manager,
     menu::{MenuBuilder, MenuItemBuilder},
     tray::TrayIconBuilder,
 };
@@ -25,35 +16,39 @@ use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut,
 use tauri_plugin_log::{Target, TargetKind};
 use tokio::sync::Mutex;
 
-use crate::emitter::telemetry_emitter::TelemetryEmitter;
-use crate::emitter::telemetry_emitter::TelemetryRecordingState;
-use crate::overlay_manager::{AVAILABLE_OVERLAYS, OverlayManager};
-use crate::session::session_data::SessionData;
-use crate::settings::overlays::lap_times::LapTimesOverlaySettings;
-use crate::settings::overlays::main::MainOverlaySettings;
-use crate::settings::overlays::proximity::ProximityOverlaySettings;
-use crate::settings::overlays::relative::RelativeOverlaySettings;
-use crate::settings::overlays::standings::StandingsOverlaySettings;
-use crate::settings::overlays::subtimer::SubTimerOverlaySettings;
-use crate::settings::overlays::telemetry::TelemetryOverlaySettings;
-use crate::settings::overlays::telemetry_reference::TelemetryReferenceOverlaySettings;
-use crate::settings::overlays::timer::TimerOverlaySettings;
-use crate::settings::overlays::track_map::TrackMapOverlaySettings;
-use crate::telemetry::telemetry_reference::BrakePoint;
-use crate::telemetry::telemetry_reference::TelemetryReference;
-use crate::telemetry::telemetry_reference::TelemetryReferenceOutput;
-use crate::util::settings_helper::{get_settings, set_settings};
-use crate::websocket::{WS_SERVER, WebSocketServer};
+use iracehud_lib::db::{DATABASE, Database};
+use iracehud_lib::overlay_manager::OverlayManager;
+use iracehud_lib::pipeline::{Pipeline, SourceMode};
+use iracehud_lib::settings::network_settings::{NetworkSettings, generate_token};
+use iracehud_lib::settings::overlay_settings::AVAILABLE_OVERLAYS;
+use iracehud_lib::settings::overlays::fuel::FuelOverlaySettings;
+use iracehud_lib::settings::overlays::lap_times::LapTimesOverlaySettings;
+use iracehud_lib::settings::overlays::main::MainOverlaySettings;
+use iracehud_lib::settings::overlays::proximity::ProximityOverlaySettings;
+use iracehud_lib::settings::overlays::relative::RelativeOverlaySettings;
+use iracehud_lib::settings::overlays::standings::StandingsOverlaySettings;
+use iracehud_lib::settings::overlays::subtimer::SubTimerOverlaySettings;
+use iracehud_lib::settings::overlays::telemetry::TelemetryOverlaySettings;
+use iracehud_lib::settings::overlays::telemetry_reference::TelemetryReferenceOverlaySettings;
+use iracehud_lib::settings::overlays::timer::TimerOverlaySettings;
+use iracehud_lib::settings::overlays::track_map::TrackMapOverlaySettings;
+use iracehud_lib::settings::settings_provider::SETTINGS_PROVIDER;
+use iracehud_lib::settings::store_settings_provider::StoreSettingsProvider;
+use iracehud_lib::source::replay_source::ReplaySpeed;
+use iracehud_lib::source::synthetic_session::SyntheticConfig;
+use iracehud_lib::source::synthetic_source::SyntheticSource;
+use iracehud_lib::telemetry::telemetry_reference::{
+    TelemetryReferenceOutput, get_reference_points,
+};
+use iracehud_lib::util::settings_helper::{get_settings, set_settings};
+use iracehud_lib::websocket::control_handler::{ControlHandler, OverlayLock};
+use iracehud_lib::websocket::discovery::{Discovery, DiscoveryInfo};
+use iracehud_lib::websocket::server::{WS_SERVER, WebSocketServer};
+use iracehud_lib::websocket::static_files::{StaticFile, StaticFiles};
 
 #[cfg(not(debug_assertions))]
 use tauri_plugin_updater::UpdaterExt;
 
-static APP_HANDLE: OnceLock<tauri::AppHandle> = OnceLock::new();
-
-const RETRY_TIMEOUT_SECS: u64 = 5;
-const SESSION_UPDATE_PERIOD_MILLIS: u64 = 25;
-const SLOW_VAR_RESET_TICKS: u32 = 50;
-
 fn open_settings_window(app_handle: tauri::AppHandle) {
     match app_handle.get_webview_window("settings") {
         Some(window) => {
@@ -93,11 +88,89 @@ async fn lock_unlock_overlays_impl(app_handle: tauri::AppHandle) {
     overlay_manager.set_locked_unlocked();
 }
 
+async fn get_overlays_locked_impl(app_handle: tauri::AppHandle) -> bool {
+    let overlay_manager = app_handle.try_state::<Mutex<OverlayManager>>();
+    match overlay_manager {
+        Some(overlay_manager) => {
+            let overlay_manager = overlay_manager.lock().await;
+            overlay_manager.get_locked()
+        }
+        None => true,
+    }
+}
+
+/// Lets WebSocket clients lock and unlock the overlay windows
+struct AppOverlayLock(tauri::AppHandle);
+
+impl OverlayLock for AppOverlayLock {
+    fn lock_unlock(&self) -> BoxFuture<'_, bool> {
+        Box::pin(async move {
+            lock_unlock_overlays_impl(self.0.clone()).await;
+            get_overlays_locked_impl(self.0.clone()).await
+        })
+    }
+
+    fn is_locked(&self) -> BoxFuture<'_, bool> {
+        Box::pin(get_overlays_locked_impl(self.0.clone()))
+    }
+}
+
+/// Serves the embedded frontend, so overlays can be added to OBS as browser sources
+struct AppAssets(tauri::AppHandle);
+
+impl StaticFiles for AppAssets {
+    fn get(&self, path: &str) -> Option<StaticFile> {
+        let asset = self.0.asset_resolver().get(path.to_string())?;
+        Some(StaticFile {
+            bytes: asset.bytes,
+            content_type: asset.mime_type,
+        })
+    }
+}
+
 async fn toggle_force_active_impl(app_handle: tauri::AppHandle) {
-    let force_active_state = app_handle.try_state::<Mutex<bool>>();
-    if let Some(force_active_state) = force_active_state {
-        let mut force_active = force_active_state.lock().await;
-        *force_active = !*force_active;
+    let pipeline = app_handle.state::<Arc<Pipeline>>();
+    pipeline.toggle_force_active().await;
+}
+
+async fn toggle_session_capture_impl(app_handle: tauri::AppHandle) -> Result<()> {
+    let captures_dir = app_handle.path().app_data_dir()?.join("captures");
+    let pipeline = app_handle.state::<Arc<Pipeline>>();
+    pipeline.toggle_session_capture(&captures_dir).await
+}
+
+/// Command line the app was started with, its source is reopened when a demo session stops
+struct StartupArgs(Vec<String>);
+
+fn toggle_demo_session_impl(app_handle: tauri::AppHandle) {
+    let pipeline = app_handle.state::<Arc<Pipeline>>();
+    if pipeline.is_synthetic_active() {
+        info!("Stopping demo session");
+        // replays start over, an app started with --demo goes live
+        let mode = match SourceMode::from_args(&app_handle.state::<StartupArgs>().0) {
+            Ok(SourceMode::Synthetic(_)) => SourceMode::Live,
+            Ok(mode) => mode,
+            Err(err) => {
+                error!(
+                    "Failed to reopen the source used before the demo: {:?}",
+                    err
+                );
+                SourceMode::Live
+            }
+        };
+        pipeline.start(mode);
+    } else {
+        info!("Starting demo session");
+        pipeline.start(SourceMode::Synthetic(Box::new(SyntheticSource::new(
+            SyntheticConfig::default(),
+            ReplaySpeed::Multiplier(1.0),
+        ))));
+    }
+}
+
+async fn toggle_session_capture_task(app_handle: tauri::AppHandle) {
+    if let Err(err) = toggle_session_capture_impl(app_handle).await {
+        error!("Failed to toggle session capture: {:?}", err);
     }
 }
 
@@ -114,6 +187,8 @@ async fn main() {
     let ctrl_f10_shortcut = Shortcut::new(Some(Modifiers::CONTROL), Code::F10);
     let ctrl_f9_shortcut = Shortcut::new(Some(Modifiers::CONTROL), Code::F9);
 
+    let args: Vec<String> = std::env::args().collect();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_opener::init())
@@ -180,15 +255,24 @@ async fn main() {
             }));
 
             // Initialize database synchronously
-            let app_handle = app.handle().clone();
+            let app_data_dir = app.path().app_data_dir()?;
             let (tx, rx) = std::sync::mpsc::sync_channel(1);
             tauri::async_runtime::spawn_blocking(move || {
                 tx.send(tauri::async_runtime::block_on(async {
-                    db::Database::new(&app_handle).await
+                    Database::new(&app_data_dir).await
                 })).unwrap();
             });
             let database = rx.recv().unwrap();
-            app.manage(db::DatabaseState(database.pool));
+            if DATABASE.set(database).is_err() {
+                error!("Database already initialized");
+            }
+
+            if SETTINGS_PROVIDER
+                .set(Box::new(StoreSettingsProvider::new(app.handle().clone())))
+                .is_err()
+            {
+                error!("Settings provider already initialized");
+            }
 
             #[cfg(not(debug_assertions))]
             {
@@ -216,11 +300,19 @@ async fn main() {
             let toggle_force_active =
                 MenuItemBuilder::with_id("toggle_force_active", "Toggle Forceful Overlays Activation (Ctrl+F9)")
                     .build(app)?;
+            let toggle_demo_session =
+                MenuItemBuilder::with_id("toggle_demo_session", "Start/Stop Demo Session")
+                    .build(app)?;
+            let toggle_session_capture =
+                MenuItemBuilder::with_id("toggle_session_capture", "Start/Stop Session Capture")
+                    .build(app)?;
             let tray_menu = MenuBuilder::new(app)
                 .item(&version)
                 .item(&settings)
                 .item(&lock_unlock_overlays)
                 .item(&toggle_force_active)
+                .item(&toggle_demo_session)
+                .item(&toggle_session_capture)
                 .separator()
                 .item(&quit)
                 .build()?;
@@ -240,7 +332,7 @@ async fn main() {
                                     overlay_manager
                                         .lock()
                                         .await
-                                        .save_positions(&app_handle_clone);
+                                        .save_positions();
                                 }
                                 app_handle_clone.exit(0);
                             });
@@ -259,17 +351,33 @@ async fn main() {
                                 "Toggle forceful overlays activation menu item clicked, toggling forceful overlays activation"
                             );
                             tauri::async_runtime::spawn(toggle_force_active_impl(app_handle.clone()));
+                        } else if event.id().as_ref() == "toggle_demo_session" {
+                            info!("Toggle demo session menu item clicked, toggling demo session");
+                            toggle_demo_session_impl(app_handle.clone());
+                        } else if event.id().as_ref() == "toggle_session_capture" {
+                            info!("Toggle session capture menu item clicked, toggling session capture");
+                            tauri::async_runtime::spawn(toggle_session_capture_task(
+                                app_handle.clone(),
+                            ));
                         }
                     }
                 })
                 .title("iRaceHUD")
                 .build(app)?;
 
-            let emitter = TelemetryEmitter::default();
-            app.manage(Mutex::new(emitter));
+            let pipeline = Arc::new(Pipeline::default());
+            app.manage(pipeline.clone());
 
             // Initialize WebSocket server
-            let server = WebSocketServer::new();
+            let app_data_dir = app.path().app_data_dir()?;
+            let network_settings = NetworkSettings::load(&app_data_dir);
+            let control_handler = ControlHandler::new(pipeline.clone())
+                .with_overlay_lock(Box::new(AppOverlayLock(app.handle().clone())));
+            let server = WebSocketServer::new()
+                .with_control_handler(control_handler)
+                .with_token(&network_settings.token)
+                .with_discovery_file(app_data_dir.join("discovery.json"))
+                .with_static_files(AppAssets(app.handle().clone()));
             let server_clone = server.clone();
             let _ = WS_SERVER
                 .set(server)
@@ -277,14 +385,9 @@ async fn main() {
 
             // Run WebSocket server in a separate task
             tokio::spawn(async move {
-                server_clone.run("127.0.0.1:0").await;
+                server_clone.run(&network_settings.bind_addr()).await;
             });
 
-            APP_HANDLE.set(app.handle().clone()).unwrap();
-
-            let force_active = false;
-            app.manage(Mutex::new(force_active));
-
             let mut overlay_manager = OverlayManager::new();
 
             for overlay in AVAILABLE_OVERLAYS {
@@ -293,11 +396,9 @@ async fn main() {
 
             app.manage(Mutex::new(overlay_manager));
 
-            async_runtime::spawn(async move {
-                if let Err(err) = connect().await {
-                    error!("Failed to connect: {:?}", err);
-                }
-            });
+            let mode = SourceMode::from_args(&args)?;
+            app.manage(StartupArgs(args.clone()));
+            pipeline.start(mode);
 
             app.global_shortcut()
                 .register_multiple([ctrl_f11_shortcut, ctrl_f10_shortcut, ctrl_f9_shortcut])?;
@@ -305,11 +406,12 @@ async fn main() {
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
-            register_event_emitter,
-            unregister_event_emitter,
             set_autostart,
             get_autostart,
-            get_ws_port,
+            get_ws_discovery,
+            get_network_settings,
+            set_network_settings,
+            regenerate_network_token,
             get_lap_times_overlay_settings,
             set_lap_times_overlay_settings,
             get_main_overlay_settings,
@@ -330,67 +432,23 @@ async fn main() {
             set_timer_overlay_settings,
             get_track_map_overlay_settings,
             set_track_map_overlay_settings,
+            get_fuel_overlay_settings,
+            set_fuel_overlay_settings,
             get_app_version,
             lock_unlock_overlays,
             get_overlays_locked,
             record_telemetry,
             get_telemetry_reference_points,
+            toggle_demo_session,
+            get_demo_session_active,
+            toggle_session_capture,
+            get_session_capture_active,
+            step_replay,
         ])
         .run(tauri::generate_context!())
         .expect("Error while running tauri application");
 }
 
-async fn connect() -> Result<()> {
-    loop {
-        info!("Start iRacing");
-        let mut client = Client::connect(Duration::from_secs(RETRY_TIMEOUT_SECS)).await;
-        let mut data = SessionData::default();
-        let mut slow_var_ticks: u32 = SLOW_VAR_RESET_TICKS;
-        while let Some(sim_state) = client.next_sim_state().await {
-            slow_var_ticks += 1;
-
-            let handle = APP_HANDLE.get().ok_or_eyre("Failed to get app handle")?;
-            let mut force_active = false;
-
-            {
-                let force_active_state = handle.try_state::<Mutex<bool>>();
-                if let Some(force_active_state) = force_active_state {
-                    force_active = *force_active_state.lock().await;
-                }
-            }
-
-            if !force_active {
-                let emitter_state = handle.state::<Mutex<TelemetryEmitter>>();
-                let emitter = emitter_state.lock().await;
-                if emitter.get_recording_state() == TelemetryRecordingState::InProgress
-                    || emitter.get_recording_state() == TelemetryRecordingState::WaitingForStart
-                {
-                    force_active = true;
-                }
-            }
-
-            let should_process_slow = slow_var_ticks >= SLOW_VAR_RESET_TICKS;
-
-            let result = data.process_tick(&sim_state, should_process_slow, force_active);
-
-            if should_process_slow {
-                slow_var_ticks = 0;
-            }
-
-            let emitter_state = handle.state::<Mutex<TelemetryEmitter>>();
-            let mut emitter = emitter_state.lock().await;
-
-            if result == session::session_data::ProcessTickResult::StateChanged {
-                emitter.reset();
-            }
-
-            emitter.emit_all(&data).await?;
-
-            tokio::time::sleep(Duration::from_millis(SESSION_UPDATE_PERIOD_MILLIS)).await;
-        }
-    }
-}
-
 #[cfg(not(debug_assertions))]
 async fn update(app: tauri::AppHandle) -> Result<()> {
     let updater = app
@@ -430,21 +488,6 @@ async fn update(app: tauri::AppHandle) -> Result<()> {
     Ok(())
 }
 
-#[tauri::command]
-async fn register_event_emitter(app: tauri::AppHandle, event: String) {
-    debug!("Registering event emitter for {}", event);
-    let emitter_state = app.app_handle().state::<Mutex<TelemetryEmitter>>();
-    let mut emitter = emitter_state.lock().await;
-    emitter.register(&event);
-}
-
-#[tauri::command]
-async fn unregister_event_emitter(app: tauri::AppHandle, event: String) {
-    let emitter_state = app.app_handle().state::<Mutex<TelemetryEmitter>>();
-    let mut emitter = emitter_state.lock().await;
-    emitter.unregister(&event);
-}
-
 #[tauri::command]
 async fn set_autostart(app: tauri::AppHandle, enabled: bool) {
     info!("Setting autostart to {}", enabled);
@@ -467,113 +510,163 @@ async fn get_autostart(app: tauri::AppHandle) -> bool {
 }
 
 #[tauri::command]
-async fn get_ws_port() -> Option<u16> {
-    websocket::WebSocketServer::get_port()
+async fn get_ws_discovery() -> Option<DiscoveryInfo> {
+    WebSocketServer::get_discovery().map(Discovery::info)
 }
 
 #[tauri::command]
-async fn get_lap_times_overlay_settings(app: tauri::AppHandle) -> LapTimesOverlaySettings {
-    get_settings(app, "lap_times")
+async fn get_network_settings(app: tauri::AppHandle) -> NetworkSettings {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data dir");
+    NetworkSettings::load(&app_data_dir)
 }
 
 #[tauri::command]
-async fn set_lap_times_overlay_settings(app: tauri::AppHandle, settings: LapTimesOverlaySettings) {
-    set_settings(app, "lap_times", settings);
+async fn set_network_settings(
+    app: tauri::AppHandle,
+    settings: NetworkSettings,
+) -> Result<(), String> {
+    if let Err(err) = settings.validate() {
+        error!("Rejected network settings: {}", err);
+        return Err(err.to_string());
+    }
+    info!("Setting network settings, applied on restart");
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data dir");
+    if let Err(err) = settings.save(&app_data_dir) {
+        error!("Failed to save network settings: {:?}", err);
+    }
+    Ok(())
 }
 
 #[tauri::command]
-async fn get_main_overlay_settings(app: tauri::AppHandle) -> MainOverlaySettings {
-    get_settings(app, "main")
+async fn regenerate_network_token(app: tauri::AppHandle) -> NetworkSettings {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data dir");
+    let settings = NetworkSettings {
+        token: generate_token(),
+        ..NetworkSettings::load(&app_data_dir)
+    };
+    if let Err(err) = settings.save(&app_data_dir) {
+        error!("Failed to save network settings: {:?}", err);
+    }
+    settings
 }
 
 #[tauri::command]
-async fn set_main_overlay_settings(app: tauri::AppHandle, settings: MainOverlaySettings) {
-    set_settings(app, "main", settings);
+async fn get_lap_times_overlay_settings() -> LapTimesOverlaySettings {
+    get_settings("lap_times")
 }
 
 #[tauri::command]
-async fn get_proximity_overlay_settings(app: tauri::AppHandle) -> ProximityOverlaySettings {
-    get_settings(app, "proximity")
+async fn set_lap_times_overlay_settings(settings: LapTimesOverlaySettings) {
+    set_settings("lap_times", settings);
 }
 
 #[tauri::command]
-async fn set_proximity_overlay_settings(app: tauri::AppHandle, settings: ProximityOverlaySettings) {
-    set_settings(app, "proximity", settings);
+async fn get_main_overlay_settings() -> MainOverlaySettings {
+    get_settings("main")
 }
 
 #[tauri::command]
-async fn get_relative_overlay_settings(app: tauri::AppHandle) -> RelativeOverlaySettings {
-    get_settings(app, "relative")
+async fn set_main_overlay_settings(settings: MainOverlaySettings) {
+    set_settings("main", settings);
 }
 
 #[tauri::command]
-async fn set_relative_overlay_settings(app: tauri::AppHandle, settings: RelativeOverlaySettings) {
-    set_settings(app, "relative", settings);
+async fn get_proximity_overlay_settings() -> ProximityOverlaySettings {
+    get_settings("proximity")
 }
 
 #[tauri::command]
-async fn get_standings_overlay_settings(app: tauri::AppHandle) -> StandingsOverlaySettings {
-    get_settings(app, "standings")
+async fn set_proximity_overlay_settings(settings: ProximityOverlaySettings) {
+    set_settings("proximity", settings);
 }
 
 #[tauri::command]
-async fn set_standings_overlay_settings(app: tauri::AppHandle, settings: StandingsOverlaySettings) {
-    set_settings(app, "standings", settings);
+async fn get_relative_overlay_settings() -> RelativeOverlaySettings {
+    get_settings("relative")
 }
 
 #[tauri::command]
-async fn get_subtimer_overlay_settings(app: tauri::AppHandle) -> SubTimerOverlaySettings {
-    get_settings(app, "subtimer")
+async fn set_relative_overlay_settings(settings: RelativeOverlaySettings) {
+    set_settings("relative", settings);
 }
 
 #[tauri::command]
-async fn set_subtimer_overlay_settings(app: tauri::AppHandle, settings: SubTimerOverlaySettings) {
-    set_settings(app, "subtimer", settings);
+async fn get_standings_overlay_settings() -> StandingsOverlaySettings {
+    get_settings("standings")
 }
 
 #[tauri::command]
-async fn get_telemetry_overlay_settings(app: tauri::AppHandle) -> TelemetryOverlaySettings {
-    get_settings(app, "telemetry")
+async fn set_standings_overlay_settings(settings: StandingsOverlaySettings) {
+    set_settings("standings", settings);
 }
 
 #[tauri::command]
-async fn set_telemetry_overlay_settings(app: tauri::AppHandle, settings: TelemetryOverlaySettings) {
-    set_settings(app, "telemetry", settings);
+async fn get_subtimer_overlay_settings() -> SubTimerOverlaySettings {
+    get_settings("subtimer")
 }
 
 #[tauri::command]
-async fn get_timer_overlay_settings(app: tauri::AppHandle) -> TimerOverlaySettings {
-    get_settings(app, "timer")
+async fn set_subtimer_overlay_settings(settings: SubTimerOverlaySettings) {
+    set_settings("subtimer", settings);
 }
 
 #[tauri::command]
-async fn set_timer_overlay_settings(app: tauri::AppHandle, settings: TimerOverlaySettings) {
-    set_settings(app, "timer", settings);
+async fn get_telemetry_overlay_settings() -> TelemetryOverlaySettings {
+    get_settings("telemetry")
 }
 
 #[tauri::command]
-async fn get_track_map_overlay_settings(app: tauri::AppHandle) -> TrackMapOverlaySettings {
-    get_settings(app, "track_map")
+async fn set_telemetry_overlay_settings(settings: TelemetryOverlaySettings) {
+    set_settings("telemetry", settings);
 }
 
 #[tauri::command]
-async fn set_track_map_overlay_settings(app: tauri::AppHandle, settings: TrackMapOverlaySettings) {
-    set_settings(app, "track_map", settings);
+async fn get_timer_overlay_settings() -> TimerOverlaySettings {
+    get_settings("timer")
 }
 
 #[tauri::command]
-async fn get_telemetry_reference_overlay_settings(
-    app: tauri::AppHandle,
-) -> TelemetryReferenceOverlaySettings {
-    get_settings(app, "telemetry_reference")
+async fn set_timer_overlay_settings(settings: TimerOverlaySettings) {
+    set_settings("timer", settings);
 }
 
 #[tauri::command]
-async fn set_telemetry_reference_overlay_settings(
-    app: tauri::AppHandle,
-    settings: TelemetryReferenceOverlaySettings,
-) {
-    set_settings(app, "telemetry_reference", settings);
+async fn get_track_map_overlay_settings() -> TrackMapOverlaySettings {
+    get_settings("track_map")
+}
+
+#[tauri::command]
+async fn set_track_map_overlay_settings(settings: TrackMapOverlaySettings) {
+    set_settings("track_map", settings);
+}
+
+#[tauri::command]
+async fn get_fuel_overlay_settings() -> FuelOverlaySettings {
+    get_settings("fuel")
+}
+
+#[tauri::command]
+async fn set_fuel_overlay_settings(settings: FuelOverlaySettings) {
+    set_settings("fuel", settings);
+}
+
+#[tauri::command]
+async fn get_telemetry_reference_overlay_settings() -> TelemetryReferenceOverlaySettings {
+    get_settings("telemetry_reference")
+}
+
+#[tauri::command]
+async fn set_telemetry_reference_overlay_settings(settings: TelemetryReferenceOverlaySettings) {
+    set_settings("telemetry_reference", settings);
 }
 
 #[tauri::command]
@@ -588,77 +681,48 @@ async fn lock_unlock_overlays(app: tauri::AppHandle) {
 
 #[tauri::command]
 async fn get_overlays_locked(app: tauri::AppHandle) -> bool {
-    let overlay_manager = app.try_state::<Mutex<OverlayManager>>();
-    match overlay_manager {
-        Some(overlay_manager) => {
-            let overlay_manager = overlay_manager.lock().await;
-            overlay_manager.get_locked()
-        }
-        None => true,
-    }
+    get_overlays_locked_impl(app).await
 }
 
 #[tauri::command]
 async fn record_telemetry(app: tauri::AppHandle) {
     info!("Recording telemetry");
-    let emitter_state = app.app_handle().state::<Mutex<TelemetryEmitter>>();
-    let mut emitter = emitter_state.lock().await;
+    let pipeline = app.state::<Arc<Pipeline>>();
+    let mut emitter = pipeline.emitter.lock().await;
     emitter.enable_recording();
 }
 
 #[tauri::command]
-async fn get_telemetry_reference_points(
-    app: tauri::AppHandle,
-    track_id: u32,
-) -> TelemetryReferenceOutput {
-    let db = app.try_state::<db::DatabaseState>().unwrap();
-
-    let stmt = r#"
-        SELECT recording_id
-        FROM telemetry_reference_meta
-        WHERE track_id = $1;
-    "#;
-
-    // fetch recording_id if it exists
-    let recording_id = sqlx::query_scalar::<_, u32>(stmt)
-        .bind(track_id)
-        .fetch_optional(&db.0)
-        .await
-        .unwrap();
-
-    if recording_id.is_none() {
-        return TelemetryReferenceOutput::default();
-    }
+async fn get_telemetry_reference_points(track_id: u32) -> TelemetryReferenceOutput {
+    let db = DATABASE.get().unwrap();
+    get_reference_points(&db.pool, track_id).await
+}
+
+#[tauri::command]
+async fn toggle_demo_session(app: tauri::AppHandle) {
+    toggle_demo_session_impl(app);
+}
+
+#[tauri::command]
+async fn get_demo_session_active(app: tauri::AppHandle) -> bool {
+    let pipeline = app.state::<Arc<Pipeline>>();
+    pipeline.is_synthetic_active()
+}
 
-    let recording_id = recording_id.unwrap();
-    let stmt = r#"
-        SELECT lap_dist, throttle, brake, steering_angle, gear
-        FROM telemetry_reference_data
-        WHERE recording_id = $1
-        ORDER BY lap_dist ASC;
-    "#;
-
-    let reference = sqlx::query_as::<_, TelemetryReference>(stmt)
-        .bind(recording_id)
-        .fetch_all(&db.0)
-        .await
-        .unwrap();
-
-    let stmt = r#"
-        SELECT lap_dist
-        FROM telemetry_reference_brake_points
-        WHERE recording_id = $1
-        ORDER BY lap_dist ASC;
-    "#;
-
-    let brake_points = sqlx::query_as::<_, BrakePoint>(stmt)
-        .bind(recording_id)
-        .fetch_all(&db.0)
-        .await
-        .unwrap();
-
-    TelemetryReferenceOutput {
-        reference,
-        brake_points,
+#[tauri::command]
+async fn toggle_session_capture(app: tauri::AppHandle) {
+    toggle_session_capture_task(app).await;
+}
+
+#[tauri::command]
+async fn get_session_capture_active(app: tauri::AppHandle) -> bool {
+    let pipeline = app.state::<Arc<Pipeline>>();
+    pipeline.is_session_capture_active().await
+}
+
+#[tauri::command]
+async fn step_replay(app: tauri::AppHandle) {
+    if !app.state::<Arc<Pipeline>>().step() {
+        warn!("Replay is not running");
     }
 }
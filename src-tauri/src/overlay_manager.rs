@@ -4,9 +4,10 @@ use log::info;
 use tauri::{WebviewUrl, WebviewWindow, WebviewWindowBuilder};
 
 use crate::emitter::overlay_manager_emitter::emit_overlay_locked_unlocked;
+use crate::settings::overlay_settings::AVAILABLE_OVERLAYS;
 use crate::settings::overlays::common_settings::CommonSettings;
 use crate::settings::overlays::{
-    lap_times::LapTimesOverlaySettings, main::MainOverlaySettings,
+    fuel::FuelOverlaySettings, lap_times::LapTimesOverlaySettings, main::MainOverlaySettings,
     proximity::ProximityOverlaySettings, relative::RelativeOverlaySettings,
     standings::StandingsOverlaySettings, subtimer::SubTimerOverlaySettings,
     telemetry::TelemetryOverlaySettings, telemetry_reference::TelemetryReferenceOverlaySettings,
@@ -14,19 +15,6 @@ use crate::settings::overlays::{
 };
 use crate::util::settings_helper::{get_common_settings, get_settings, set_settings};
 
-pub const AVAILABLE_OVERLAYS: [&str; 10] = [
-    "main",
-    "standings",
-    "lap_times",
-    "proximity",
-    "relative",
-    "subtimer",
-    "telemetry",
-    "telemetry_reference",
-    "timer",
-    "track_map",
-];
-
 pub static OVERLAY_MANAGER: OnceLock<OverlayManager> = OnceLock::new();
 
 #[derive(Debug)]
@@ -54,35 +42,19 @@ impl OverlayManager {
         }
 
         let settings: CommonSettings = match overlay_name {
-            "main" => get_common_settings::<MainOverlaySettings>(app_handle.clone(), overlay_name),
-            "standings" => {
-                get_common_settings::<StandingsOverlaySettings>(app_handle.clone(), overlay_name)
-            }
-            "lap_times" => {
-                get_common_settings::<LapTimesOverlaySettings>(app_handle.clone(), overlay_name)
-            }
-            "proximity" => {
-                get_common_settings::<ProximityOverlaySettings>(app_handle.clone(), overlay_name)
-            }
-            "relative" => {
-                get_common_settings::<RelativeOverlaySettings>(app_handle.clone(), overlay_name)
-            }
-            "subtimer" => {
-                get_common_settings::<SubTimerOverlaySettings>(app_handle.clone(), overlay_name)
-            }
-            "telemetry" => {
-                get_common_settings::<TelemetryOverlaySettings>(app_handle.clone(), overlay_name)
-            }
-            "telemetry_reference" => get_common_settings::<TelemetryReferenceOverlaySettings>(
-                app_handle.clone(),
-                overlay_name,
-            ),
-            "timer" => {
-                get_common_settings::<TimerOverlaySettings>(app_handle.clone(), overlay_name)
-            }
-            "track_map" => {
-                get_common_settings::<TrackMapOverlaySettings>(app_handle.clone(), overlay_name)
+            "main" => get_common_settings::<MainOverlaySettings>(overlay_name),
+            "standings" => get_common_settings::<StandingsOverlaySettings>(overlay_name),
+            "lap_times" => get_common_settings::<LapTimesOverlaySettings>(overlay_name),
+            "proximity" => get_common_settings::<ProximityOverlaySettings>(overlay_name),
+            "relative" => get_common_settings::<RelativeOverlaySettings>(overlay_name),
+            "subtimer" => get_common_settings::<SubTimerOverlaySettings>(overlay_name),
+            "telemetry" => get_common_settings::<TelemetryOverlaySettings>(overlay_name),
+            "telemetry_reference" => {
+                get_common_settings::<TelemetryReferenceOverlaySettings>(overlay_name)
             }
+            "timer" => get_common_settings::<TimerOverlaySettings>(overlay_name),
+            "track_map" => get_common_settings::<TrackMapOverlaySettings>(overlay_name),
+            "fuel" => get_common_settings::<FuelOverlaySettings>(overlay_name),
             _ => panic!("Unknown overlay type: {}", overlay_name),
         };
 
@@ -136,97 +108,78 @@ impl OverlayManager {
         emit_overlay_locked_unlocked(self.locked);
     }
 
-    pub fn save_positions(&self, app_handle: &tauri::AppHandle) {
+    pub fn save_positions(&self) {
         for (overlay_name, window) in self.overlay_windows.iter() {
             let position = window.inner_position();
             if position.is_ok() {
                 let position = position.unwrap();
                 match overlay_name.as_str() {
                     "main" => {
-                        let mut settings =
-                            get_settings::<MainOverlaySettings>(app_handle.clone(), overlay_name);
+                        let mut settings = get_settings::<MainOverlaySettings>(overlay_name);
                         settings.common_settings.x = position.x as u32;
                         settings.common_settings.y = position.y as u32;
-                        set_settings(app_handle.clone(), overlay_name, settings);
+                        set_settings(overlay_name, settings);
                     }
                     "standings" => {
-                        let mut settings = get_settings::<StandingsOverlaySettings>(
-                            app_handle.clone(),
-                            overlay_name,
-                        );
+                        let mut settings = get_settings::<StandingsOverlaySettings>(overlay_name);
                         settings.common_settings.x = position.x as u32;
                         settings.common_settings.y = position.y as u32;
-                        set_settings(app_handle.clone(), overlay_name, settings);
+                        set_settings(overlay_name, settings);
                     }
                     "lap_times" => {
-                        let mut settings = get_settings::<LapTimesOverlaySettings>(
-                            app_handle.clone(),
-                            overlay_name,
-                        );
+                        let mut settings = get_settings::<LapTimesOverlaySettings>(overlay_name);
                         settings.common_settings.x = position.x as u32;
                         settings.common_settings.y = position.y as u32;
-                        set_settings(app_handle.clone(), overlay_name, settings);
+                        set_settings(overlay_name, settings);
                     }
                     "proximity" => {
-                        let mut settings = get_settings::<ProximityOverlaySettings>(
-                            app_handle.clone(),
-                            overlay_name,
-                        );
+                        let mut settings = get_settings::<ProximityOverlaySettings>(overlay_name);
                         settings.common_settings.x = position.x as u32;
                         settings.common_settings.y = position.y as u32;
-                        set_settings(app_handle.clone(), overlay_name, settings);
+                        set_settings(overlay_name, settings);
                     }
                     "relative" => {
-                        let mut settings = get_settings::<RelativeOverlaySettings>(
-                            app_handle.clone(),
-                            overlay_name,
-                        );
+                        let mut settings = get_settings::<RelativeOverlaySettings>(overlay_name);
                         settings.common_settings.x = position.x as u32;
                         settings.common_settings.y = position.y as u32;
-                        set_settings(app_handle.clone(), overlay_name, settings);
+                        set_settings(overlay_name, settings);
                     }
                     "subtimer" => {
-                        let mut settings = get_settings::<SubTimerOverlaySettings>(
-                            app_handle.clone(),
-                            overlay_name,
-                        );
+                        let mut settings = get_settings::<SubTimerOverlaySettings>(overlay_name);
                         settings.common_settings.x = position.x as u32;
                         settings.common_settings.y = position.y as u32;
-                        set_settings(app_handle.clone(), overlay_name, settings);
+                        set_settings(overlay_name, settings);
                     }
                     "telemetry" => {
-                        let mut settings = get_settings::<TelemetryOverlaySettings>(
-                            app_handle.clone(),
-                            overlay_name,
-                        );
+                        let mut settings = get_settings::<TelemetryOverlaySettings>(overlay_name);
                         settings.common_settings.x = position.x as u32;
                         settings.common_settings.y = position.y as u32;
-                        set_settings(app_handle.clone(), overlay_name, settings);
+                        set_settings(overlay_name, settings);
                     }
                     "telemetry_reference" => {
-                        let mut settings = get_settings::<TelemetryReferenceOverlaySettings>(
-                            app_handle.clone(),
-                            overlay_name,
-                        );
+                        let mut settings =
+                            get_settings::<TelemetryReferenceOverlaySettings>(overlay_name);
                         settings.common_settings.x = position.x as u32;
                         settings.common_settings.y = position.y as u32;
-                        set_settings(app_handle.clone(), overlay_name, settings);
+                        set_settings(overlay_name, settings);
                     }
                     "timer" => {
-                        let mut settings =
-                            get_settings::<TimerOverlaySettings>(app_handle.clone(), overlay_name);
+                        let mut settings = get_settings::<TimerOverlaySettings>(overlay_name);
                         settings.common_settings.x = position.x as u32;
                         settings.common_settings.y = position.y as u32;
-                        set_settings(app_handle.clone(), overlay_name, settings);
+                        set_settings(overlay_name, settings);
                     }
                     "track_map" => {
-                        let mut settings = get_settings::<TrackMapOverlaySettings>(
-                            app_handle.clone(),
-                            overlay_name,
-                        );
+                        let mut settings = get_settings::<TrackMapOverlaySettings>(overlay_name);
+                        settings.common_settings.x = position.x as u32;
+                        settings.common_settings.y = position.y as u32;
+                        set_settings(overlay_name, settings);
+                    }
+                    "fuel" => {
+                        let mut settings = get_settings::<FuelOverlaySettings>(overlay_name);
                         settings.common_settings.x = position.x as u32;
                         settings.common_settings.y = position.y as u32;
-                        set_settings(app_handle.clone(), overlay_name, settings);
+                        set_settings(overlay_name, settings);
                     }
                     _ => panic!("Unknown overlay type: {}", overlay_name),
                 }
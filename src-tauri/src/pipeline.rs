@@ -0,0 +1,257 @@
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex as StdMutex},
+    time::Duration,
+};
+
+use eyre::Result;
+use log::{error, info, warn};
+use tokio::sync::{Mutex, Notify};
+use tokio::task::JoinHandle;
+
+use crate::emitter::telemetry_emitter::{TelemetryEmitter, TelemetryRecordingState};
+use crate::session::session_data::{ProcessTickResult, SessionData};
+use crate::source::capture::CAPTURE_EXTENSION;
+use crate::source::capture_recorder::CaptureRecorder;
+use crate::source::ibt_source::IbtSource;
+use crate::source::iracing_source::IRacingSource;
+use crate::source::replay_source::{ReplaySource, ReplaySpeed};
+use crate::source::synthetic_session::SyntheticConfig;
+use crate::source::synthetic_source::SyntheticSource;
+use crate::source::telemetry_source::TelemetrySource;
+
+const RETRY_TIMEOUT_SECS: u64 = 5;
+const SLOW_VAR_RESET_TICKS: u32 = 50;
+
+/// Telemetry processing shared by the desktop app and the headless server
+#[derive(Default)]
+pub struct Pipeline {
+    pub emitter: Mutex<TelemetryEmitter>,
+    pub force_active: Mutex<bool>,
+    pub recorder: Mutex<Option<CaptureRecorder>>,
+    source_task: StdMutex<Option<SourceTask>>,
+}
+
+impl Pipeline {
+    pub async fn connect(&self) -> Result<()> {
+        loop {
+            info!("Start iRacing");
+            let mut source = IRacingSource::connect(Duration::from_secs(RETRY_TIMEOUT_SECS)).await;
+            self.run_source(&mut source).await?;
+        }
+    }
+
+    pub async fn run(&self, mode: SourceMode) -> Result<()> {
+        match mode {
+            SourceMode::Live => self.connect().await,
+            SourceMode::Ibt(mut source) => self.run_source(source.as_mut()).await,
+            SourceMode::Capture(mut source) => self.run_source(&mut source).await,
+            SourceMode::Synthetic(mut source) => self.run_source(source.as_mut()).await,
+        }
+    }
+
+    /// Runs the source in the background, replacing the one started before
+    pub fn start(self: &Arc<Self>, mode: SourceMode) {
+        let mut source_task = self.source_task.lock().unwrap();
+        if let Some(previous) = source_task.take() {
+            previous.handle.abort();
+        }
+        let synthetic = matches!(mode, SourceMode::Synthetic(_));
+        let stepper = mode.stepper();
+        let pipeline = self.clone();
+        let handle = tokio::spawn(async move {
+            if let Err(err) = pipeline.run(mode).await {
+                error!("Telemetry source failed: {:?}", err);
+            }
+        });
+        *source_task = Some(SourceTask {
+            handle,
+            synthetic,
+            stepper,
+        });
+    }
+
+    /// Advances a stepped replay or demo session by one tick, `false` if no such source runs
+    pub fn step(&self) -> bool {
+        let stepper = self
+            .source_task
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|task| task.stepper.clone());
+        match stepper {
+            Some(stepper) => {
+                stepper.notify_one();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_synthetic_active(&self) -> bool {
+        self.source_task
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|task| task.synthetic && !task.handle.is_finished())
+    }
+
+    pub async fn run_source<S: TelemetrySource>(&self, source: &mut S) -> Result<()> {
+        let mut data = SessionData::default();
+        let mut slow_var_ticks: u32 = SLOW_VAR_RESET_TICKS;
+        while let Some(tick) = source.next_tick().await {
+            slow_var_ticks += 1;
+
+            let mut force_active = *self.force_active.lock().await;
+
+            if !force_active {
+                let emitter = self.emitter.lock().await;
+                if emitter.get_recording_state() == TelemetryRecordingState::InProgress
+                    || emitter.get_recording_state() == TelemetryRecordingState::WaitingForStart
+                {
+                    force_active = true;
+                }
+            }
+
+            if let Some(recorder) = self.recorder.lock().await.as_mut()
+                && let Err(err) = recorder.record(&tick)
+            {
+                error!("Failed to record session capture tick: {:?}", err);
+            }
+
+            let should_process_slow = slow_var_ticks >= SLOW_VAR_RESET_TICKS;
+
+            let result = data.process_tick(&tick, should_process_slow, force_active);
+
+            if should_process_slow {
+                slow_var_ticks = 0;
+            }
+
+            let mut emitter = self.emitter.lock().await;
+
+            if result == ProcessTickResult::StateChanged {
+                emitter.reset();
+            }
+
+            emitter.emit_all(&data).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn toggle_force_active(&self) {
+        let mut force_active = self.force_active.lock().await;
+        *force_active = !*force_active;
+    }
+
+    pub async fn toggle_session_capture(&self, captures_dir: &Path) -> Result<()> {
+        let mut recorder = self.recorder.lock().await;
+        match recorder.take() {
+            Some(active_recorder) => {
+                active_recorder.finish()?;
+            }
+            None => {
+                std::fs::create_dir_all(captures_dir)?;
+                let file_name = format!(
+                    "capture_{}.{}",
+                    chrono::Local::now().format("%Y%m%d_%H%M%S"),
+                    CAPTURE_EXTENSION
+                );
+                *recorder = Some(CaptureRecorder::create(&captures_dir.join(file_name))?);
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn is_session_capture_active(&self) -> bool {
+        self.recorder.lock().await.is_some()
+    }
+}
+
+struct SourceTask {
+    handle: JoinHandle<()>,
+    synthetic: bool,
+    stepper: Option<Arc<Notify>>,
+}
+
+pub enum SourceMode {
+    Live,
+    Ibt(Box<IbtSource>),
+    Capture(ReplaySource),
+    Synthetic(Box<SyntheticSource>),
+}
+
+impl SourceMode {
+    /// Handle used to advance a stepped replay or demo session by one tick
+    pub fn stepper(&self) -> Option<Arc<Notify>> {
+        match self {
+            SourceMode::Live => None,
+            SourceMode::Ibt(source) => Some(source.stepper()),
+            SourceMode::Capture(source) => Some(source.stepper()),
+            SourceMode::Synthetic(source) => Some(source.stepper()),
+        }
+    }
+
+    /// Picks the source requested on the command line, live iRacing data by default
+    pub fn from_args(args: &[String]) -> Result<Self> {
+        if let Some((path, speed)) = replay_args(args) {
+            return open_replay(&path, speed);
+        }
+        if let Some(config) = synthetic_args(args) {
+            return Ok(SourceMode::Synthetic(Box::new(SyntheticSource::new(
+                config,
+                replay_speed_arg(args),
+            ))));
+        }
+        Ok(SourceMode::Live)
+    }
+}
+
+/// Opens an .ibt file or a session capture depending on the file extension
+pub fn open_replay(path: &Path, speed: ReplaySpeed) -> Result<SourceMode> {
+    if path.extension().is_some_and(|extension| extension == "ibt") {
+        Ok(SourceMode::Ibt(Box::new(IbtSource::open(path, speed)?)))
+    } else {
+        Ok(SourceMode::Capture(ReplaySource::open(path, speed)?))
+    }
+}
+
+fn replay_speed_arg(args: &[String]) -> ReplaySpeed {
+    args.iter()
+        .position(|arg| arg == "--replay-speed")
+        .and_then(|idx| args.get(idx + 1))
+        .map(|speed| {
+            ReplaySpeed::from_str(speed).unwrap_or_else(|err| {
+                warn!("{}, falling back to real time", err);
+                ReplaySpeed::Multiplier(1.0)
+            })
+        })
+        .unwrap_or(ReplaySpeed::Multiplier(1.0))
+}
+
+/// Parses `--demo [config path]` from the command line
+pub fn synthetic_args(args: &[String]) -> Option<SyntheticConfig> {
+    let idx = args.iter().position(|arg| arg == "--demo")?;
+    match args.get(idx + 1).filter(|arg| !arg.starts_with("--")) {
+        Some(path) => Some(
+            SyntheticSource::load_config(Path::new(path)).unwrap_or_else(|err| {
+                warn!(
+                    "Failed to load demo config {}: {:?}, using defaults",
+                    path, err
+                );
+                SyntheticConfig::default()
+            }),
+        ),
+        None => Some(SyntheticConfig::default()),
+    }
+}
+
+/// Parses `--replay <capture or .ibt path> [--replay-speed <multiplier|step>]` from the command line
+pub fn replay_args(args: &[String]) -> Option<(PathBuf, ReplaySpeed)> {
+    let path = args
+        .iter()
+        .position(|arg| arg == "--replay")
+        .and_then(|idx| args.get(idx + 1))
+        .map(PathBuf::from)?;
+    Some((path, replay_speed_arg(args)))
+}
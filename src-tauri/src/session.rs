@@ -1,5 +1,21 @@
+pub mod car_left_right;
+pub mod car_setup;
 pub mod driver;
+pub mod driver_info;
+pub mod fuel;
+pub mod lap_history;
 pub mod lap_time;
+pub mod pit_history;
+pub mod position_history;
+pub mod qualify_result;
+pub mod rating_projection;
 pub mod results_position;
+pub mod sector_timing;
 pub mod session_data;
+pub mod session_flags;
+pub mod session_info;
 pub mod session_type;
+pub mod split_time_info;
+pub mod spotter;
+pub mod weather;
+pub mod weekend_info;
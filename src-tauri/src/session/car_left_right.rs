@@ -0,0 +1,37 @@
+/// irsdk_CarLeftRight, the spotter's view of cars alongside the player
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum CarLeftRight {
+    #[default]
+    Off,
+    Clear,
+    CarLeft,
+    CarRight,
+    CarLeftRight,
+    TwoCarsLeft,
+    TwoCarsRight,
+}
+
+impl CarLeftRight {
+    pub fn from_raw(value: u32) -> Self {
+        match value {
+            1 => Self::Clear,
+            2 => Self::CarLeft,
+            3 => Self::CarRight,
+            4 => Self::CarLeftRight,
+            5 => Self::TwoCarsLeft,
+            6 => Self::TwoCarsRight,
+            _ => Self::Off,
+        }
+    }
+
+    pub fn is_left(self) -> bool {
+        matches!(self, Self::CarLeft | Self::CarLeftRight | Self::TwoCarsLeft)
+    }
+
+    pub fn is_right(self) -> bool {
+        matches!(
+            self,
+            Self::CarRight | Self::CarLeftRight | Self::TwoCarsRight
+        )
+    }
+}
@@ -0,0 +1,53 @@
+use std::collections::BTreeMap;
+
+use yaml_rust::Yaml;
+
+use crate::util::yaml_value::{yaml_string, yaml_u32};
+
+/// Setup values differ for every car, so they are kept as a flat map keyed by their path,
+/// e.g. `Chassis.Front.ArbBlades`
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CarSetup {
+    pub update_count: Option<u32>,
+    pub values: BTreeMap<String, String>,
+}
+
+impl CarSetup {
+    pub fn from_yaml(yaml: &Yaml) -> Option<Self> {
+        let hash = yaml.as_hash()?;
+        let mut values = BTreeMap::new();
+        for (key, value) in hash {
+            let Some(key) = yaml_string(key) else {
+                continue;
+            };
+            if key != "UpdateCount" {
+                flatten(&key, value, &mut values);
+            }
+        }
+        Some(Self {
+            update_count: yaml_u32(&yaml["UpdateCount"]),
+            values,
+        })
+    }
+
+    pub fn get(&self, path: &str) -> Option<&str> {
+        self.values.get(path).map(String::as_str)
+    }
+}
+
+fn flatten(path: &str, yaml: &Yaml, values: &mut BTreeMap<String, String>) {
+    match yaml {
+        Yaml::Hash(hash) => {
+            for (key, value) in hash {
+                if let Some(key) = yaml_string(key) {
+                    flatten(&format!("{}.{}", path, key), value, values);
+                }
+            }
+        }
+        _ => {
+            if let Some(value) = yaml_string(yaml) {
+                values.insert(path.to_string(), value);
+            }
+        }
+    }
+}
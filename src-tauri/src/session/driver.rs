@@ -1,5 +1,11 @@
 use crate::util::signed_duration::SignedDuration;
 
+use super::lap_history::LapHistory;
+use super::pit_history::PitHistory;
+use super::position_history::PositionHistory;
+use super::sector_timing::SectorTiming;
+use super::session_flags::SessionFlags;
+
 #[derive(Clone, Default, Debug)]
 pub struct Driver {
     pub car_id: u32,
@@ -20,20 +26,32 @@ pub struct Driver {
     pub car_number: String,
     pub car_class_id: u32,
     pub irating: u32,
+    /// Expected iRating change if the race finished in the current order
+    pub irating_change: i32,
     pub lic_string: String,
-    pub is_player: bool,
+    /// Follows `focus_car_id`, the camera car while spectating, so not for player-only logic
+    pub is_focus: bool,
     pub is_leader: bool,
+    pub is_class_leader: bool,
     pub is_in_pits: bool,
     pub is_off_track: bool,
     pub is_off_world: bool,
     pub car_class_est_lap_time: SignedDuration,
     pub is_player_class: bool,
     pub car_class_color: u32,
+    pub car_class_name: String,
     pub team_name: String,
     pub is_out: bool,
     pub result_position: Option<u32>,
     pub result_class_position: Option<u32>,
     pub ahead_behind: i32,
+    pub car_model_id: u32,
+    pub club_name: String,
+    pub lap_history: LapHistory,
+    pub sector_timing: SectorTiming,
+    pub pit_history: PitHistory,
+    pub position_history: PositionHistory,
+    pub session_flags: SessionFlags,
 }
 
 #[derive(Default)]
@@ -47,7 +65,10 @@ pub struct DriverBuilder {
     car_class_est_lap_time: SignedDuration,
     is_player_class: bool,
     car_class_color: u32,
+    car_class_name: String,
     team_name: String,
+    car_model_id: u32,
+    club_name: String,
 }
 
 impl DriverBuilder {
@@ -100,11 +121,26 @@ impl DriverBuilder {
         self
     }
 
+    pub fn car_class_name(mut self, car_class_name: String) -> Self {
+        self.car_class_name = car_class_name;
+        self
+    }
+
     pub fn team_name(mut self, team_name: String) -> Self {
         self.team_name = team_name;
         self
     }
 
+    pub fn car_model_id(mut self, car_model_id: u32) -> Self {
+        self.car_model_id = car_model_id;
+        self
+    }
+
+    pub fn club_name(mut self, club_name: String) -> Self {
+        self.club_name = club_name;
+        self
+    }
+
     pub fn build(self) -> Driver {
         Driver {
             car_id: self.car_id,
@@ -116,7 +152,10 @@ impl DriverBuilder {
             car_class_est_lap_time: self.car_class_est_lap_time,
             is_player_class: self.is_player_class,
             car_class_color: self.car_class_color,
+            car_class_name: self.car_class_name,
             team_name: self.team_name,
+            car_model_id: self.car_model_id,
+            club_name: self.club_name,
             ..Default::default()
         }
     }
@@ -0,0 +1,186 @@
+use yaml_rust::Yaml;
+
+use crate::util::yaml_value::{yaml_bool, yaml_f32, yaml_f64, yaml_string, yaml_u32};
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DriverEntry {
+    pub car_idx: u32,
+    pub user_name: Option<String>,
+    pub abbrev_name: Option<String>,
+    pub initials: Option<String>,
+    pub user_id: Option<u32>,
+    pub team_id: Option<u32>,
+    pub team_name: Option<String>,
+    pub car_number: Option<String>,
+    pub car_number_raw: Option<u32>,
+    pub car_path: Option<String>,
+    pub car_class_id: Option<u32>,
+    pub car_id: Option<u32>,
+    pub car_is_pace_car: bool,
+    pub car_is_ai: bool,
+    pub car_is_electric: bool,
+    pub car_screen_name: Option<String>,
+    pub car_screen_name_short: Option<String>,
+    pub car_class_short_name: Option<String>,
+    pub car_class_rel_speed: Option<u32>,
+    pub car_class_license_level: Option<u32>,
+    pub car_class_max_fuel_pct: Option<f32>,       // in %
+    pub car_class_weight_penalty: Option<f32>,     // in kg
+    pub car_class_power_adjust: Option<f32>,       // in %
+    pub car_class_dry_tire_set_limit: Option<f32>, // in %
+    pub car_class_color: Option<u32>,
+    pub car_class_est_lap_time: Option<f64>, // in s
+    pub irating: Option<u32>,
+    pub lic_level: Option<u32>,
+    pub lic_sub_level: Option<u32>,
+    pub lic_string: Option<String>,
+    pub lic_color: Option<u32>,
+    pub is_spectator: bool,
+    pub car_design_str: Option<String>,
+    pub helmet_design_str: Option<String>,
+    pub suit_design_str: Option<String>,
+    pub car_number_design_str: Option<String>,
+    pub car_sponsor_1: Option<u32>,
+    pub car_sponsor_2: Option<u32>,
+    pub club_name: Option<String>,
+    pub club_id: Option<u32>,
+    pub division_name: Option<String>,
+    pub division_id: Option<u32>,
+    pub cur_driver_incident_count: Option<u32>,
+    pub team_incident_count: Option<u32>,
+}
+
+impl DriverEntry {
+    pub fn from_yaml(yaml: &Yaml) -> Option<Self> {
+        Some(Self {
+            car_idx: yaml_u32(&yaml["CarIdx"])?,
+            user_name: yaml_string(&yaml["UserName"]),
+            abbrev_name: yaml_string(&yaml["AbbrevName"]),
+            initials: yaml_string(&yaml["Initials"]),
+            user_id: yaml_u32(&yaml["UserID"]),
+            team_id: yaml_u32(&yaml["TeamID"]),
+            team_name: yaml_string(&yaml["TeamName"]),
+            car_number: yaml_string(&yaml["CarNumber"]),
+            car_number_raw: yaml_u32(&yaml["CarNumberRaw"]),
+            car_path: yaml_string(&yaml["CarPath"]),
+            car_class_id: yaml_u32(&yaml["CarClassID"]),
+            car_id: yaml_u32(&yaml["CarID"]),
+            car_is_pace_car: yaml_bool(&yaml["CarIsPaceCar"]).unwrap_or(false),
+            car_is_ai: yaml_bool(&yaml["CarIsAI"]).unwrap_or(false),
+            car_is_electric: yaml_bool(&yaml["CarIsElectric"]).unwrap_or(false),
+            car_screen_name: yaml_string(&yaml["CarScreenName"]),
+            car_screen_name_short: yaml_string(&yaml["CarScreenNameShort"]),
+            car_class_short_name: yaml_string(&yaml["CarClassShortName"]),
+            car_class_rel_speed: yaml_u32(&yaml["CarClassRelSpeed"]),
+            car_class_license_level: yaml_u32(&yaml["CarClassLicenseLevel"]),
+            car_class_max_fuel_pct: yaml_f32(&yaml["CarClassMaxFuelPct"]),
+            car_class_weight_penalty: yaml_f32(&yaml["CarClassWeightPenalty"]),
+            car_class_power_adjust: yaml_f32(&yaml["CarClassPowerAdjust"]),
+            car_class_dry_tire_set_limit: yaml_f32(&yaml["CarClassDryTireSetLimit"]),
+            car_class_color: yaml_u32(&yaml["CarClassColor"]),
+            car_class_est_lap_time: yaml_f64(&yaml["CarClassEstLapTime"]),
+            irating: yaml_u32(&yaml["IRating"]),
+            lic_level: yaml_u32(&yaml["LicLevel"]),
+            lic_sub_level: yaml_u32(&yaml["LicSubLevel"]),
+            lic_string: yaml_string(&yaml["LicString"]),
+            lic_color: yaml_u32(&yaml["LicColor"]),
+            is_spectator: yaml_bool(&yaml["IsSpectator"]).unwrap_or(false),
+            car_design_str: yaml_string(&yaml["CarDesignStr"]),
+            helmet_design_str: yaml_string(&yaml["HelmetDesignStr"]),
+            suit_design_str: yaml_string(&yaml["SuitDesignStr"]),
+            car_number_design_str: yaml_string(&yaml["CarNumberDesignStr"]),
+            car_sponsor_1: yaml_u32(&yaml["CarSponsor_1"]),
+            car_sponsor_2: yaml_u32(&yaml["CarSponsor_2"]),
+            club_name: yaml_string(&yaml["ClubName"]),
+            club_id: yaml_u32(&yaml["ClubID"]),
+            division_name: yaml_string(&yaml["DivisionName"]),
+            division_id: yaml_u32(&yaml["DivisionID"]),
+            cur_driver_incident_count: yaml_u32(&yaml["CurDriverIncidentCount"]),
+            team_incident_count: yaml_u32(&yaml["TeamIncidentCount"]),
+        })
+    }
+
+    pub fn is_pace_car(&self) -> bool {
+        self.car_is_pace_car || self.user_name.as_deref() == Some("Pace Car")
+    }
+
+    /// iRacing doesn't provide the class short name in AI races: https://github.com/SHWotever/SimHub/issues/1847
+    pub fn car_class_name(&self) -> Option<&str> {
+        self.car_class_short_name
+            .as_deref()
+            .filter(|name| !name.is_empty())
+            .or(self.car_screen_name_short.as_deref())
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DriverInfo {
+    pub driver_car_idx: Option<u32>,
+    pub driver_user_id: Option<u32>,
+    pub pace_car_idx: Option<u32>,
+    pub driver_head_pos_x: Option<f32>,
+    pub driver_head_pos_y: Option<f32>,
+    pub driver_head_pos_z: Option<f32>,
+    pub driver_car_is_electric: Option<bool>,
+    pub driver_car_idle_rpm: Option<f32>,
+    pub driver_car_red_line: Option<f32>,
+    pub driver_car_eng_cylinder_count: Option<u32>,
+    pub driver_car_fuel_kg_per_ltr: Option<f32>,
+    pub driver_car_fuel_max_ltr: Option<f32>,
+    pub driver_car_max_fuel_pct: Option<f32>,
+    pub driver_car_gear_num_forward: Option<u32>,
+    pub driver_car_gear_neutral: Option<u32>,
+    pub driver_car_gear_reverse: Option<u32>,
+    pub driver_car_sl_first_rpm: Option<f32>,
+    pub driver_car_sl_shift_rpm: Option<f32>,
+    pub driver_car_sl_last_rpm: Option<f32>,
+    pub driver_car_sl_blink_rpm: Option<f32>,
+    pub driver_car_version: Option<String>,
+    pub driver_pit_trk_pct: Option<f32>,
+    pub driver_car_est_lap_time: Option<f64>,
+    pub driver_setup_name: Option<String>,
+    pub driver_setup_is_modified: Option<bool>,
+    pub driver_setup_load_type_name: Option<String>,
+    pub driver_setup_passed_tech: Option<bool>,
+    pub driver_incident_count: Option<u32>,
+    pub drivers: Vec<DriverEntry>,
+}
+
+impl DriverInfo {
+    pub fn from_yaml(yaml: &Yaml) -> Self {
+        Self {
+            driver_car_idx: yaml_u32(&yaml["DriverCarIdx"]),
+            driver_user_id: yaml_u32(&yaml["DriverUserID"]),
+            pace_car_idx: yaml_u32(&yaml["PaceCarIdx"]),
+            driver_head_pos_x: yaml_f32(&yaml["DriverHeadPosX"]),
+            driver_head_pos_y: yaml_f32(&yaml["DriverHeadPosY"]),
+            driver_head_pos_z: yaml_f32(&yaml["DriverHeadPosZ"]),
+            driver_car_is_electric: yaml_bool(&yaml["DriverCarIsElectric"]),
+            driver_car_idle_rpm: yaml_f32(&yaml["DriverCarIdleRPM"]),
+            driver_car_red_line: yaml_f32(&yaml["DriverCarRedLine"]),
+            driver_car_eng_cylinder_count: yaml_u32(&yaml["DriverCarEngCylinderCount"]),
+            driver_car_fuel_kg_per_ltr: yaml_f32(&yaml["DriverCarFuelKgPerLtr"]),
+            driver_car_fuel_max_ltr: yaml_f32(&yaml["DriverCarFuelMaxLtr"]),
+            driver_car_max_fuel_pct: yaml_f32(&yaml["DriverCarMaxFuelPct"]),
+            driver_car_gear_num_forward: yaml_u32(&yaml["DriverCarGearNumForward"]),
+            driver_car_gear_neutral: yaml_u32(&yaml["DriverCarGearNeutral"]),
+            driver_car_gear_reverse: yaml_u32(&yaml["DriverCarGearReverse"]),
+            driver_car_sl_first_rpm: yaml_f32(&yaml["DriverCarSLFirstRPM"]),
+            driver_car_sl_shift_rpm: yaml_f32(&yaml["DriverCarSLShiftRPM"]),
+            driver_car_sl_last_rpm: yaml_f32(&yaml["DriverCarSLLastRPM"]),
+            driver_car_sl_blink_rpm: yaml_f32(&yaml["DriverCarSLBlinkRPM"]),
+            driver_car_version: yaml_string(&yaml["DriverCarVersion"]),
+            driver_pit_trk_pct: yaml_f32(&yaml["DriverPitTrkPct"]),
+            driver_car_est_lap_time: yaml_f64(&yaml["DriverCarEstLapTime"]),
+            driver_setup_name: yaml_string(&yaml["DriverSetupName"]),
+            driver_setup_is_modified: yaml_bool(&yaml["DriverSetupIsModified"]),
+            driver_setup_load_type_name: yaml_string(&yaml["DriverSetupLoadTypeName"]),
+            driver_setup_passed_tech: yaml_bool(&yaml["DriverSetupPassedTech"]),
+            driver_incident_count: yaml_u32(&yaml["DriverIncidentCount"]),
+            drivers: yaml["Drivers"]
+                .as_vec()
+                .map(|drivers| drivers.iter().filter_map(DriverEntry::from_yaml).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct FuelLap {
+    pub lap: u32,
+    pub used: f32, // in l
+    /// Neither a pit nor a caution lap, only these count towards the averages
+    pub is_green: bool,
+}
+
+pub struct FuelTick {
+    pub fuel_level: f32,
+    pub fuel_level_pct: f32,
+    pub fuel_use_per_hour: f32,
+    pub laps_completed: u32,
+    pub is_in_pits: bool,
+    pub is_caution: bool,
+}
+
+/// Fuel usage of the player car, measured between crossings of the start/finish line
+#[derive(Clone, Default, Debug)]
+pub struct FuelTracker {
+    pub fuel_level: f32,        // in l
+    pub fuel_level_pct: f32,    // 0..1
+    pub fuel_use_per_hour: f32, // in kg/h
+    laps: Vec<FuelLap>,
+    laps_completed: Option<u32>,
+    // None until the car has crossed the line while being tracked
+    lap_start_fuel: Option<f32>,
+    lap_is_green: bool,
+}
+
+impl FuelTracker {
+    pub fn laps(&self) -> &[FuelLap] {
+        &self.laps
+    }
+
+    pub fn last_lap(&self) -> Option<&FuelLap> {
+        self.laps.last()
+    }
+
+    /// Average usage over the last `laps` green laps
+    pub fn average_green_lap_usage(&self, laps: usize) -> Option<f32> {
+        let green_laps = self
+            .laps
+            .iter()
+            .rev()
+            .filter(|lap| lap.is_green)
+            .take(laps.max(1))
+            .map(|lap| lap.used)
+            .collect::<Vec<f32>>();
+        match green_laps.is_empty() {
+            true => None,
+            false => Some(green_laps.iter().sum::<f32>() / green_laps.len() as f32),
+        }
+    }
+
+    pub fn update(&mut self, tick: FuelTick) {
+        self.fuel_level = tick.fuel_level;
+        self.fuel_level_pct = tick.fuel_level_pct;
+        self.fuel_use_per_hour = tick.fuel_use_per_hour;
+        let is_green = !tick.is_in_pits && !tick.is_caution;
+
+        match self.laps_completed {
+            Some(laps_completed) if tick.laps_completed > laps_completed => {
+                if let Some(lap_start_fuel) = self.lap_start_fuel
+                    && tick.laps_completed == laps_completed + 1
+                {
+                    let used = lap_start_fuel - tick.fuel_level;
+                    self.laps.push(FuelLap {
+                        lap: tick.laps_completed,
+                        used,
+                        is_green: self.lap_is_green && used > 0.0,
+                    });
+                }
+                self.lap_start_fuel = Some(tick.fuel_level);
+                self.lap_is_green = is_green;
+            }
+            Some(laps_completed) if tick.laps_completed == laps_completed => {
+                self.lap_is_green &= is_green;
+            }
+            // joined mid-lap or a new session has started
+            _ => {
+                if self
+                    .laps_completed
+                    .is_some_and(|laps_completed| tick.laps_completed < laps_completed)
+                {
+                    self.laps.clear();
+                }
+                self.lap_start_fuel = None;
+                self.lap_is_green = false;
+            }
+        }
+        self.laps_completed = Some(tick.laps_completed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(laps_completed: u32, fuel_level: f32, is_in_pits: bool) -> FuelTick {
+        FuelTick {
+            fuel_level,
+            fuel_level_pct: fuel_level / 100.0,
+            fuel_use_per_hour: 80.0,
+            laps_completed,
+            is_in_pits,
+            is_caution: false,
+        }
+    }
+
+    #[test]
+    fn measures_laps_from_the_first_crossing() {
+        let mut fuel = FuelTracker::default();
+        fuel.update(tick(3, 60.0, false));
+        fuel.update(tick(4, 58.0, false));
+        fuel.update(tick(5, 55.0, false));
+        fuel.update(tick(6, 51.0, false));
+        let laps = fuel.laps();
+        assert_eq!(laps.len(), 2);
+        assert_eq!((laps[0].lap, laps[0].used), (5, 3.0));
+        assert_eq!(fuel.average_green_lap_usage(5), Some(3.5));
+        assert_eq!(fuel.average_green_lap_usage(1), Some(4.0));
+    }
+
+    #[test]
+    fn excludes_pit_laps_from_averages() {
+        let mut fuel = FuelTracker::default();
+        fuel.update(tick(0, 10.0, false));
+        fuel.update(tick(1, 10.0, false));
+        fuel.update(tick(1, 7.0, true));
+        fuel.update(tick(1, 90.0, true));
+        fuel.update(tick(2, 88.0, false));
+        fuel.update(tick(3, 85.0, false));
+        assert_eq!(fuel.laps().len(), 2);
+        assert!(!fuel.laps()[0].is_green);
+        assert_eq!(fuel.average_green_lap_usage(5), Some(3.0));
+    }
+}
@@ -0,0 +1,221 @@
+use std::time::Duration;
+
+use crate::util::signed_duration::SignedDuration;
+
+// CarIdxLastLapTime is published a moment after the car crosses the line
+const LAP_TIME_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct DriverLap {
+    pub lap: u32,
+    /// Not positive when the sim didn't report a time for the lap
+    pub lap_time: SignedDuration,
+    pub position: u32,
+    pub class_position: u32,
+    pub is_in_pits: bool,
+    pub is_caution: bool,
+}
+
+#[derive(Clone, Debug)]
+struct PendingLap {
+    lap: DriverLap,
+    completed_at: Duration,
+    previous_lap_time: SignedDuration,
+}
+
+/// Completed laps of a single car, built from its lap counter and last lap time transitions
+#[derive(Clone, Default, Debug)]
+pub struct LapHistory {
+    laps: Vec<DriverLap>,
+    pending: Option<PendingLap>,
+    laps_completed: Option<u32>,
+    last_lap_time: SignedDuration,
+    current_lap_in_pits: bool,
+    current_lap_caution: bool,
+}
+
+pub struct LapHistoryTick {
+    pub laps_completed: u32,
+    pub last_lap_time: SignedDuration,
+    pub session_time: Duration,
+    pub position: u32,
+    pub class_position: u32,
+    pub is_in_pits: bool,
+    pub is_caution: bool,
+}
+
+impl LapHistory {
+    /// Completed laps, oldest first
+    pub fn laps(&self) -> &[DriverLap] {
+        &self.laps
+    }
+
+    pub fn last_lap(&self) -> Option<&DriverLap> {
+        self.laps.last()
+    }
+
+    /// The lap in progress started in or went through the pits, e.g. an out lap
+    pub fn current_lap_in_pits(&self) -> bool {
+        self.current_lap_in_pits
+    }
+
+    /// Average time of the last `laps` timed laps without a pit stop or a caution
+    pub fn average_lap_time(&self, laps: usize) -> Option<SignedDuration> {
+        let lap_times = self
+            .laps
+            .iter()
+            .rev()
+            .filter(|lap| !lap.is_in_pits && !lap.is_caution && lap.lap_time > SignedDuration::ZERO)
+            .take(laps.max(1))
+            .map(|lap| lap.lap_time.as_secs_f64())
+            .collect::<Vec<f64>>();
+        match lap_times.is_empty() {
+            true => None,
+            false => Some(SignedDuration::from_secs_f64(
+                lap_times.iter().sum::<f64>() / lap_times.len() as f64,
+            )),
+        }
+    }
+
+    /// Feeds the car state of the current tick, returns the lap whose time got known on this tick
+    pub fn update(&mut self, tick: LapHistoryTick) -> Option<&DriverLap> {
+        let mut finished = false;
+        match self.laps_completed {
+            // lap counter went backwards, a new session has started
+            Some(laps_completed) if tick.laps_completed < laps_completed => {
+                self.laps.clear();
+                self.pending = None;
+                self.reset_current_lap(&tick);
+            }
+            Some(laps_completed) if tick.laps_completed > laps_completed => {
+                finished = self.finish_pending();
+                self.current_lap_in_pits |= tick.is_in_pits;
+                self.current_lap_caution |= tick.is_caution;
+                self.pending = Some(PendingLap {
+                    lap: DriverLap {
+                        lap: tick.laps_completed,
+                        lap_time: SignedDuration::from_secs_f32(-1.0),
+                        position: tick.position,
+                        class_position: tick.class_position,
+                        is_in_pits: self.current_lap_in_pits,
+                        is_caution: self.current_lap_caution,
+                    },
+                    completed_at: tick.session_time,
+                    previous_lap_time: self.last_lap_time,
+                });
+                self.reset_current_lap(&tick);
+            }
+            Some(_) => {
+                self.current_lap_in_pits |= tick.is_in_pits;
+                self.current_lap_caution |= tick.is_caution;
+            }
+            // joined mid-session, the laps before are unknown
+            None => self.reset_current_lap(&tick),
+        }
+        self.laps_completed = Some(tick.laps_completed);
+        self.last_lap_time = tick.last_lap_time;
+
+        if let Some(pending) = self.pending.as_mut() {
+            let time_changed = tick.last_lap_time != pending.previous_lap_time;
+            let timed_out =
+                tick.session_time.saturating_sub(pending.completed_at) >= LAP_TIME_TIMEOUT;
+            // on timeout the lap keeps its unknown time, the sim still shows the previous lap's
+            if time_changed {
+                pending.lap.lap_time = tick.last_lap_time;
+            }
+            if time_changed || timed_out {
+                finished |= self.finish_pending();
+            }
+        }
+        match finished {
+            true => self.laps.last(),
+            false => None,
+        }
+    }
+
+    fn finish_pending(&mut self) -> bool {
+        match self.pending.take() {
+            Some(pending) => {
+                self.laps.push(pending.lap);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn reset_current_lap(&mut self, tick: &LapHistoryTick) {
+        self.current_lap_in_pits = tick.is_in_pits;
+        self.current_lap_caution = tick.is_caution;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(laps_completed: u32, last_lap_time: f32, session_time: f64) -> LapHistoryTick {
+        LapHistoryTick {
+            laps_completed,
+            last_lap_time: SignedDuration::from_secs_f32(last_lap_time),
+            session_time: Duration::from_secs_f64(session_time),
+            position: 3,
+            class_position: 2,
+            is_in_pits: false,
+            is_caution: false,
+        }
+    }
+
+    #[test]
+    fn waits_for_last_lap_time_after_crossing_the_line() {
+        let mut history = LapHistory::default();
+        assert!(history.update(tick(4, 90.5, 100.0)).is_none());
+        assert!(history.update(tick(5, 90.5, 190.0)).is_none());
+        let lap = history.update(tick(5, 91.25, 190.5)).cloned().unwrap();
+        assert_eq!(lap.lap, 5);
+        assert_eq!(lap.lap_time, SignedDuration::from_secs_f32(91.25));
+        assert_eq!((lap.position, lap.class_position), (3, 2));
+        assert_eq!(history.laps().len(), 1);
+    }
+
+    #[test]
+    fn leaves_lap_untimed_after_timeout() {
+        let mut history = LapHistory::default();
+        history.update(tick(1, 90.0, 100.0));
+        history.update(tick(2, 90.0, 190.0));
+        assert!(history.update(tick(2, 90.0, 192.0)).is_none());
+        let lap = history.update(tick(2, 90.0, 193.0)).cloned().unwrap();
+        assert!(!lap.lap_time.is_positive());
+        assert_eq!(history.average_lap_time(5), None);
+    }
+
+    #[test]
+    fn flags_pit_and_caution_laps() {
+        let mut history = LapHistory::default();
+        history.update(tick(1, 90.0, 100.0));
+        history.update(LapHistoryTick {
+            is_in_pits: true,
+            ..tick(1, 90.0, 150.0)
+        });
+        history.update(tick(2, 120.0, 220.0));
+        history.update(LapHistoryTick {
+            is_caution: true,
+            ..tick(2, 120.0, 260.0)
+        });
+        history.update(tick(3, 130.0, 350.0));
+        let laps = history.laps();
+        assert_eq!(laps.len(), 2);
+        assert!(laps[0].is_in_pits && !laps[0].is_caution);
+        assert!(!laps[1].is_in_pits && laps[1].is_caution);
+    }
+
+    #[test]
+    fn clears_history_when_lap_counter_goes_backwards() {
+        let mut history = LapHistory::default();
+        history.update(tick(1, 90.0, 100.0));
+        history.update(tick(2, 91.0, 190.0));
+        assert_eq!(history.laps().len(), 1);
+        history.update(tick(0, -1.0, 10.0));
+        assert!(history.laps().is_empty());
+        assert!(history.last_lap().is_none());
+    }
+}
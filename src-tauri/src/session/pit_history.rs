@@ -0,0 +1,184 @@
+use std::time::Duration;
+
+use crate::util::signed_duration::SignedDuration;
+
+// irsdk_TrkLoc
+const TRACK_SURFACE_NOT_IN_WORLD: i32 = -1;
+const TRACK_SURFACE_IN_PIT_STALL: i32 = 1;
+const TRACK_SURFACE_APROACHING_PITS: i32 = 2;
+
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct PitStop {
+    /// Lap the car was on when it entered the pit lane
+    pub lap: u32,
+    pub entered_at: Duration,
+    /// None while the car is still in the pits
+    pub exited_at: Option<Duration>,
+    pub laps_completed_at_exit: u32,
+    /// Time in the pit stall, zero for a drive-through
+    pub stall_time: SignedDuration,
+}
+
+impl PitStop {
+    pub fn pit_lane_time(&self) -> Option<SignedDuration> {
+        let exited_at = self.exited_at?;
+        Some(SignedDuration::from_secs_f64(
+            exited_at.saturating_sub(self.entered_at).as_secs_f64(),
+        ))
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+enum PitState {
+    #[default]
+    NotInWorld,
+    OnTrack,
+    PitLane,
+    PitStall {
+        since: Duration,
+    },
+}
+
+/// Pit stops of a single car, detected from `CarIdxTrackSurface` transitions
+#[derive(Clone, Default, Debug)]
+pub struct PitHistory {
+    stops: Vec<PitStop>,
+    state: PitState,
+}
+
+impl PitHistory {
+    pub fn stops(&self) -> &[PitStop] {
+        &self.stops
+    }
+
+    pub fn stop_count(&self) -> u32 {
+        self.stops.len() as u32
+    }
+
+    pub fn last_stop(&self) -> Option<&PitStop> {
+        self.stops.last()
+    }
+
+    /// Laps completed since the car left the pits, or since the start when it hasn't stopped yet
+    pub fn laps_since_last_stop(&self, laps_completed: u32) -> u32 {
+        match self.stops.last() {
+            Some(stop) if stop.exited_at.is_none() => 0,
+            Some(stop) => laps_completed.saturating_sub(stop.laps_completed_at_exit),
+            None => laps_completed,
+        }
+    }
+
+    pub fn update(&mut self, track_surface: i32, laps_completed: u32, session_time: Duration) {
+        let state = match track_surface {
+            TRACK_SURFACE_NOT_IN_WORLD => PitState::NotInWorld,
+            TRACK_SURFACE_IN_PIT_STALL => match self.state {
+                PitState::PitStall { since } => PitState::PitStall { since },
+                _ => PitState::PitStall {
+                    since: session_time,
+                },
+            },
+            TRACK_SURFACE_APROACHING_PITS => PitState::PitLane,
+            _ => PitState::OnTrack,
+        };
+        let in_pits = matches!(state, PitState::PitLane | PitState::PitStall { .. });
+        let open_stop = self
+            .stops
+            .last_mut()
+            .filter(|stop| stop.exited_at.is_none());
+
+        match (self.state, open_stop) {
+            // only entering from the track counts, a car reset or towed to its stall hasn't stopped
+            (PitState::OnTrack, None) if in_pits => self.stops.push(PitStop {
+                lap: laps_completed + 1,
+                entered_at: session_time,
+                ..Default::default()
+            }),
+            (PitState::PitStall { since }, Some(stop)) if state != self.state => {
+                stop.stall_time = stop.stall_time
+                    + SignedDuration::from_secs_f64(
+                        session_time.saturating_sub(since).as_secs_f64(),
+                    );
+                if state == PitState::OnTrack {
+                    stop.exited_at = Some(session_time);
+                    stop.laps_completed_at_exit = laps_completed;
+                }
+            }
+            (PitState::PitLane, Some(stop)) if state == PitState::OnTrack => {
+                stop.exited_at = Some(session_time);
+                stop.laps_completed_at_exit = laps_completed;
+            }
+            _ => {}
+        }
+        self.state = state;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ON_TRACK: i32 = 3;
+
+    fn drive(history: &mut PitHistory, surfaces: &[(i32, u32, u64)]) {
+        for (track_surface, laps_completed, session_time) in surfaces {
+            history.update(
+                *track_surface,
+                *laps_completed,
+                Duration::from_secs(*session_time),
+            );
+        }
+    }
+
+    #[test]
+    fn records_stop_with_pit_lane_and_stall_time() {
+        let mut history = PitHistory::default();
+        drive(
+            &mut history,
+            &[
+                (ON_TRACK, 11, 100),
+                (TRACK_SURFACE_APROACHING_PITS, 11, 110),
+                (TRACK_SURFACE_IN_PIT_STALL, 11, 125),
+                (TRACK_SURFACE_APROACHING_PITS, 12, 160),
+                (ON_TRACK, 12, 170),
+            ],
+        );
+        let stop = history.last_stop().unwrap();
+        assert_eq!(stop.lap, 12);
+        assert_eq!(
+            stop.pit_lane_time(),
+            Some(SignedDuration::from_secs_f64(60.0))
+        );
+        assert_eq!(stop.stall_time, SignedDuration::from_secs_f64(35.0));
+        assert_eq!(history.stop_count(), 1);
+        assert_eq!(history.laps_since_last_stop(15), 3);
+    }
+
+    #[test]
+    fn stop_is_open_while_in_pits() {
+        let mut history = PitHistory::default();
+        drive(
+            &mut history,
+            &[(ON_TRACK, 4, 100), (TRACK_SURFACE_APROACHING_PITS, 4, 110)],
+        );
+        assert_eq!(history.last_stop().unwrap().pit_lane_time(), None);
+        assert_eq!(history.laps_since_last_stop(4), 0);
+    }
+
+    #[test]
+    fn ignores_cars_starting_or_reset_in_their_stall() {
+        let mut history = PitHistory::default();
+        drive(
+            &mut history,
+            &[
+                (TRACK_SURFACE_IN_PIT_STALL, 0, 10),
+                (TRACK_SURFACE_APROACHING_PITS, 0, 20),
+                (ON_TRACK, 0, 30),
+                (TRACK_SURFACE_NOT_IN_WORLD, 2, 300),
+                (TRACK_SURFACE_IN_PIT_STALL, 2, 310),
+                (ON_TRACK, 2, 340),
+            ],
+        );
+        assert_eq!(history.stop_count(), 0);
+        assert_eq!(history.laps_since_last_stop(5), 5);
+    }
+}
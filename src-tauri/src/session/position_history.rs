@@ -0,0 +1,57 @@
+/// Class positions of a car over a race
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct PositionHistory {
+    /// Grid slot in class
+    pub start_position: Option<u32>,
+    /// Class position at the end of every completed lap, index 0 is lap 1
+    pub laps: Vec<u32>,
+}
+
+impl PositionHistory {
+    pub fn update(&mut self, laps_completed: u32, position: u32) {
+        // laps missed while the car wasn't processed, e.g. after joining mid-race, take the current position
+        while (self.laps.len() as u32) < laps_completed {
+            self.laps.push(position);
+        }
+    }
+
+    /// Positions gained since the end of `lap`, since the start for lap 0
+    pub fn positions_gained_since(&self, lap: u32, position: u32) -> i32 {
+        let reference = match lap {
+            0 => self.start_position,
+            lap => self.laps.get(lap as usize - 1).copied(),
+        };
+        reference.map_or(0, |reference| reference as i32 - position as i32)
+    }
+
+    pub fn positions_gained(&self, position: u32) -> i32 {
+        self.positions_gained_since(0, position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_position_per_lap() {
+        let mut history = PositionHistory {
+            start_position: Some(5),
+            ..Default::default()
+        };
+        history.update(0, 4);
+        history.update(1, 3);
+        history.update(1, 2);
+        history.update(3, 6);
+        assert_eq!(history.laps, vec![3, 6, 6]);
+        assert_eq!(history.positions_gained(6), -1);
+        assert_eq!(history.positions_gained_since(1, 2), 1);
+        assert_eq!(history.positions_gained_since(5, 2), 0);
+    }
+
+    #[test]
+    fn gains_nothing_without_grid_slot() {
+        let history = PositionHistory::default();
+        assert_eq!(history.positions_gained(1), 0);
+    }
+}
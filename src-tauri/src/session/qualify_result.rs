@@ -0,0 +1,29 @@
+use yaml_rust::Yaml;
+
+use crate::util::signed_duration::SignedDuration;
+use crate::util::yaml_value::{yaml_f64, yaml_i64, yaml_u32};
+
+/// Entry of `QualifyResultsInfo`, the grid of the race
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct QualifyResult {
+    pub car_id: u32,
+    pub position: u32,
+    pub class_position: u32,
+    pub fastest_lap: u32,
+    pub fastest_time: SignedDuration,
+}
+
+impl QualifyResult {
+    pub fn from_yaml(yaml: &Yaml) -> Self {
+        Self {
+            car_id: yaml_u32(&yaml["CarIdx"]).unwrap_or(0),
+            // iRacing reports qualify positions starting from 0
+            position: (yaml_i64(&yaml["Position"]).unwrap_or(0) + 1) as u32,
+            class_position: (yaml_i64(&yaml["ClassPosition"]).unwrap_or(0) + 1) as u32,
+            fastest_lap: yaml_u32(&yaml["FastestLap"]).unwrap_or(0),
+            fastest_time: SignedDuration::from_secs_f64(
+                yaml_f64(&yaml["FastestTime"]).unwrap_or(0.0),
+            ),
+        }
+    }
+}
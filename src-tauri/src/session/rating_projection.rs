@@ -0,0 +1,166 @@
+use std::f64::consts::LN_2;
+
+const BR1: f64 = 1600.0 / LN_2;
+/// Corners per incident at which the safety rating stays the same
+const BREAK_EVEN_CORNERS_PER_INCIDENT: f32 = 40.0;
+const SAFETY_RATING_PER_CORNER: f32 = 0.0007;
+
+pub struct RatingEntry {
+    pub irating: u32,
+    /// Took the start, DNFs included and scored by finishing order, only cars that never started
+    /// share the starters' gains
+    pub is_starter: bool,
+}
+
+/// Chance that a driver rated `a` finishes ahead of a driver rated `b`, both as `exp(-irating / BR1)`
+fn chance(a: f64, b: f64) -> f64 {
+    (1.0 - a) * b / ((1.0 - b) * a + (1.0 - a) * b)
+}
+
+/// Expected iRating changes of a class, `entries` in running order
+///
+/// Unrated entries, e.g. AI or hosted cars, are left out and get no change, an iRating of 0
+/// would make every chance against them `0 / 0`
+pub fn irating_changes(entries: &[RatingEntry]) -> Vec<i32> {
+    let rated = entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| entry.irating > 0)
+        .map(|(idx, _)| idx)
+        .collect::<Vec<usize>>();
+    let rated_entries = rated.iter().map(|idx| &entries[*idx]).collect::<Vec<_>>();
+    let mut changes = vec![0; entries.len()];
+    for (idx, change) in rated.iter().zip(rated_irating_changes(&rated_entries)) {
+        changes[*idx] = change;
+    }
+    changes
+}
+
+fn rated_irating_changes(entries: &[&RatingEntry]) -> Vec<i32> {
+    let registered = entries.len() as f64;
+    let starters = entries.iter().filter(|entry| entry.is_starter).count() as f64;
+    let non_starters = registered - starters;
+    if starters == 0.0 {
+        return vec![0; entries.len()];
+    }
+
+    let exps = entries
+        .iter()
+        .map(|entry| (-(entry.irating as f64) / BR1).exp())
+        .collect::<Vec<f64>>();
+    let expected_scores = exps
+        .iter()
+        .map(|a| exps.iter().map(|b| chance(*a, *b)).sum::<f64>() - 0.5)
+        .collect::<Vec<f64>>();
+
+    let mut changes = vec![0.0; entries.len()];
+    let mut position = 0.0;
+    for (idx, entry) in entries.iter().enumerate() {
+        if !entry.is_starter {
+            continue;
+        }
+        position += 1.0;
+        let fudge = ((registered - non_starters / 2.0) / 2.0 - position) / 100.0;
+        changes[idx] = (registered - position - expected_scores[idx] - fudge) * 200.0 / starters;
+    }
+
+    if non_starters > 0.0 {
+        let starters_change = changes.iter().sum::<f64>();
+        let non_starters_expected = entries
+            .iter()
+            .zip(expected_scores.iter())
+            .filter(|(entry, _)| !entry.is_starter)
+            .map(|(_, expected)| *expected)
+            .sum::<f64>()
+            / non_starters;
+        for (idx, entry) in entries.iter().enumerate() {
+            if !entry.is_starter {
+                changes[idx] =
+                    -starters_change / non_starters * expected_scores[idx] / non_starters_expected;
+            }
+        }
+    }
+
+    changes.iter().map(|change| change.round() as i32).collect()
+}
+
+/// Approximate safety rating change for `incidents` over `corners` driven
+pub fn safety_rating_change(incidents: u32, corners: f32) -> f32 {
+    (corners - incidents as f32 * BREAK_EVEN_CORNERS_PER_INCIDENT) * SAFETY_RATING_PER_CORNER
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(iratings: &[(u32, bool)]) -> Vec<RatingEntry> {
+        iratings
+            .iter()
+            .map(|(irating, is_starter)| RatingEntry {
+                irating: *irating,
+                is_starter: *is_starter,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rewards_beating_higher_rated_drivers() {
+        let changes = irating_changes(&entries(&[(1500, true), (2000, true), (2500, true)]));
+        assert!(changes[0] > 0);
+        assert!(changes[2] < 0);
+        assert!(changes[0] > changes[1]);
+
+        let even = irating_changes(&entries(&[(2000, true), (2000, true)]));
+        assert!(even[0] > 0 && even[1] < 0);
+    }
+
+    #[test]
+    fn scores_dnfs_by_finishing_order() {
+        // the DNF took the start, nobody is redistributed as a non-starter
+        let changes = irating_changes(&entries(&[(2000, true), (2000, true), (2000, true)]));
+        assert!(changes[2] < 0);
+        assert!(changes.iter().sum::<i32>().abs() <= 2);
+        assert_eq!(
+            changes,
+            irating_changes(&entries(&[
+                (2000, true),
+                (2000, true),
+                (2000, true),
+                (0, true)
+            ]))[..3]
+        );
+    }
+
+    #[test]
+    fn leaves_unrated_entries_out() {
+        let changes = irating_changes(&entries(&[
+            (1500, true),
+            (0, true),
+            (2500, true),
+            (0, false),
+        ]));
+        assert_eq!(changes[1], 0);
+        assert_eq!(changes[3], 0);
+        assert!(changes[0] > 0);
+        assert!(changes[2] < 0);
+    }
+
+    #[test]
+    fn non_starters_lose_what_starters_gain() {
+        let changes = irating_changes(&entries(&[
+            (2000, true),
+            (2000, true),
+            (2000, true),
+            (2000, false),
+        ]));
+        assert!(changes[3] < 0);
+        assert!(changes.iter().sum::<i32>().abs() <= 2);
+    }
+
+    #[test]
+    fn keeps_safety_rating_at_break_even() {
+        assert_eq!(safety_rating_change(2, 80.0), 0.0);
+        assert!(safety_rating_change(0, 100.0) > 0.0);
+        assert!(safety_rating_change(4, 100.0) < 0.0);
+    }
+}
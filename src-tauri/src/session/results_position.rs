@@ -1,11 +1,48 @@
+use yaml_rust::Yaml;
+
 use crate::util::signed_duration::SignedDuration;
+use crate::util::yaml_value::{yaml_f32, yaml_f64, yaml_i64, yaml_string, yaml_u32};
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone, PartialEq)]
 pub struct ResultsPosition {
     pub car_id: u32,
     pub position: u32,
     pub class_position: u32,
+    pub lap: u32,
+    pub time: SignedDuration,
+    pub fastest_lap: u32,
     pub fastest_time: SignedDuration,
     pub last_time: SignedDuration,
+    pub laps_led: u32,
+    pub laps_complete: u32,
+    pub joker_laps_complete: u32,
+    pub laps_driven: f32,
+    pub incidents: u32,
     pub reason_out_id: u32,
+    pub reason_out_str: Option<String>,
+}
+
+impl ResultsPosition {
+    pub fn from_yaml(yaml: &Yaml) -> Self {
+        Self {
+            car_id: yaml_u32(&yaml["CarIdx"]).unwrap_or(0),
+            position: yaml_u32(&yaml["Position"]).unwrap_or(0),
+            // iRacing reports class positions starting from 0
+            class_position: (yaml_i64(&yaml["ClassPosition"]).unwrap_or(0) + 1) as u32,
+            lap: yaml_u32(&yaml["Lap"]).unwrap_or(0),
+            time: SignedDuration::from_secs_f64(yaml_f64(&yaml["Time"]).unwrap_or(0.0)),
+            fastest_lap: yaml_u32(&yaml["FastestLap"]).unwrap_or(0),
+            fastest_time: SignedDuration::from_secs_f64(
+                yaml_f64(&yaml["FastestTime"]).unwrap_or(0.0),
+            ),
+            last_time: SignedDuration::from_secs_f64(yaml_f64(&yaml["LastTime"]).unwrap_or(0.0)),
+            laps_led: yaml_u32(&yaml["LapsLed"]).unwrap_or(0),
+            laps_complete: yaml_u32(&yaml["LapsComplete"]).unwrap_or(0),
+            joker_laps_complete: yaml_u32(&yaml["JokerLapsComplete"]).unwrap_or(0),
+            laps_driven: yaml_f32(&yaml["LapsDriven"]).unwrap_or(0.0),
+            incidents: yaml_u32(&yaml["Incidents"]).unwrap_or(0),
+            reason_out_id: yaml_u32(&yaml["ReasonOutID"]).unwrap_or(0),
+            reason_out_str: yaml_string(&yaml["ReasonOutStr"]),
+        }
+    }
 }
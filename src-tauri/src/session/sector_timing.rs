@@ -0,0 +1,221 @@
+use std::time::Duration;
+
+use crate::util::signed_duration::SignedDuration;
+
+use super::split_time_info::Sector;
+
+// Anything further in a single tick is a tow or a reset rather than driving
+const MAX_LAP_DIST_PCT_STEP: f32 = 0.1;
+
+/// Sector splits of a single car, timed from `CarIdxLapDistPct` crossing the sector boundaries
+#[derive(Clone, Default, Debug)]
+pub struct SectorTiming {
+    last_lap_dist_pct: Option<f32>,
+    last_session_time: Duration,
+    current_sector: usize,
+    // None until the car has crossed into the current sector while being tracked
+    sector_started_at: Option<Duration>,
+    pub current_lap: Vec<Option<SignedDuration>>,
+    pub last_lap: Vec<Option<SignedDuration>>,
+    pub best: Vec<Option<SignedDuration>>,
+}
+
+impl SectorTiming {
+    pub fn current_sector(&self) -> usize {
+        self.current_sector
+    }
+
+    /// Time spent in the current sector, None while the car's sector start is unknown
+    pub fn current_sector_time(&self, session_time: Duration) -> Option<SignedDuration> {
+        let started_at = self.sector_started_at?;
+        Some(SignedDuration::from_secs_f64(
+            session_time.saturating_sub(started_at).as_secs_f64(),
+        ))
+    }
+
+    /// Current lap time if the car matched its best sectors from here on, None until every
+    /// sector of the lap so far is timed and every remaining sector has a best
+    pub fn projected_lap_time(&self, session_time: Duration) -> Option<SignedDuration> {
+        let completed = self.current_lap[..self.current_sector]
+            .iter()
+            .copied()
+            .sum::<Option<SignedDuration>>()?;
+        let current = self.current_sector_time(session_time)?;
+        let current = match self.best.get(self.current_sector).copied().flatten() {
+            Some(best) if best > current => best,
+            _ => current,
+        };
+        let remaining = self.best[self.current_sector + 1..]
+            .iter()
+            .copied()
+            .sum::<Option<SignedDuration>>()?;
+        Some(completed + current + remaining)
+    }
+
+    pub fn update(&mut self, sectors: &[Sector], lap_dist_pct: f32, session_time: Duration) {
+        if sectors.len() != self.best.len() {
+            *self = Self {
+                current_lap: vec![None; sectors.len()],
+                last_lap: vec![None; sectors.len()],
+                best: vec![None; sectors.len()],
+                ..Default::default()
+            };
+        }
+        if sectors.is_empty() || lap_dist_pct < 0.0 {
+            self.last_lap_dist_pct = None;
+            return;
+        }
+
+        let last_lap_dist_pct = match self.last_lap_dist_pct {
+            Some(value) => value,
+            None => {
+                self.start_tracking(sectors, lap_dist_pct, session_time);
+                return;
+            }
+        };
+        let mut step = lap_dist_pct - last_lap_dist_pct;
+        if step < -0.5 {
+            step += 1.0;
+        }
+        if step < 0.0 {
+            // jitter or rolling backwards, wait until the car is past the last position again
+            return;
+        }
+        if step > MAX_LAP_DIST_PCT_STEP {
+            self.start_tracking(sectors, lap_dist_pct, session_time);
+            return;
+        }
+
+        let tick_duration = session_time.saturating_sub(self.last_session_time);
+        let mut offset = 0.0;
+        loop {
+            let next_sector = (self.current_sector + 1) % sectors.len();
+            let boundary = match next_sector {
+                0 => 1.0,
+                _ => sectors[next_sector].sector_start_pct,
+            } + offset;
+            if boundary <= last_lap_dist_pct || boundary > last_lap_dist_pct + step {
+                break;
+            }
+            let crossed_at = self.last_session_time
+                + tick_duration.mul_f32((boundary - last_lap_dist_pct) / step);
+            self.complete_sector(crossed_at);
+            self.current_sector = next_sector;
+            if next_sector == 0 {
+                self.last_lap = std::mem::replace(&mut self.current_lap, vec![None; sectors.len()]);
+                offset += 1.0;
+            }
+        }
+        self.last_lap_dist_pct = Some(lap_dist_pct);
+        self.last_session_time = session_time;
+    }
+
+    fn start_tracking(&mut self, sectors: &[Sector], lap_dist_pct: f32, session_time: Duration) {
+        self.current_sector = sectors
+            .iter()
+            .rposition(|sector| sector.sector_start_pct <= lap_dist_pct)
+            .unwrap_or(0);
+        self.sector_started_at = None;
+        self.last_lap_dist_pct = Some(lap_dist_pct);
+        self.last_session_time = session_time;
+    }
+
+    fn complete_sector(&mut self, crossed_at: Duration) {
+        if let Some(started_at) = self.sector_started_at {
+            let time =
+                SignedDuration::from_secs_f64(crossed_at.saturating_sub(started_at).as_secs_f64());
+            let sector = self.current_sector;
+            self.current_lap[sector] = Some(time);
+            if self.best[sector].is_none_or(|best| time < best) {
+                self.best[sector] = Some(time);
+            }
+        }
+        self.sector_started_at = Some(crossed_at);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sectors() -> Vec<Sector> {
+        [0.0, 0.25, 0.5]
+            .iter()
+            .enumerate()
+            .map(|(sector_num, sector_start_pct)| Sector {
+                sector_num: sector_num as u32,
+                sector_start_pct: *sector_start_pct,
+            })
+            .collect()
+    }
+
+    /// Drives from `from` to `to` (in laps) at `pct_per_sec`, sampled at 10Hz
+    fn drive(timing: &mut SectorTiming, from: f64, to: f64, pct_per_sec: f64, start_time: f64) {
+        let sectors = sectors();
+        let samples = ((to - from) / pct_per_sec * 10.0).round() as u32;
+        for sample in 0..=samples {
+            let pct = from + pct_per_sec * sample as f64 / 10.0;
+            let time = start_time + sample as f64 / 10.0;
+            timing.update(&sectors, pct.fract() as f32, Duration::from_secs_f64(time));
+        }
+    }
+
+    fn secs(time: Option<SignedDuration>) -> f32 {
+        (time.unwrap().as_secs_f32() * 100.0).round() / 100.0
+    }
+
+    #[test]
+    fn times_sectors_after_the_first_full_sector() {
+        let mut timing = SectorTiming::default();
+        // 1% of the lap per second, joined in the middle of the first sector
+        drive(&mut timing, 0.1, 1.3, 0.01, 0.0);
+        assert_eq!(timing.last_lap[0], None);
+        assert_eq!(secs(timing.last_lap[1]), 25.0);
+        assert_eq!(secs(timing.last_lap[2]), 50.0);
+        assert_eq!(secs(timing.current_lap[0]), 25.0);
+        assert_eq!(timing.current_lap[1], None);
+        assert_eq!(timing.current_sector(), 1);
+    }
+
+    #[test]
+    fn keeps_personal_best_per_sector() {
+        let mut timing = SectorTiming::default();
+        drive(&mut timing, 0.2, 1.0, 0.02, 0.0);
+        drive(&mut timing, 1.01, 2.3, 0.01, 41.0);
+        assert_eq!(secs(timing.last_lap[0]), 25.0);
+        assert_eq!(secs(timing.last_lap[1]), 25.0);
+        assert_eq!(secs(timing.last_lap[2]), 50.0);
+        assert_eq!(secs(timing.best[0]), 25.0);
+        assert_eq!(secs(timing.best[1]), 12.5);
+        assert_eq!(secs(timing.best[2]), 25.0);
+    }
+
+    #[test]
+    fn projects_lap_time_from_best_sectors() {
+        let mut timing = SectorTiming::default();
+        drive(&mut timing, 0.2, 1.3, 0.01, 0.0);
+        drive(&mut timing, 1.3, 2.3, 0.02, 110.0);
+        // best sectors are 12.5s, 15s and 25s, the second sector started at 157.5s
+        assert_eq!(timing.current_sector(), 1);
+        assert_eq!(
+            secs(timing.projected_lap_time(Duration::from_secs_f64(162.5))),
+            52.5
+        );
+        assert_eq!(
+            secs(timing.projected_lap_time(Duration::from_secs_f64(200.0))),
+            80.0
+        );
+    }
+
+    #[test]
+    fn restarts_timing_after_a_tow() {
+        let mut timing = SectorTiming::default();
+        drive(&mut timing, 0.99, 1.4, 0.01, 0.0);
+        assert_eq!(secs(timing.current_lap[0]), 25.0);
+        // towed to the pits just before the line
+        drive(&mut timing, 0.95, 1.3, 0.01, 100.0);
+        assert_eq!(secs(timing.last_lap[0]), 25.0);
+        assert_eq!(timing.last_lap[2], None);
+        assert_eq!(secs(timing.current_lap[0]), 25.0);
+    }
+}
@@ -4,19 +4,99 @@ use std::{collections::HashMap, time::Duration};
 
 use chrono::{DateTime, Local};
 use log::{debug, error, info};
-use simetry::iracing::{SimState, UNLIMITED_LAPS, UNLIMITED_TIME};
+use simetry::iracing::{UNLIMITED_LAPS, UNLIMITED_TIME};
 
 use crate::session::session_type::SessionType;
+use crate::source::telemetry_tick::TelemetryTick;
 use crate::util::{get_strength_of_field::get_strength_of_field, signed_duration::SignedDuration};
 
+use super::car_left_right::CarLeftRight;
+use super::fuel::{FuelTick, FuelTracker};
+use super::lap_history::LapHistoryTick;
+use super::rating_projection::{RatingEntry, irating_changes};
 use super::results_position::ResultsPosition;
+use super::session_flags::{FlagTransition, SessionFlags};
+use super::session_info::SessionInfo;
+use super::spotter::{Spotter, SpotterTick};
+use super::weather::{Skies, TrackWetness, Weather, WeatherTick, WeekendWeather};
 use super::{driver::Driver, lap_time::LapTime};
 
+/// Every variable `process_tick` reads, keep in sync when adding new ones
+pub const PROCESSED_VARIABLES: &[&str] = &[
+    "SessionTick",
+    "SessionUniqueID",
+    "CamCarIdx",
+    "IsOnTrack",
+    "IsOnTrackCar",
+    "SessionNum",
+    "SessionTimeTotal",
+    "SessionLapsTotal",
+    "PlayerCarMyIncidentCount",
+    "PlayerCarSLShiftRPM",
+    "PlayerCarSLBlinkRPM",
+    "SessionTime",
+    "PlayerCarIdx",
+    "PlayerCarClass",
+    "Lap",
+    "RaceLaps",
+    "LapCurrentLapTime",
+    "LapDist",
+    "LapDeltaToSessionLastlLap",
+    "LapDeltaToBestLap",
+    "LapDeltaToOptimalLap",
+    "SessionTimeRemain",
+    "SessionLapsRemainEx",
+    "Gear",
+    "Speed",
+    "RPM",
+    "Brake",
+    "Throttle",
+    "BrakeABSactive",
+    "SteeringWheelAngle",
+    "CarLeftRight",
+    "CarIdxLapDistPct",
+    "CarIdxLapCompleted",
+    "CarIdxLap",
+    "CarIdxEstTime",
+    "CarIdxBestLapTime",
+    "CarIdxLastLapTime",
+    "CarIdxTrackSurface",
+    "LapLastLapTime",
+    "SessionFlags",
+    "CarIdxSessionFlags",
+    "FuelLevel",
+    "FuelLevelPct",
+    "FuelUsePerHour",
+    "OnPitRoad",
+    "AirTemp",
+    "TrackTempCrew",
+    "TrackWetness",
+    "Precipitation",
+    "WindVel",
+    "WindDir",
+    "Skies",
+    "FogLevel",
+];
+
+/// Identifies a session of a weekend, data is kept for as long as it stays the same
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+pub struct SessionKey {
+    pub session_unique_id: i32,
+    pub session_num: u32,
+}
+
 #[derive(Default, Debug)]
 pub struct SessionData {
     pub abs_active: bool,
     pub activated: bool,
     pub active: bool,
+    pub session_key: Option<SessionKey>,
+    /// Player is in the car, `active` can be forced while they are not
+    pub is_on_track: bool,
+    /// Player is out of the car and the camera follows another car, e.g. a teammate or a replay
+    pub is_spectating: bool,
+    /// Car the relative, gaps, standings and track map are about, the camera car while spectating
+    pub focus_car_id: Option<u32>,
     pub brake: u32,
     pub lap_dist: u32, // in cm
     pub current_time: DateTime<Local>,
@@ -31,6 +111,7 @@ pub struct SessionData {
     pub gear: i32,
     pub incident_limit: u32,
     pub incidents: u32,
+    pub car_left_right: CarLeftRight,
     pub is_left: bool,
     pub is_right: bool,
     pub lap_time: SignedDuration,
@@ -38,6 +119,7 @@ pub struct SessionData {
     pub laps_total: u32,
     pub last_lap_time: SignedDuration,
     pub leader_car_id: u32,
+    pub class_leader_car_ids: HashMap<u32, u32>,
     pub player_car_class: u32,
     pub player_car_id: Option<u32>,
     pub player_lap_times: Vec<LapTime>,
@@ -65,6 +147,13 @@ pub struct SessionData {
     pub results_positions_mapping: HashMap<u32, usize>,
     pub results_official: bool,
     pub steering_angle: i32, // in radian * 100
+    pub session_info: SessionInfo,
+    pub class_best_sector_times: HashMap<u32, Vec<Option<SignedDuration>>>,
+    pub fuel: FuelTracker,
+    pub session_flags: SessionFlags,
+    pub flag: FlagTransition,
+    pub spotter: Spotter,
+    pub weather: Weather,
 }
 
 #[derive(PartialEq)]
@@ -74,9 +163,9 @@ pub enum ProcessTickResult {
 }
 
 impl SessionData {
-    pub fn process_tick(
+    pub fn process_tick<T: TelemetryTick>(
         &mut self,
-        sim_state: &SimState,
+        sim_state: &T,
         should_process_slow: bool,
         force_active: bool,
     ) -> ProcessTickResult {
@@ -92,35 +181,63 @@ impl SessionData {
             return ProcessTickResult::None;
         }
 
+        // session_key, leaving and re-entering the car (garage, tow, reset) keeps the data
+        let mut result = ProcessTickResult::None;
+        let raw_session_unique_id_value = sim_state.read_name("SessionUniqueID").unwrap_or(0);
+        let raw_session_num_value: i32 = sim_state.read_name("SessionNum").unwrap_or(0);
+        let session_key = SessionKey {
+            session_unique_id: raw_session_unique_id_value,
+            session_num: raw_session_num_value as u32,
+        };
+        if self.session_key != Some(session_key) {
+            if self.session_key.is_some() {
+                info!(
+                    "Session changed to {:?}, resetting session data",
+                    session_key
+                );
+                *self = Self {
+                    processed_slow: self.processed_slow,
+                    current_time: self.current_time,
+                    ..Default::default()
+                };
+                result = ProcessTickResult::StateChanged;
+            }
+            self.session_key = Some(session_key);
+        }
+
         // active
         let raw_is_on_track_value = sim_state.read_name("IsOnTrack").unwrap_or(false);
-        let raw_is_on_track_car_value = sim_state.read_name("IsOnTrackCar").unwrap_or(false);
+        let raw_is_on_track_car_value = sim_state
+            .read_name("IsOnTrackCar")
+            .unwrap_or(raw_is_on_track_value);
+
+        self.is_on_track = raw_is_on_track_value && raw_is_on_track_car_value;
 
-        let active = force_active || (raw_is_on_track_value && raw_is_on_track_car_value);
+        // is_spectating, drivers are only known once the session info has been read
+        let raw_cam_car_idx_value: i32 = sim_state.read_name("CamCarIdx").unwrap_or(-1);
+        self.is_spectating = !self.is_on_track
+            && raw_cam_car_idx_value >= 0
+            && (self.drivers.is_empty()
+                || self.drivers.contains_key(&(raw_cam_car_idx_value as u32)));
+
+        let active = force_active || self.is_on_track || self.is_spectating;
         let activated = active != self.active;
         if activated {
             info!(
                 "Session state changed to {}",
                 if active { "active" } else { "inactive" }
             );
-            if active {
-                // TODO: save some data for reinitializing the session
-                // TODO: allow to show some data while player not on track
-                let _ = std::mem::take(self);
-            }
-            self.active = active;
         }
         self.active = active;
         self.activated = activated;
 
         if !active {
-            return ProcessTickResult::None;
+            return result;
         }
 
         // slow vars
         if should_process_slow {
             // session_num
-            let raw_session_num_value = sim_state.read_name("SessionNum").unwrap_or(0);
             let session_num_value = raw_session_num_value as u32;
             self.session_num = session_num_value;
 
@@ -169,9 +286,29 @@ impl SessionData {
         let player_car_idx_value = sim_state.read_name("PlayerCarIdx").unwrap_or(0);
         self.player_car_id = Some(player_car_idx_value as u32);
 
-        // player_car_class
+        // focus_car_id
+        self.focus_car_id = match self.is_spectating {
+            true => Some(raw_cam_car_idx_value as u32),
+            false => self.player_car_id,
+        };
+
+        // player_car_class, the class of the focus car
         let player_car_class_value = sim_state.read_name("PlayerCarClass").unwrap_or(0);
-        self.player_car_class = player_car_class_value as u32;
+        self.player_car_class = match self.is_spectating {
+            true => self
+                .focus_car_id
+                .and_then(|car_id| self.drivers.get(&car_id))
+                .map(|driver| driver.car_class_id)
+                .unwrap_or(player_car_class_value as u32),
+            false => player_car_class_value as u32,
+        };
+        if let Some(focus_car) = self
+            .focus_car_id
+            .and_then(|car_id| self.drivers.get(&car_id))
+            && self.player_car_class_name != focus_car.car_class_name
+        {
+            self.player_car_class_name = focus_car.car_class_name.clone();
+        }
 
         // lap
         let raw_lap_value = sim_state.read_name("Lap").unwrap_or(0);
@@ -252,16 +389,11 @@ impl SessionData {
         self.steering_angle = steering_angle_value;
 
         // proximity
-        // TODO: bitfield parsing doesn't work
         let raw_car_left_right_value = sim_state.read_name("CarLeftRight").unwrap_or(0);
-        let is_left = raw_car_left_right_value == 2
-            || raw_car_left_right_value == 4
-            || raw_car_left_right_value == 5;
-        let is_right = raw_car_left_right_value == 3
-            || raw_car_left_right_value == 4
-            || raw_car_left_right_value == 6;
-        self.is_left = is_left;
-        self.is_right = is_right;
+        let car_left_right = CarLeftRight::from_raw(raw_car_left_right_value);
+        self.car_left_right = car_left_right;
+        self.is_left = car_left_right.is_left();
+        self.is_right = car_left_right.is_right();
 
         // positions+distance
         let lap_dist_pct: Vec<f32> = sim_state.read_name("CarIdxLapDistPct").unwrap_or_default();
@@ -285,7 +417,21 @@ impl SessionData {
             .read_name("CarIdxTrackSurface")
             .unwrap_or_default();
 
+        // session_flags
+        let raw_session_flags_value = sim_state.read_name("SessionFlags").unwrap_or(0);
+        self.session_flags = SessionFlags::from_bits_retain(raw_session_flags_value);
+        let is_caution = self.session_flags.is_caution();
+
+        let car_idx_session_flags_value: Vec<u32> = sim_state
+            .read_name("CarIdxSessionFlags")
+            .unwrap_or_default();
+
+        let raw_lap_last_lap_time_value = sim_state.read_name("LapLastLapTime").unwrap_or(0.0);
+        let lap_last_lap_time_value = SignedDuration::from_secs_f32(raw_lap_last_lap_time_value);
+
+        // CarIdx arrays are not available in every source (e.g. .ibt files)
         for (car_id, driver) in self.drivers.iter_mut() {
+            let car_idx = *car_id as usize;
             let result_position_idx = self.results_positions_mapping.get(car_id);
             let result_position = if let Some(idx) = result_position_idx {
                 &self.results_positions[*idx]
@@ -293,19 +439,19 @@ impl SessionData {
                 &ResultsPosition::default()
             };
 
-            let lap_dist_pct_value = match lap_dist_pct[*car_id as usize] {
+            let lap_dist_pct_value = match lap_dist_pct.get(car_idx).copied().unwrap_or(0.0) {
                 value if value < 0.0 => 0.0,
                 value => value,
             };
-            let mut laps_completed_value = match laps_completed[*car_id as usize] {
+            let mut laps_completed_value = match laps_completed.get(car_idx).copied().unwrap_or(0) {
                 value if value >= UNLIMITED_LAPS || value <= 0 => 0,
                 value => value,
             } as u32;
-            let laps_started_value = match laps_started[*car_id as usize] {
+            let laps_started_value = match laps_started.get(car_idx).copied().unwrap_or(0) {
                 value if value >= UNLIMITED_LAPS || value <= 0 => 0,
                 value => value,
             } as u32;
-            let est_time_value = car_idx_est_time_value[*car_id as usize];
+            let est_time_value = car_idx_est_time_value.get(car_idx).copied().unwrap_or(0.0);
             if laps_started_value == 0 {
                 laps_completed_value = 0;
             }
@@ -313,20 +459,169 @@ impl SessionData {
             driver.laps_completed = laps_completed_value;
             driver.total_completed = laps_completed_value as f32 + lap_dist_pct_value;
             driver.estimated = SignedDuration::from_secs_f32(est_time_value);
-            driver.best_lap_time =
-                SignedDuration::from_secs_f32(car_idx_best_lap_time_value[*car_id as usize]);
+            driver.best_lap_time = SignedDuration::from_secs_f32(
+                car_idx_best_lap_time_value
+                    .get(car_idx)
+                    .copied()
+                    .unwrap_or(0.0),
+            );
             if !driver.best_lap_time.is_positive() && result_position.fastest_time.is_positive() {
                 driver.best_lap_time = result_position.fastest_time;
             }
-            driver.last_lap_time =
-                SignedDuration::from_secs_f32(car_idx_last_lap_time_value[*car_id as usize]);
+            driver.last_lap_time = SignedDuration::from_secs_f32(
+                car_idx_last_lap_time_value
+                    .get(car_idx)
+                    .copied()
+                    .unwrap_or(0.0),
+            );
             if !driver.last_lap_time.is_positive() && result_position.last_time.is_positive() {
                 driver.last_lap_time = result_position.last_time;
             }
-            driver.is_in_pits = car_idx_track_surface_value[*car_id as usize] == 1
-                || car_idx_track_surface_value[*car_id as usize] == 2;
-            driver.is_off_track = car_idx_track_surface_value[*car_id as usize] == 0;
-            driver.is_off_world = car_idx_track_surface_value[*car_id as usize] == -1;
+            let track_surface_value = car_idx_track_surface_value
+                .get(car_idx)
+                .copied()
+                .unwrap_or(-1);
+            driver.is_in_pits = track_surface_value == 1 || track_surface_value == 2;
+            driver.is_off_track = track_surface_value == 0;
+            driver.is_off_world = track_surface_value == -1;
+            driver.session_flags = SessionFlags::from_bits_retain(
+                car_idx_session_flags_value
+                    .get(car_idx)
+                    .copied()
+                    .unwrap_or(0),
+            );
+
+            // pit_history
+            driver.pit_history.update(
+                track_surface_value,
+                driver.laps_completed,
+                self.session_time,
+            );
+
+            // sector_timing
+            driver.sector_timing.update(
+                &self.session_info.split_time_info.sectors,
+                lap_dist_pct.get(car_idx).copied().unwrap_or(-1.0),
+                self.session_time,
+            );
+
+            // lap_history
+            let is_player = self.player_car_id == Some(*car_id);
+            let (history_laps_completed, history_last_lap_time) = match laps_completed.get(car_idx)
+            {
+                Some(_) => (driver.laps_completed, driver.last_lap_time),
+                // player laps are still known from the scalar vars
+                None if is_player => (self.lap.saturating_sub(1), lap_last_lap_time_value),
+                None => continue,
+            };
+            let completed_lap = driver.lap_history.update(LapHistoryTick {
+                laps_completed: history_laps_completed,
+                last_lap_time: history_last_lap_time,
+                session_time: self.session_time,
+                position: driver.position,
+                class_position: driver.class_position,
+                is_in_pits: driver.is_in_pits,
+                is_caution,
+            });
+            // player_lap_times
+            if is_player
+                && let Some(lap) = completed_lap
+                && lap.lap_time > SignedDuration::ZERO
+            {
+                self.player_lap_times
+                    .insert(0, LapTime::new(lap.lap, lap.lap_time));
+                self.last_lap_time = lap.lap_time;
+            }
+        }
+
+        // spotter
+        let player = self
+            .player_car_id
+            .and_then(|car_id| self.drivers.get(&car_id));
+        let track_length = self.session_info.weekend_info.track_length.unwrap_or(0.0) * 1000.0;
+        let car_distances = match player {
+            Some(player) if track_length > 0.0 => self
+                .drivers
+                .values()
+                .filter(|driver| {
+                    driver.car_id != player.car_id && !driver.is_in_pits && !driver.is_off_world
+                })
+                .map(|driver| {
+                    ((driver.lap_dist_pct - player.lap_dist_pct + 1.5).rem_euclid(1.0) - 0.5)
+                        * track_length
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        self.spotter.update(SpotterTick {
+            car_left_right,
+            session_time: self.session_time,
+            car_distances,
+        });
+
+        // flag
+        let player_session_flags = self
+            .player_car_id
+            .and_then(|car_id| self.drivers.get(&car_id))
+            .map(|driver| driver.session_flags)
+            .unwrap_or_default();
+        self.flag
+            .update((self.session_flags | player_session_flags).flag());
+
+        // fuel
+        let raw_fuel_level_value: f32 = sim_state.read_name("FuelLevel").unwrap_or(0.0);
+        let raw_fuel_level_pct_value: f32 = sim_state.read_name("FuelLevelPct").unwrap_or(0.0);
+        let raw_fuel_use_per_hour_value: f32 = sim_state.read_name("FuelUsePerHour").unwrap_or(0.0);
+        let raw_on_pit_road_value = sim_state.read_name("OnPitRoad").unwrap_or(false);
+        self.fuel.update(FuelTick {
+            fuel_level: raw_fuel_level_value,
+            fuel_level_pct: raw_fuel_level_pct_value,
+            fuel_use_per_hour: raw_fuel_use_per_hour_value,
+            laps_completed: self.lap.saturating_sub(1),
+            is_in_pits: raw_on_pit_road_value,
+            is_caution,
+        });
+
+        // weather
+        if should_process_slow {
+            let raw_air_temp_value = sim_state.read_name("AirTemp").unwrap_or(0.0);
+            let raw_track_temp_crew_value = sim_state.read_name("TrackTempCrew").unwrap_or(0.0);
+            let raw_track_wetness_value = sim_state.read_name("TrackWetness").unwrap_or(0);
+            let raw_precipitation_value = sim_state.read_name("Precipitation").unwrap_or(0.0);
+            let raw_wind_vel_value = sim_state.read_name("WindVel").unwrap_or(0.0);
+            let raw_wind_dir_value = sim_state.read_name("WindDir").unwrap_or(0.0);
+            let raw_skies_value = sim_state.read_name("Skies").unwrap_or(0);
+            let raw_fog_level_value = sim_state.read_name("FogLevel").unwrap_or(0.0);
+            self.weather.update(WeatherTick {
+                session_time: self.session_time,
+                air_temp: raw_air_temp_value,
+                track_temp: raw_track_temp_crew_value,
+                track_wetness: TrackWetness::from_raw(raw_track_wetness_value),
+                precipitation: raw_precipitation_value,
+                wind_vel: raw_wind_vel_value,
+                wind_dir: raw_wind_dir_value,
+                skies: Skies::from_raw(raw_skies_value),
+                fog_level: raw_fog_level_value,
+            });
+        }
+
+        // class_best_sector_times
+        self.class_best_sector_times.clear();
+        for driver in self.drivers.values() {
+            let best_sector_times = self
+                .class_best_sector_times
+                .entry(driver.car_class_id)
+                .or_insert_with(|| vec![None; driver.sector_timing.best.len()]);
+            for (best, driver_best) in best_sector_times
+                .iter_mut()
+                .zip(driver.sector_timing.best.iter())
+            {
+                if let Some(driver_best) = driver_best
+                    && best.is_none_or(|best| *driver_best < best)
+                {
+                    *best = Some(*driver_best);
+                }
+            }
         }
 
         let mut driver_positions = self
@@ -334,6 +629,8 @@ impl SessionData {
             .iter()
             .map(|(car_id, driver)| (*car_id, driver))
             .collect::<Vec<(u32, &Driver)>>();
+        // the sorts below are stable, start from car order so ties don't depend on map iteration
+        driver_positions.sort_by_key(|(car_id, _)| *car_id);
         driver_positions.sort_by(|a, b| {
             a.1.result_position
                 .unwrap_or(0)
@@ -365,6 +662,7 @@ impl SessionData {
         self.driver_positions = driver_positions.iter().map(|(car_id, _)| *car_id).collect();
 
         let mut car_class_positions_count: HashMap<u32, u32> = HashMap::new();
+        self.class_leader_car_ids.clear();
         let mut position_total: u32 = 0;
         let mut player_class_driver_positions: Vec<u32> = Vec::new();
 
@@ -380,10 +678,19 @@ impl SessionData {
             *class_position += 1;
             driver.class_position = *class_position;
             driver.position = position as u32 + 1;
-            if self.player_car_id.is_some() && *car_id == self.player_car_id.unwrap() {
+            if self.session_type == SessionType::Race {
+                driver
+                    .position_history
+                    .update(driver.laps_completed, driver.class_position);
+            }
+            if self.focus_car_id == Some(*car_id) {
                 self.position = driver.position;
                 self.class_position = driver.class_position;
             }
+            if driver.class_position == 1 {
+                self.class_leader_car_ids
+                    .insert(driver.car_class_id, *car_id);
+            }
             if driver.car_class_id == self.player_car_class && driver.class_position == 1 {
                 self.leader_car_id = *car_id;
             }
@@ -406,30 +713,77 @@ impl SessionData {
         // positions_total
         self.positions_total = position_total;
 
-        // gaps
-        if !self.driver_positions.is_empty() && self.player_car_id.is_some() {
-            let player = self.drivers.get(&self.player_car_id.unwrap());
-            if player.is_none() {
-                error!("Player not found");
+        // irating_change
+        if self.session_type == SessionType::Race {
+            let mut class_car_ids: HashMap<u32, Vec<u32>> = HashMap::new();
+            for car_id in self.driver_positions.iter() {
+                if let Some(driver) = self.drivers.get(car_id) {
+                    class_car_ids
+                        .entry(driver.car_class_id)
+                        .or_default()
+                        .push(*car_id);
+                }
             }
-            let player = player.unwrap();
+            for car_ids in class_car_ids.values_mut() {
+                // retired cars are scored behind the ones still running
+                car_ids.sort_by_key(|car_id| self.drivers[car_id].is_out);
+                let entries = car_ids
+                    .iter()
+                    .map(|car_id| {
+                        let driver = &self.drivers[car_id];
+                        RatingEntry {
+                            irating: driver.irating,
+                            is_starter: driver.laps_completed > 0
+                                || driver.result_position.is_some(),
+                        }
+                    })
+                    .collect::<Vec<RatingEntry>>();
+                for (car_id, change) in car_ids.iter().zip(irating_changes(&entries)) {
+                    if let Some(driver) = self.drivers.get_mut(car_id) {
+                        driver.irating_change = change;
+                    }
+                }
+            }
+        }
+
+        // gaps
+        let focus_car = self
+            .focus_car_id
+            .and_then(|car_id| self.drivers.get(&car_id));
+        if !self.driver_positions.is_empty() && self.focus_car_id.is_some() && focus_car.is_none() {
+            error!("Player not found");
+        }
+        if !self.driver_positions.is_empty()
+            && let Some(player) = focus_car
+        {
             let player_total_completed = player.total_completed;
             let player_estimated = player.estimated;
             let player_dist_pct = player.lap_dist_pct;
             let player_car_class_est_lap_time = player.car_class_est_lap_time;
 
-            let leader = self.drivers.get(&self.leader_car_id);
-            if leader.is_none() {
+            if !self.drivers.contains_key(&self.leader_car_id) {
                 error!("Leader not found");
             }
-            let leader = leader.unwrap();
-            let leader_total_completed = leader.total_completed;
-            let leader_estimated = leader.estimated;
+            // gaps are to the leader of the driver's own class
+            let class_leaders = self
+                .class_leader_car_ids
+                .iter()
+                .filter_map(|(car_class_id, car_id)| {
+                    let leader = self.drivers.get(car_id)?;
+                    Some((*car_class_id, (leader.total_completed, leader.estimated)))
+                })
+                .collect::<HashMap<u32, (f32, SignedDuration)>>();
 
             for driver in self.drivers.values_mut() {
                 driver.is_leader = driver.car_id == self.leader_car_id;
-                driver.is_player =
-                    self.player_car_id.is_some() && driver.car_id == self.player_car_id.unwrap();
+                driver.is_class_leader =
+                    self.class_leader_car_ids.get(&driver.car_class_id) == Some(&driver.car_id);
+                let (leader_total_completed, leader_estimated) = class_leaders
+                    .get(&driver.car_class_id)
+                    .copied()
+                    .unwrap_or((driver.total_completed, driver.estimated));
+                driver.is_focus = self.focus_car_id == Some(driver.car_id);
+                driver.is_player_class = driver.car_class_id == self.player_car_class;
                 let leader_gap_laps = leader_total_completed - driver.total_completed;
                 if leader_gap_laps >= 1.0 {
                     driver.leader_gap_laps = leader_gap_laps as i32;
@@ -485,238 +839,114 @@ impl SessionData {
             }
         }
 
-        // extra slow vars
-        if should_process_slow {
-            // player_lap_times
-            if self.lap > 1 {
-                let raw_lap_last_lap_time_value =
-                    sim_state.read_name("LapLastLapTime").unwrap_or(0.0);
-                let lap_last_lap_time_value =
-                    SignedDuration::from_secs_f32(raw_lap_last_lap_time_value);
-                match lap_last_lap_time_value {
-                    value if value.is_positive() => {
-                        // TODO: This check is not exactly safe
-                        if lap_last_lap_time_value != self.last_lap_time {
-                            self.player_lap_times
-                                .insert(0, LapTime::new(self.lap - 1, value));
-                            self.last_lap_time = value;
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
-
-        let session_info_update = sim_state.header().session_info_update;
+        let session_info_update = sim_state.session_info_update();
         if self.session_info_update != session_info_update {
             debug!("Session info updated");
-            let session = sim_state.session_info();
+            let session_info = SessionInfo::from_yaml(sim_state.session_info());
 
             // incident_limit
-            let incident_limit = &session["WeekendInfo"]["WeekendOptions"]["IncidentLimit"];
-            let incident_limit_value = match incident_limit.as_str() {
-                Some(_) => 0u32,
-                None => match incident_limit.as_i64() {
-                    Some(value) => value as u32,
-                    None => 0u32,
-                },
-            };
-            self.incident_limit = incident_limit_value;
+            self.incident_limit = session_info
+                .weekend_info
+                .weekend_options
+                .incident_limit
+                .unwrap_or(0);
 
             // track_id
-            let track_id = session["WeekendInfo"]["TrackID"].as_i64().unwrap_or(0) as u32;
-            self.track_id = track_id;
+            self.track_id = session_info.weekend_info.track_id.unwrap_or(0);
 
             // session
-            let sessions = session["SessionInfo"]["Sessions"].as_vec();
-            if sessions.is_some() {
-                let sessions = sessions.unwrap();
-                for session in sessions {
-                    let session_num = session["SessionNum"].as_i64();
-                    let session_type = session["SessionType"].as_str();
-                    if session_num.is_none()
-                        || session_type.is_none()
-                        || session_num.unwrap() as u32 != self.session_num
-                    {
-                        continue;
-                    }
-                    let session_type = session_type.unwrap().to_string();
-                    self.session_type = SessionType::from_str(&session_type).unwrap();
-
-                    let results_positions = session["ResultsPositions"].as_vec();
-                    if results_positions.is_some() {
-                        let results_positions = results_positions.unwrap();
-                        for (i, result_position) in results_positions.iter().enumerate() {
-                            let car_id = result_position["CarIdx"].as_i64().unwrap_or(0) as u32;
-                            let position = result_position["Position"].as_i64().unwrap_or(0) as u32;
-                            let class_position =
-                                (result_position["ClassPosition"].as_i64().unwrap_or(0) + 1) as u32;
-                            let fastest_time =
-                                result_position["FastestTime"].as_f64().unwrap_or(0.0);
-                            let fastest_time = SignedDuration::from_secs_f64(fastest_time);
-                            let last_time = result_position["LastTime"].as_f64().unwrap_or(0.0);
-                            let last_time = SignedDuration::from_secs_f64(last_time);
-                            let reason_out_id =
-                                result_position["ReasonOutID"].as_i64().unwrap_or(0) as u32;
-
-                            let results_position = ResultsPosition {
-                                car_id,
-                                position,
-                                class_position,
-                                fastest_time,
-                                last_time,
-                                reason_out_id,
-                            };
-
-                            self.results_positions.push(results_position);
-                            self.results_positions_mapping.insert(car_id, i);
-                        }
-                    }
-
-                    let results_official = session["ResultsOfficial"].as_i64().unwrap_or(0) != 0;
-                    self.results_official = results_official;
+            self.results_positions.clear();
+            self.results_positions_mapping.clear();
+            if let Some(session) = session_info.session(self.session_num) {
+                if let Some(session_type) = &session.session_type {
+                    self.session_type = SessionType::from_str(session_type).unwrap();
                 }
+                for (i, results_position) in session.results_positions.iter().enumerate() {
+                    self.results_positions_mapping
+                        .insert(results_position.car_id, i);
+                }
+                self.results_positions = session.results_positions.clone();
+                self.results_official = session.results_official;
             }
 
-            let drivers = session["DriverInfo"]["Drivers"].as_vec();
-
-            match drivers {
-                Some(drivers) => {
-                    for driver in drivers {
-                        let car_id = driver["CarIdx"].as_i64();
-                        if car_id.is_none() {
-                            error!("CarIdx not found");
-                            continue;
-                        }
-                        let car_id = car_id.unwrap() as u32;
-
-                        let user_name = driver["UserName"].as_str();
-                        if user_name.is_none() {
-                            error!("UserName not found");
-                            continue;
-                        }
-                        let user_name = user_name.unwrap().to_string();
-
-                        let team_name = driver["TeamName"].as_str();
-                        if team_name.is_none() {
-                            error!("TeamName not found");
-                            continue;
-                        }
-                        let team_name = team_name.unwrap().to_string();
-
-                        // Skip pace car
-                        if user_name == "Pace Car" {
-                            continue;
-                        }
-
-                        if self.player_car_id.is_some() && car_id == self.player_car_id.unwrap() {
-                            let car_class_short_name = driver["CarClassShortName"].as_str();
-                            if car_class_short_name.is_none() {
-                                // iRacing doesn't provide this value in AI races: https://github.com/SHWotever/SimHub/issues/1847
-                                let car_screen_name_short = driver["CarScreenNameShort"].as_str();
-                                if car_screen_name_short.is_none() {
-                                    error!("CarScreenNameShort not found");
-                                } else {
-                                    let car_screen_name_short =
-                                        car_screen_name_short.unwrap().to_string();
-                                    self.player_car_class_name = car_screen_name_short;
-                                }
-                            } else {
-                                let car_class_short_name =
-                                    car_class_short_name.unwrap().to_string();
-                                self.player_car_class_name = car_class_short_name;
-                            }
-                        }
-
-                        let car_number = driver["CarNumber"].as_str();
-                        if car_number.is_none() {
-                            error!("CarNumber not found");
-                            continue;
-                        }
-                        let car_number = car_number.unwrap().to_string();
-
-                        let car_class_id = driver["CarClassID"].as_i64();
-                        if car_class_id.is_none() {
-                            error!("CarClassID not found");
-                            continue;
-                        }
-                        let car_class_id = car_class_id.unwrap() as u32;
-
-                        let irating = driver["IRating"].as_i64();
-                        if irating.is_none() {
-                            error!("IRating not found");
-                            continue;
-                        }
-                        let irating = irating.unwrap() as u32;
+            if session_info.driver_info.drivers.is_empty() {
+                error!("No drivers found");
+                return result;
+            }
 
-                        let lic_string = driver["LicString"].as_str();
-                        if lic_string.is_none() {
-                            error!("LicString not found");
-                            continue;
-                        }
-                        let lic_string = lic_string.unwrap();
+            for driver_entry in session_info.driver_info.drivers.iter() {
+                // Skip pace car
+                if driver_entry.is_pace_car() {
+                    continue;
+                }
 
-                        let car_class_est_lap_time = driver["CarClassEstLapTime"].as_f64();
-                        if car_class_est_lap_time.is_none() {
-                            error!("CarClassEstLapTime not found");
-                            continue;
+                let car_id = driver_entry.car_idx;
+                if self.focus_car_id == Some(car_id) {
+                    match driver_entry.car_class_name() {
+                        Some(car_class_name) => {
+                            self.player_car_class_name = car_class_name.to_string()
                         }
-                        let car_class_est_lap_time = car_class_est_lap_time.unwrap();
-                        let car_class_est_lap_time =
-                            SignedDuration::from_secs_f64(car_class_est_lap_time);
-
-                        let car_class_color = driver["CarClassColor"].as_i64();
-                        if car_class_color.is_none() {
-                            error!("CarClassColor not found");
-                            continue;
-                        }
-                        let car_class_color = car_class_color.unwrap() as u32;
-
-                        let driver_results_position = self.results_positions_mapping.get(&car_id);
-                        let mut result_position: Option<u32> = None;
-                        let mut result_class_position: Option<u32> = None;
-                        let mut is_out = false;
-                        if let Some(position) = driver_results_position {
-                            result_position = Some(self.results_positions[*position].position);
-                            result_class_position =
-                                Some(self.results_positions[*position].class_position);
-                            is_out = self.results_positions[*position].reason_out_id != 0;
-                        }
-
-                        let driver = self.drivers.get_mut(&car_id);
-                        let driver = match driver {
-                            Some(driver) => driver,
-                            None => {
-                                let mut new_driver = Driver::builder()
-                                    .car_id(car_id)
-                                    .user_name(user_name)
-                                    .car_number(car_number)
-                                    .car_class_id(car_class_id)
-                                    .irating(irating)
-                                    .lic_string(lic_string.to_string())
-                                    .car_class_est_lap_time(car_class_est_lap_time)
-                                    .is_player_class(self.player_car_class == car_class_id)
-                                    .car_class_color(car_class_color)
-                                    .team_name(team_name)
-                                    .build();
-                                new_driver.is_out = is_out;
-                                new_driver.result_position = result_position;
-                                new_driver.result_class_position = result_class_position;
-                                self.drivers.insert(car_id, new_driver);
-                                self.drivers.get_mut(&car_id).unwrap()
-                            }
-                        };
-                        driver.is_out = is_out;
-                        driver.result_position = result_position;
-                        driver.result_class_position = result_class_position;
+                        None => error!("CarScreenNameShort not found"),
                     }
                 }
-                None => {
-                    error!("No drivers found");
-                    return ProcessTickResult::None;
+
+                let driver_results_position = self.results_positions_mapping.get(&car_id);
+                let mut result_position: Option<u32> = None;
+                let mut result_class_position: Option<u32> = None;
+                let mut is_out = false;
+                if let Some(position) = driver_results_position {
+                    result_position = Some(self.results_positions[*position].position);
+                    result_class_position = Some(self.results_positions[*position].class_position);
+                    is_out = self.results_positions[*position].reason_out_id != 0;
+                }
+
+                let car_class_id = driver_entry.car_class_id.unwrap_or(0);
+                let driver = self.drivers.entry(car_id).or_insert_with(|| {
+                    Driver::builder()
+                        .car_id(car_id)
+                        .user_name(driver_entry.user_name.clone().unwrap_or_default())
+                        .car_number(driver_entry.car_number.clone().unwrap_or_default())
+                        .car_class_id(car_class_id)
+                        .irating(driver_entry.irating.unwrap_or(0))
+                        .lic_string(driver_entry.lic_string.clone().unwrap_or_default())
+                        .car_class_est_lap_time(SignedDuration::from_secs_f64(
+                            driver_entry.car_class_est_lap_time.unwrap_or(0.0),
+                        ))
+                        .is_player_class(self.player_car_class == car_class_id)
+                        .car_class_color(driver_entry.car_class_color.unwrap_or(0))
+                        .car_class_name(
+                            driver_entry
+                                .car_class_name()
+                                .unwrap_or_default()
+                                .to_string(),
+                        )
+                        .team_name(driver_entry.team_name.clone().unwrap_or_default())
+                        .car_model_id(driver_entry.car_id.unwrap_or(0))
+                        .club_name(driver_entry.club_name.clone().unwrap_or_default())
+                        .build()
+                });
+                driver.is_out = is_out;
+                driver.result_position = result_position;
+                driver.result_class_position = result_class_position;
+
+                // start_position, the grid comes from qualifying or from the first race results
+                if self.session_type == SessionType::Race
+                    && driver.position_history.start_position.is_none()
+                {
+                    driver.position_history.start_position = session_info
+                        .qualify_results
+                        .iter()
+                        .find(|result| result.car_id == car_id)
+                        .map(|result| result.class_position)
+                        .or(result_class_position);
                 }
             }
 
+            // weekend weather
+            self.weather.weekend = WeekendWeather::from_weekend_info(&session_info.weekend_info);
+
+            self.session_info = session_info;
+
             if !self.drivers.is_empty() {
                 // strength_of_field
                 let strength_of_field = get_strength_of_field(self);
@@ -725,6 +955,6 @@ impl SessionData {
 
             self.session_info_update = session_info_update;
         };
-        ProcessTickResult::None
+        result
     }
 }
@@ -0,0 +1,150 @@
+use bitflags::bitflags;
+use strum_macros::Display;
+
+bitflags! {
+    /// irsdk_Flags, reported for the session in `SessionFlags` and per car in `CarIdxSessionFlags`
+    #[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+    pub struct SessionFlags: u32 {
+        const CHECKERED = 0x0000_0001;
+        const WHITE = 0x0000_0002;
+        const GREEN = 0x0000_0004;
+        const YELLOW = 0x0000_0008;
+        const RED = 0x0000_0010;
+        const BLUE = 0x0000_0020;
+        const DEBRIS = 0x0000_0040;
+        const CROSSED = 0x0000_0080;
+        const YELLOW_WAVING = 0x0000_0100;
+        const ONE_LAP_TO_GREEN = 0x0000_0200;
+        const GREEN_HELD = 0x0000_0400;
+        const TEN_TO_GO = 0x0000_0800;
+        const FIVE_TO_GO = 0x0000_1000;
+        const RANDOM_WAVING = 0x0000_2000;
+        const CAUTION = 0x0000_4000;
+        const CAUTION_WAVING = 0x0000_8000;
+        const BLACK = 0x0001_0000;
+        const DISQUALIFY = 0x0002_0000;
+        const SERVICIBLE = 0x0004_0000;
+        const FURLED = 0x0008_0000;
+        /// Meatball, the car has to pit for repairs
+        const REPAIR = 0x0010_0000;
+        const START_HIDDEN = 0x1000_0000;
+        const START_READY = 0x2000_0000;
+        const START_SET = 0x4000_0000;
+        const START_GO = 0x8000_0000;
+    }
+}
+
+/// The single flag a marshal would be showing, most important first
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum Flag {
+    #[default]
+    None,
+    Disqualified,
+    Black,
+    Red,
+    Meatball,
+    Checkered,
+    Caution,
+    Yellow,
+    Blue,
+    White,
+    Green,
+}
+
+impl SessionFlags {
+    pub fn is_caution(self) -> bool {
+        self.intersects(Self::CAUTION | Self::CAUTION_WAVING)
+    }
+
+    pub fn flag(self) -> Flag {
+        if self.contains(Self::DISQUALIFY) {
+            Flag::Disqualified
+        } else if self.contains(Self::BLACK) {
+            Flag::Black
+        } else if self.contains(Self::RED) {
+            Flag::Red
+        } else if self.contains(Self::REPAIR) {
+            Flag::Meatball
+        } else if self.contains(Self::CHECKERED) {
+            Flag::Checkered
+        } else if self.is_caution() {
+            Flag::Caution
+        } else if self.intersects(Self::YELLOW | Self::YELLOW_WAVING) {
+            Flag::Yellow
+        } else if self.contains(Self::BLUE) {
+            Flag::Blue
+        } else if self.contains(Self::WHITE) {
+            Flag::White
+        } else if self.intersects(Self::GREEN | Self::GREEN_HELD | Self::START_GO) {
+            Flag::Green
+        } else {
+            Flag::None
+        }
+    }
+
+    /// snake_case names of the set flags, e.g. `["green", "ten_to_go"]`
+    pub fn names(self) -> Vec<String> {
+        self.iter_names()
+            .map(|(name, _)| name.to_lowercase())
+            .collect()
+    }
+}
+
+/// Change of the flag shown to the player, combining session and player car flags
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct FlagTransition {
+    pub flag: Flag,
+    pub previous_flag: Flag,
+}
+
+impl FlagTransition {
+    pub fn update(&mut self, flag: Flag) {
+        if flag != self.flag {
+            self.previous_flag = self.flag;
+            self.flag = flag;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_raw_flags() {
+        let flags = SessionFlags::from_bits_retain(0x0010_0804);
+        assert!(flags.contains(SessionFlags::GREEN | SessionFlags::TEN_TO_GO));
+        assert_eq!(flags.flag(), Flag::Meatball);
+        assert_eq!(flags.names(), vec!["green", "ten_to_go", "repair"]);
+        assert!(!flags.is_caution());
+        assert!(SessionFlags::from_bits_retain(0x8000).is_caution());
+    }
+
+    #[test]
+    fn picks_the_most_important_flag() {
+        assert_eq!(SessionFlags::empty().flag(), Flag::None);
+        assert_eq!(
+            (SessionFlags::GREEN | SessionFlags::BLUE).flag(),
+            Flag::Blue
+        );
+        assert_eq!(
+            (SessionFlags::WHITE | SessionFlags::CAUTION_WAVING).flag(),
+            Flag::Caution
+        );
+        assert_eq!(
+            (SessionFlags::CHECKERED | SessionFlags::BLACK).flag(),
+            Flag::Black
+        );
+    }
+
+    #[test]
+    fn keeps_previous_flag_on_transitions_only() {
+        let mut transition = FlagTransition::default();
+        transition.update(Flag::Green);
+        transition.update(Flag::Green);
+        transition.update(Flag::White);
+        assert_eq!(transition.flag, Flag::White);
+        assert_eq!(transition.previous_flag, Flag::Green);
+    }
+}
@@ -0,0 +1,203 @@
+use yaml_rust::Yaml;
+
+use crate::util::yaml_value::{yaml_bool, yaml_f64, yaml_string, yaml_u32};
+
+use super::car_setup::CarSetup;
+use super::driver_info::DriverInfo;
+use super::qualify_result::QualifyResult;
+use super::results_position::ResultsPosition;
+use super::split_time_info::SplitTimeInfo;
+use super::weekend_info::WeekendInfo;
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Session {
+    pub session_num: u32,
+    /// `None` for sessions with unlimited laps
+    pub session_laps: Option<u32>,
+    /// `None` for sessions with unlimited time, in seconds
+    pub session_time: Option<f64>,
+    pub session_num_laps_to_avg: Option<u32>,
+    pub session_type: Option<String>,
+    pub session_track_rubber_state: Option<String>,
+    pub session_name: Option<String>,
+    pub session_sub_type: Option<String>,
+    pub session_skipped: Option<bool>,
+    pub session_run_groups_used: Option<bool>,
+    pub session_enforce_tire_compound_change: Option<bool>,
+    pub results_positions: Vec<ResultsPosition>,
+    pub results_average_lap_time: Option<f64>,
+    pub results_num_caution_flags: Option<u32>,
+    pub results_num_caution_laps: Option<u32>,
+    pub results_num_lead_changes: Option<u32>,
+    pub results_laps_complete: Option<u32>,
+    pub results_official: bool,
+}
+
+impl Session {
+    pub fn from_yaml(yaml: &Yaml) -> Self {
+        Self {
+            session_num: yaml_u32(&yaml["SessionNum"]).unwrap_or(0),
+            session_laps: yaml_u32(&yaml["SessionLaps"]),
+            session_time: yaml_f64(&yaml["SessionTime"]),
+            session_num_laps_to_avg: yaml_u32(&yaml["SessionNumLapsToAvg"]),
+            session_type: yaml_string(&yaml["SessionType"]),
+            session_track_rubber_state: yaml_string(&yaml["SessionTrackRubberState"]),
+            session_name: yaml_string(&yaml["SessionName"]),
+            session_sub_type: yaml_string(&yaml["SessionSubType"]),
+            session_skipped: yaml_bool(&yaml["SessionSkipped"]),
+            session_run_groups_used: yaml_bool(&yaml["SessionRunGroupsUsed"]),
+            session_enforce_tire_compound_change: yaml_bool(
+                &yaml["SessionEnforceTireCompoundChange"],
+            ),
+            results_positions: yaml["ResultsPositions"]
+                .as_vec()
+                .map(|positions| positions.iter().map(ResultsPosition::from_yaml).collect())
+                .unwrap_or_default(),
+            results_average_lap_time: yaml_f64(&yaml["ResultsAverageLapTime"]),
+            results_num_caution_flags: yaml_u32(&yaml["ResultsNumCautionFlags"]),
+            results_num_caution_laps: yaml_u32(&yaml["ResultsNumCautionLaps"]),
+            results_num_lead_changes: yaml_u32(&yaml["ResultsNumLeadChanges"]),
+            results_laps_complete: yaml_u32(&yaml["ResultsLapsComplete"]),
+            results_official: yaml_bool(&yaml["ResultsOfficial"]).unwrap_or(false),
+        }
+    }
+}
+
+/// Typed view of the session info YAML, every field the sim may omit is optional
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SessionInfo {
+    pub weekend_info: WeekendInfo,
+    pub sessions: Vec<Session>,
+    pub qualify_results: Vec<QualifyResult>,
+    pub driver_info: DriverInfo,
+    pub split_time_info: SplitTimeInfo,
+    pub car_setup: Option<CarSetup>,
+}
+
+impl SessionInfo {
+    pub fn from_yaml(yaml: &Yaml) -> Self {
+        Self {
+            weekend_info: WeekendInfo::from_yaml(&yaml["WeekendInfo"]),
+            sessions: yaml["SessionInfo"]["Sessions"]
+                .as_vec()
+                .map(|sessions| sessions.iter().map(Session::from_yaml).collect())
+                .unwrap_or_default(),
+            qualify_results: yaml["QualifyResultsInfo"]["Results"]
+                .as_vec()
+                .map(|results| results.iter().map(QualifyResult::from_yaml).collect())
+                .unwrap_or_default(),
+            driver_info: DriverInfo::from_yaml(&yaml["DriverInfo"]),
+            split_time_info: SplitTimeInfo::from_yaml(&yaml["SplitTimeInfo"]),
+            car_setup: CarSetup::from_yaml(&yaml["CarSetup"]),
+        }
+    }
+
+    pub fn session(&self, session_num: u32) -> Option<&Session> {
+        self.sessions
+            .iter()
+            .find(|session| session.session_num == session_num)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use yaml_rust::YamlLoader;
+
+    use super::*;
+
+    /// Trimmed from a real session, `CarNumber` is unquoted and most fields are missing
+    const SESSION_INFO: &str = r#"
+WeekendInfo:
+ TrackName: spa 2024 up
+ TrackID: 525
+ TrackLength: 6.93 km
+ TrackSkies: Partly Cloudy
+ TrackSurfaceTemp: 33.54 C
+ TrackRelativeHumidity: 62 %
+ WeekendOptions:
+  NumStarters: 2
+  IncidentLimit: unlimited
+SessionInfo:
+ Sessions:
+ - SessionNum: 0
+   SessionLaps: unlimited
+   SessionTime: 1800.0000 sec
+   SessionType: Practice
+   ResultsPositions:
+ - SessionNum: 2
+   SessionLaps: 12
+   SessionTime: unlimited
+   SessionType: Race
+   ResultsPositions:
+   - Position: 1
+     ClassPosition: 0
+     CarIdx: 3
+     Lap: 5
+     FastestTime: 137.8120
+     LapsComplete: 5
+     ReasonOutStr: Running
+DriverInfo:
+ DriverCarIdx: 3
+ DriverCarRedLine: 7500.000
+ Drivers:
+ - CarIdx: 0
+   UserName: Pace Car
+   CarIsPaceCar: 1
+ - CarIdx: 3
+   UserName: Jane Doe
+   CarNumber: 12
+   CarClassColor: 0xffda59
+   IRating: 2345
+   LicString: A 3.41
+ - UserName: Missing CarIdx
+SplitTimeInfo:
+ Sectors:
+ - SectorNum: 1
+   SectorStartPct: 0.512
+ - SectorNum: 0
+   SectorStartPct: 0.000000
+"#;
+
+    #[test]
+    fn parses_a_trimmed_session_info() {
+        let yaml = YamlLoader::load_from_str(SESSION_INFO).unwrap().remove(0);
+        let session_info = SessionInfo::from_yaml(&yaml);
+
+        let weekend_info = &session_info.weekend_info;
+        assert_eq!(weekend_info.track_length, Some(6.93));
+        assert_eq!(weekend_info.track_relative_humidity, Some(62.0));
+        assert_eq!(weekend_info.track_air_temp, None);
+        assert_eq!(weekend_info.weekend_options.num_starters, Some(2));
+        assert_eq!(weekend_info.weekend_options.incident_limit, None);
+
+        let practice = session_info.session(0).unwrap();
+        assert_eq!(practice.session_laps, None);
+        assert_eq!(practice.session_time, Some(1800.0));
+        assert!(practice.results_positions.is_empty());
+        let race = session_info.session(2).unwrap();
+        assert_eq!(race.session_laps, Some(12));
+        assert_eq!(race.session_time, None);
+        assert_eq!(race.results_positions[0].car_id, 3);
+        assert_eq!(race.results_positions[0].class_position, 1);
+        assert_eq!(race.results_positions[0].laps_complete, 5);
+        assert!(session_info.session(1).is_none());
+
+        let driver_info = &session_info.driver_info;
+        assert_eq!(driver_info.driver_car_idx, Some(3));
+        assert_eq!(driver_info.driver_car_red_line, Some(7500.0));
+        assert_eq!(driver_info.drivers.len(), 2);
+        assert!(driver_info.drivers[0].is_pace_car());
+        let driver = &driver_info.drivers[1];
+        assert_eq!(driver.car_number.as_deref(), Some("12"));
+        assert_eq!(driver.car_class_color, Some(0xffda59));
+        assert_eq!(driver.irating, Some(2345));
+        assert_eq!(driver.car_class_id, None);
+        assert!(!driver.car_is_pace_car);
+
+        let sectors = &session_info.split_time_info.sectors;
+        assert_eq!(sectors[0].sector_num, 0);
+        assert_eq!(sectors[1].sector_start_pct, 0.512);
+        assert!(session_info.qualify_results.is_empty());
+        assert!(session_info.car_setup.is_none());
+    }
+}
@@ -0,0 +1,35 @@
+use yaml_rust::Yaml;
+
+use crate::util::yaml_value::{yaml_f32, yaml_u32};
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Sector {
+    pub sector_num: u32,
+    pub sector_start_pct: f32,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SplitTimeInfo {
+    pub sectors: Vec<Sector>,
+}
+
+impl SplitTimeInfo {
+    pub fn from_yaml(yaml: &Yaml) -> Self {
+        let mut sectors = yaml["Sectors"]
+            .as_vec()
+            .map(|sectors| {
+                sectors
+                    .iter()
+                    .filter_map(|sector| {
+                        Some(Sector {
+                            sector_num: yaml_u32(&sector["SectorNum"])?,
+                            sector_start_pct: yaml_f32(&sector["SectorStartPct"])?,
+                        })
+                    })
+                    .collect::<Vec<Sector>>()
+            })
+            .unwrap_or_default();
+        sectors.sort_by_key(|sector| sector.sector_num);
+        Self { sectors }
+    }
+}
@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use strum_macros::Display;
+
+use super::car_left_right::CarLeftRight;
+
+const CAR_LENGTH: f32 = 4.5; // in m
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum SpotterState {
+    #[default]
+    Off,
+    Clear,
+    CarLeft,
+    CarRight,
+    ThreeWide,
+    TwoCarsLeft,
+    TwoCarsRight,
+}
+
+impl From<CarLeftRight> for SpotterState {
+    fn from(value: CarLeftRight) -> Self {
+        match value {
+            CarLeftRight::Off => Self::Off,
+            CarLeftRight::Clear => Self::Clear,
+            CarLeftRight::CarLeft => Self::CarLeft,
+            CarLeftRight::CarRight => Self::CarRight,
+            CarLeftRight::CarLeftRight => Self::ThreeWide,
+            CarLeftRight::TwoCarsLeft => Self::TwoCarsLeft,
+            CarLeftRight::TwoCarsRight => Self::TwoCarsRight,
+        }
+    }
+}
+
+pub struct SpotterTick {
+    pub car_left_right: CarLeftRight,
+    pub session_time: Duration,
+    /// Distance along the track from the player to every other car on track, in m, ahead is positive
+    pub car_distances: Vec<f32>,
+}
+
+/// Spotter calls of the player car, with the overlap of the closest car alongside
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct Spotter {
+    pub state: SpotterState,
+    pub previous_state: SpotterState,
+    pub changed_at: Duration,
+    /// Share of the player car covered by the closest car alongside, 0..1
+    pub overlap: f32,
+    /// The closest car alongside is ahead of the player
+    pub is_ahead: bool,
+}
+
+impl Spotter {
+    pub fn update(&mut self, tick: SpotterTick) {
+        let state = SpotterState::from(tick.car_left_right);
+        if state != self.state {
+            self.previous_state = self.state;
+            self.state = state;
+            self.changed_at = tick.session_time;
+        }
+
+        let closest = match state {
+            SpotterState::Off | SpotterState::Clear => None,
+            _ => tick
+                .car_distances
+                .iter()
+                .copied()
+                .min_by(|a, b| a.abs().total_cmp(&b.abs())),
+        };
+        (self.overlap, self.is_ahead) = match closest {
+            Some(distance) => (
+                (1.0 - distance.abs() / CAR_LENGTH).clamp(0.0, 1.0),
+                distance > 0.0,
+            ),
+            None => (0.0, false),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(car_left_right: CarLeftRight, secs: u64, car_distances: &[f32]) -> SpotterTick {
+        SpotterTick {
+            car_left_right,
+            session_time: Duration::from_secs(secs),
+            car_distances: car_distances.to_vec(),
+        }
+    }
+
+    #[test]
+    fn records_transitions_with_timestamps() {
+        let mut spotter = Spotter::default();
+        spotter.update(tick(CarLeftRight::Clear, 10, &[]));
+        spotter.update(tick(CarLeftRight::CarLeft, 12, &[-2.0]));
+        spotter.update(tick(CarLeftRight::CarLeft, 13, &[-1.0]));
+        assert_eq!(spotter.state, SpotterState::CarLeft);
+        assert_eq!(spotter.previous_state, SpotterState::Clear);
+        assert_eq!(spotter.changed_at, Duration::from_secs(12));
+        spotter.update(tick(CarLeftRight::CarLeftRight, 14, &[]));
+        assert_eq!(spotter.state, SpotterState::ThreeWide);
+    }
+
+    #[test]
+    fn measures_overlap_of_the_closest_car() {
+        let mut spotter = Spotter::default();
+        spotter.update(tick(CarLeftRight::TwoCarsRight, 10, &[-3.6, 0.9, 40.0]));
+        assert!((spotter.overlap - 0.8).abs() < 1e-4);
+        assert!(spotter.is_ahead);
+        spotter.update(tick(CarLeftRight::Clear, 11, &[5.0]));
+        assert_eq!(spotter.overlap, 0.0);
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use strum_macros::Display;
+
+use super::weekend_info::WeekendInfo;
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+const MAX_HISTORY: Duration = Duration::from_secs(30 * 60);
+const TRACK_TEMP_THRESHOLD: f32 = 0.5; // in C
+const PRECIPITATION_THRESHOLD: f32 = 0.05;
+
+/// irsdk_TrackWetness
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum TrackWetness {
+    #[default]
+    Unknown,
+    Dry,
+    MostlyDry,
+    VeryLightlyWet,
+    LightlyWet,
+    ModeratelyWet,
+    VeryWet,
+    ExtremelyWet,
+}
+
+impl TrackWetness {
+    pub fn from_raw(value: i32) -> Self {
+        match value {
+            1 => Self::Dry,
+            2 => Self::MostlyDry,
+            3 => Self::VeryLightlyWet,
+            4 => Self::LightlyWet,
+            5 => Self::ModeratelyWet,
+            6 => Self::VeryWet,
+            7 => Self::ExtremelyWet,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum Skies {
+    #[default]
+    Clear,
+    PartlyCloudy,
+    MostlyCloudy,
+    Overcast,
+}
+
+impl Skies {
+    pub fn from_raw(value: i32) -> Self {
+        match value {
+            1 => Self::PartlyCloudy,
+            2 => Self::MostlyCloudy,
+            3 => Self::Overcast,
+            _ => Self::Clear,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum Trend {
+    #[default]
+    Steady,
+    Rising,
+    Falling,
+}
+
+impl Trend {
+    fn from_change(change: f32, threshold: f32) -> Self {
+        if change >= threshold {
+            Self::Rising
+        } else if change <= -threshold {
+            Self::Falling
+        } else {
+            Self::Steady
+        }
+    }
+}
+
+pub struct WeatherTick {
+    pub session_time: Duration,
+    pub air_temp: f32,
+    pub track_temp: f32,
+    pub track_wetness: TrackWetness,
+    pub precipitation: f32,
+    pub wind_vel: f32,
+    pub wind_dir: f32,
+    pub skies: Skies,
+    pub fog_level: f32,
+}
+
+/// Conditions the session was set up with, from the weekend info of the session info
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct WeekendWeather {
+    pub weather_type: String,
+    pub skies: String,
+    pub air_temp: f32,          // in C
+    pub track_temp: f32,        // in C
+    pub relative_humidity: f32, // 0..1
+}
+
+impl WeekendWeather {
+    pub fn from_weekend_info(weekend_info: &WeekendInfo) -> Self {
+        Self {
+            weather_type: weekend_info.track_weather_type.clone().unwrap_or_default(),
+            skies: weekend_info.track_skies.clone().unwrap_or_default(),
+            air_temp: weekend_info.track_air_temp.unwrap_or_default(),
+            track_temp: weekend_info.track_surface_temp.unwrap_or_default(),
+            relative_humidity: weekend_info.track_relative_humidity.unwrap_or_default() / 100.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct WeatherSample {
+    session_time: Duration,
+    track_temp: f32,
+    track_wetness: TrackWetness,
+    precipitation: f32,
+}
+
+/// Current conditions and a history of samples for the trends
+#[derive(Clone, Default, Debug)]
+pub struct Weather {
+    pub air_temp: f32,   // in C
+    pub track_temp: f32, // in C
+    pub track_wetness: TrackWetness,
+    pub precipitation: f32, // 0..1
+    pub wind_vel: f32,      // in m/s
+    pub wind_dir: f32,      // in rad
+    pub skies: Skies,
+    pub fog_level: f32, // 0..1
+    /// Only the live values above follow dynamic weather
+    pub weekend: WeekendWeather,
+    samples: VecDeque<WeatherSample>,
+}
+
+impl Weather {
+    pub fn update(&mut self, tick: WeatherTick) {
+        self.air_temp = tick.air_temp;
+        self.track_temp = tick.track_temp;
+        self.track_wetness = tick.track_wetness;
+        self.precipitation = tick.precipitation;
+        self.wind_vel = tick.wind_vel;
+        self.wind_dir = tick.wind_dir;
+        self.skies = tick.skies;
+        self.fog_level = tick.fog_level;
+
+        // a replay or a new session went back in time
+        if self
+            .samples
+            .back()
+            .is_some_and(|sample| sample.session_time > tick.session_time)
+        {
+            self.samples.clear();
+        }
+        if self
+            .samples
+            .back()
+            .is_none_or(|sample| tick.session_time >= sample.session_time + SAMPLE_INTERVAL)
+        {
+            self.samples.push_back(WeatherSample {
+                session_time: tick.session_time,
+                track_temp: tick.track_temp,
+                track_wetness: tick.track_wetness,
+                precipitation: tick.precipitation,
+            });
+        }
+        while self
+            .samples
+            .front()
+            .is_some_and(|sample| sample.session_time + MAX_HISTORY < tick.session_time)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Oldest sample within `window` of the latest one
+    fn window_start(&self, window: Duration) -> Option<&WeatherSample> {
+        let latest = self.samples.back()?;
+        self.samples
+            .iter()
+            .find(|sample| sample.session_time + window >= latest.session_time)
+    }
+
+    /// Rising when the track gets wetter, falling when it dries
+    pub fn wetness_trend(&self, window: Duration) -> Trend {
+        match self.window_start(window) {
+            Some(start) => Trend::from_change(
+                (self.track_wetness as i32 - start.track_wetness as i32) as f32,
+                1.0,
+            ),
+            None => Trend::Steady,
+        }
+    }
+
+    pub fn precipitation_trend(&self, window: Duration) -> Trend {
+        match self.window_start(window) {
+            Some(start) => Trend::from_change(
+                self.precipitation - start.precipitation,
+                PRECIPITATION_THRESHOLD,
+            ),
+            None => Trend::Steady,
+        }
+    }
+
+    pub fn track_temp_trend(&self, window: Duration) -> Trend {
+        match self.window_start(window) {
+            Some(start) => {
+                Trend::from_change(self.track_temp - start.track_temp, TRACK_TEMP_THRESHOLD)
+            }
+            None => Trend::Steady,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(
+        secs: u64,
+        track_temp: f32,
+        track_wetness: TrackWetness,
+        precipitation: f32,
+    ) -> WeatherTick {
+        WeatherTick {
+            session_time: Duration::from_secs(secs),
+            air_temp: 20.0,
+            track_temp,
+            track_wetness,
+            precipitation,
+            wind_vel: 0.0,
+            wind_dir: 0.0,
+            skies: Skies::Overcast,
+            fog_level: 0.0,
+        }
+    }
+
+    #[test]
+    fn detects_drying_track_and_easing_rain() {
+        let mut weather = Weather::default();
+        weather.update(tick(0, 20.0, TrackWetness::VeryWet, 0.6));
+        weather.update(tick(120, 20.5, TrackWetness::ModeratelyWet, 0.3));
+        weather.update(tick(240, 21.0, TrackWetness::LightlyWet, 0.1));
+        let window = Duration::from_secs(300);
+        assert_eq!(weather.wetness_trend(window), Trend::Falling);
+        assert_eq!(weather.precipitation_trend(window), Trend::Falling);
+        assert_eq!(weather.track_temp_trend(window), Trend::Rising);
+    }
+
+    #[test]
+    fn only_looks_at_the_window() {
+        let mut weather = Weather::default();
+        weather.update(tick(0, 20.0, TrackWetness::Dry, 0.0));
+        weather.update(tick(600, 20.0, TrackWetness::LightlyWet, 0.4));
+        weather.update(tick(900, 20.0, TrackWetness::LightlyWet, 0.42));
+        let window = Duration::from_secs(300);
+        assert_eq!(weather.wetness_trend(window), Trend::Steady);
+        assert_eq!(weather.precipitation_trend(window), Trend::Steady);
+        assert_eq!(
+            weather.wetness_trend(Duration::from_secs(1200)),
+            Trend::Rising
+        );
+    }
+
+    #[test]
+    fn samples_at_an_interval() {
+        let mut weather = Weather::default();
+        for secs in 0..30 {
+            weather.update(tick(secs, 20.0, TrackWetness::Dry, 0.0));
+        }
+        assert_eq!(weather.samples.len(), 3);
+    }
+}
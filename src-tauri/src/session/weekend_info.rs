@@ -0,0 +1,188 @@
+use yaml_rust::Yaml;
+
+use crate::util::yaml_value::{yaml_bool, yaml_f32, yaml_string, yaml_u32};
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct WeekendOptions {
+    pub num_starters: Option<u32>,
+    pub starting_grid: Option<String>,
+    pub qualify_scoring: Option<String>,
+    pub course_cautions: Option<String>,
+    pub standing_start: Option<bool>,
+    pub restarts: Option<String>,
+    pub weather_type: Option<String>,
+    pub skies: Option<String>,
+    pub wind_direction: Option<String>,
+    pub wind_speed: Option<String>,
+    pub weather_temp: Option<String>,
+    pub relative_humidity: Option<String>,
+    pub fog_level: Option<String>,
+    pub time_of_day: Option<String>,
+    pub date: Option<String>,
+    pub unofficial: Option<bool>,
+    pub commercial_mode: Option<String>,
+    pub night_mode: Option<String>,
+    pub is_fixed_setup: Option<bool>,
+    pub strict_laps_checking: Option<String>,
+    pub has_open_registration: Option<bool>,
+    pub hardcore_level: Option<u32>,
+    pub num_joker_laps: Option<u32>,
+    /// `None` when the session has no incident limit
+    pub incident_limit: Option<u32>,
+    pub fast_repairs_limit: Option<u32>,
+    pub green_white_checkered_limit: Option<u32>,
+}
+
+impl WeekendOptions {
+    pub fn from_yaml(yaml: &Yaml) -> Self {
+        Self {
+            num_starters: yaml_u32(&yaml["NumStarters"]),
+            starting_grid: yaml_string(&yaml["StartingGrid"]),
+            qualify_scoring: yaml_string(&yaml["QualifyScoring"]),
+            course_cautions: yaml_string(&yaml["CourseCautions"]),
+            standing_start: yaml_bool(&yaml["StandingStart"]),
+            restarts: yaml_string(&yaml["Restarts"]),
+            weather_type: yaml_string(&yaml["WeatherType"]),
+            skies: yaml_string(&yaml["Skies"]),
+            wind_direction: yaml_string(&yaml["WindDirection"]),
+            wind_speed: yaml_string(&yaml["WindSpeed"]),
+            weather_temp: yaml_string(&yaml["WeatherTemp"]),
+            relative_humidity: yaml_string(&yaml["RelativeHumidity"]),
+            fog_level: yaml_string(&yaml["FogLevel"]),
+            time_of_day: yaml_string(&yaml["TimeOfDay"]),
+            date: yaml_string(&yaml["Date"]),
+            unofficial: yaml_bool(&yaml["Unofficial"]),
+            commercial_mode: yaml_string(&yaml["CommercialMode"]),
+            night_mode: yaml_string(&yaml["NightMode"]),
+            is_fixed_setup: yaml_bool(&yaml["IsFixedSetup"]),
+            strict_laps_checking: yaml_string(&yaml["StrictLapsChecking"]),
+            has_open_registration: yaml_bool(&yaml["HasOpenRegistration"]),
+            hardcore_level: yaml_u32(&yaml["HardcoreLevel"]),
+            num_joker_laps: yaml_u32(&yaml["NumJokerLaps"]),
+            incident_limit: yaml_u32(&yaml["IncidentLimit"]),
+            fast_repairs_limit: yaml_u32(&yaml["FastRepairsLimit"]),
+            green_white_checkered_limit: yaml_u32(&yaml["GreenWhiteCheckeredLimit"]),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct WeekendInfo {
+    pub track_name: Option<String>,
+    pub track_id: Option<u32>,
+    pub track_length: Option<f32>,          // in km
+    pub track_length_official: Option<f32>, // in km
+    pub track_display_name: Option<String>,
+    pub track_display_short_name: Option<String>,
+    pub track_config_name: Option<String>,
+    pub track_city: Option<String>,
+    pub track_state: Option<String>,
+    pub track_country: Option<String>,
+    pub track_altitude: Option<f32>,     // in m
+    pub track_latitude: Option<f32>,     // in degrees
+    pub track_longitude: Option<f32>,    // in degrees
+    pub track_north_offset: Option<f32>, // in rad
+    pub track_num_turns: Option<u32>,
+    pub track_pit_speed_limit: Option<f32>, // in kph
+    pub track_pace_speed: Option<f32>,      // in kph
+    pub track_num_pit_stalls: Option<u32>,
+    pub track_type: Option<String>,
+    pub track_direction: Option<String>,
+    pub track_weather_type: Option<String>,
+    pub track_skies: Option<String>,
+    pub track_surface_temp: Option<f32>,      // in C
+    pub track_air_temp: Option<f32>,          // in C
+    pub track_air_pressure: Option<f32>,      // in Hg
+    pub track_wind_vel: Option<f32>,          // in m/s
+    pub track_wind_dir: Option<f32>,          // in rad
+    pub track_relative_humidity: Option<f32>, // in %
+    pub track_fog_level: Option<f32>,         // in %
+    pub track_precipitation: Option<f32>,     // in %
+    pub track_cleanup: Option<bool>,
+    pub track_dynamic_track: Option<bool>,
+    pub track_version: Option<String>,
+    pub series_id: Option<u32>,
+    pub season_id: Option<u32>,
+    pub session_id: Option<u32>,
+    pub sub_session_id: Option<u32>,
+    pub league_id: Option<u32>,
+    pub official: Option<bool>,
+    pub race_week: Option<u32>,
+    pub event_type: Option<String>,
+    pub category: Option<String>,
+    pub sim_mode: Option<String>,
+    pub team_racing: Option<bool>,
+    pub min_drivers: Option<u32>,
+    pub max_drivers: Option<u32>,
+    pub dc_rule_set: Option<String>,
+    pub qualifier_must_start_race: Option<bool>,
+    pub num_car_classes: Option<u32>,
+    pub num_car_types: Option<u32>,
+    pub heat_racing: Option<bool>,
+    pub build_type: Option<String>,
+    pub build_target: Option<String>,
+    pub build_version: Option<String>,
+    pub weekend_options: WeekendOptions,
+}
+
+impl WeekendInfo {
+    pub fn from_yaml(yaml: &Yaml) -> Self {
+        Self {
+            track_name: yaml_string(&yaml["TrackName"]),
+            track_id: yaml_u32(&yaml["TrackID"]),
+            track_length: yaml_f32(&yaml["TrackLength"]),
+            track_length_official: yaml_f32(&yaml["TrackLengthOfficial"]),
+            track_display_name: yaml_string(&yaml["TrackDisplayName"]),
+            track_display_short_name: yaml_string(&yaml["TrackDisplayShortName"]),
+            track_config_name: yaml_string(&yaml["TrackConfigName"]),
+            track_city: yaml_string(&yaml["TrackCity"]),
+            track_state: yaml_string(&yaml["TrackState"]),
+            track_country: yaml_string(&yaml["TrackCountry"]),
+            track_altitude: yaml_f32(&yaml["TrackAltitude"]),
+            track_latitude: yaml_f32(&yaml["TrackLatitude"]),
+            track_longitude: yaml_f32(&yaml["TrackLongitude"]),
+            track_north_offset: yaml_f32(&yaml["TrackNorthOffset"]),
+            track_num_turns: yaml_u32(&yaml["TrackNumTurns"]),
+            track_pit_speed_limit: yaml_f32(&yaml["TrackPitSpeedLimit"]),
+            track_pace_speed: yaml_f32(&yaml["TrackPaceSpeed"]),
+            track_num_pit_stalls: yaml_u32(&yaml["TrackNumPitStalls"]),
+            track_type: yaml_string(&yaml["TrackType"]),
+            track_direction: yaml_string(&yaml["TrackDirection"]),
+            track_weather_type: yaml_string(&yaml["TrackWeatherType"]),
+            track_skies: yaml_string(&yaml["TrackSkies"]),
+            track_surface_temp: yaml_f32(&yaml["TrackSurfaceTemp"]),
+            track_air_temp: yaml_f32(&yaml["TrackAirTemp"]),
+            track_air_pressure: yaml_f32(&yaml["TrackAirPressure"]),
+            track_wind_vel: yaml_f32(&yaml["TrackWindVel"]),
+            track_wind_dir: yaml_f32(&yaml["TrackWindDir"]),
+            track_relative_humidity: yaml_f32(&yaml["TrackRelativeHumidity"]),
+            track_fog_level: yaml_f32(&yaml["TrackFogLevel"]),
+            track_precipitation: yaml_f32(&yaml["TrackPrecipitation"]),
+            track_cleanup: yaml_bool(&yaml["TrackCleanup"]),
+            track_dynamic_track: yaml_bool(&yaml["TrackDynamicTrack"]),
+            track_version: yaml_string(&yaml["TrackVersion"]),
+            series_id: yaml_u32(&yaml["SeriesID"]),
+            season_id: yaml_u32(&yaml["SeasonID"]),
+            session_id: yaml_u32(&yaml["SessionID"]),
+            sub_session_id: yaml_u32(&yaml["SubSessionID"]),
+            league_id: yaml_u32(&yaml["LeagueID"]),
+            official: yaml_bool(&yaml["Official"]),
+            race_week: yaml_u32(&yaml["RaceWeek"]),
+            event_type: yaml_string(&yaml["EventType"]),
+            category: yaml_string(&yaml["Category"]),
+            sim_mode: yaml_string(&yaml["SimMode"]),
+            team_racing: yaml_bool(&yaml["TeamRacing"]),
+            min_drivers: yaml_u32(&yaml["MinDrivers"]),
+            max_drivers: yaml_u32(&yaml["MaxDrivers"]),
+            dc_rule_set: yaml_string(&yaml["DCRuleSet"]),
+            qualifier_must_start_race: yaml_bool(&yaml["QualifierMustStartRace"]),
+            num_car_classes: yaml_u32(&yaml["NumCarClasses"]),
+            num_car_types: yaml_u32(&yaml["NumCarTypes"]),
+            heat_racing: yaml_bool(&yaml["HeatRacing"]),
+            build_type: yaml_string(&yaml["BuildType"]),
+            build_target: yaml_string(&yaml["BuildTarget"]),
+            build_version: yaml_string(&yaml["BuildVersion"]),
+            weekend_options: WeekendOptions::from_yaml(&yaml["WeekendOptions"]),
+        }
+    }
+}
@@ -1 +1,8 @@
+pub mod default_settings;
+pub mod file_settings_provider;
+pub mod network_settings;
+pub mod overlay_settings;
 pub mod overlays;
+pub mod settings_provider;
+#[cfg(feature = "gui")]
+pub mod store_settings_provider;
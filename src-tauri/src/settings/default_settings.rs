@@ -0,0 +1,38 @@
+pub fn get_default_settings(overlay_name: &str) -> Option<&'static str> {
+    match overlay_name {
+        "fuel" => Some(include_str!(
+            "../../data/default_settings/fuel_overlay.json"
+        )),
+        "lap_times" => Some(include_str!(
+            "../../data/default_settings/lap_times_overlay.json"
+        )),
+        "main" => Some(include_str!(
+            "../../data/default_settings/main_overlay.json"
+        )),
+        "proximity" => Some(include_str!(
+            "../../data/default_settings/proximity_overlay.json"
+        )),
+        "relative" => Some(include_str!(
+            "../../data/default_settings/relative_overlay.json"
+        )),
+        "standings" => Some(include_str!(
+            "../../data/default_settings/standings_overlay.json"
+        )),
+        "subtimer" => Some(include_str!(
+            "../../data/default_settings/subtimer_overlay.json"
+        )),
+        "telemetry" => Some(include_str!(
+            "../../data/default_settings/telemetry_overlay.json"
+        )),
+        "telemetry_reference" => Some(include_str!(
+            "../../data/default_settings/telemetry_reference_overlay.json"
+        )),
+        "timer" => Some(include_str!(
+            "../../data/default_settings/timer_overlay.json"
+        )),
+        "track_map" => Some(include_str!(
+            "../../data/default_settings/track_map_overlay.json"
+        )),
+        _ => None,
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use log::error;
+
+use super::default_settings::get_default_settings;
+use super::settings_provider::SettingsProvider;
+
+/// Stores settings as `<overlay>_overlay.json` files in the same layout as the Tauri store
+pub struct FileSettingsProvider {
+    dir: PathBuf,
+    cache: RwLock<HashMap<String, serde_json::Value>>,
+}
+
+impl FileSettingsProvider {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn path(&self, overlay_name: &str) -> PathBuf {
+        self.dir.join(format!("{}_overlay.json", overlay_name))
+    }
+
+    fn read(&self, overlay_name: &str) -> Option<serde_json::Value> {
+        let file = File::open(self.path(overlay_name)).ok()?;
+        let store: serde_json::Value = serde_json::from_reader(file).ok()?;
+        store.get("settings").cloned()
+    }
+}
+
+impl SettingsProvider for FileSettingsProvider {
+    fn load(&self, overlay_name: &str) -> serde_json::Value {
+        if let Some(settings) = self.cache.read().unwrap().get(overlay_name) {
+            return settings.clone();
+        }
+        let settings = match self.read(overlay_name) {
+            Some(settings) => settings,
+            None => {
+                let default_settings = get_default_settings(overlay_name)
+                    .unwrap_or_else(|| panic!("Unknown overlay type: {}", overlay_name));
+                let default_settings: serde_json::Value =
+                    serde_json::from_str(default_settings).unwrap();
+                let settings = default_settings["settings"].clone();
+                self.save(overlay_name, settings.clone());
+                settings
+            }
+        };
+        self.cache
+            .write()
+            .unwrap()
+            .insert(overlay_name.to_string(), settings.clone());
+        settings
+    }
+
+    fn save(&self, overlay_name: &str, settings: serde_json::Value) {
+        self.cache
+            .write()
+            .unwrap()
+            .insert(overlay_name.to_string(), settings.clone());
+        let store = serde_json::json!({ "settings": settings });
+        let result = fs::create_dir_all(&self.dir).and_then(|_| {
+            fs::write(
+                self.path(overlay_name),
+                serde_json::to_string_pretty(&store).unwrap(),
+            )
+        });
+        if let Err(err) = result {
+            error!(
+                "Failed to save {} overlay settings: {:?}",
+                overlay_name, err
+            );
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::fs;
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+
+use eyre::{Result, bail};
+use log::{error, info};
+use rand::Rng;
+use rand::distr::Alphanumeric;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+const FILE_NAME: &str = "network.json";
+const TOKEN_LENGTH: usize = 24;
+pub const LOOPBACK_ADDRESS: &str = "127.0.0.1";
+
+/// Where the WebSocket server listens and the token clients need, applied on restart
+#[derive(Type, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct NetworkSettings {
+    /// `127.0.0.1` for this PC only, `0.0.0.0` for the local network
+    pub bind_address: String,
+    /// 0 picks a free port on every start
+    pub port: u16,
+    pub token: String,
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        Self {
+            bind_address: LOOPBACK_ADDRESS.to_string(),
+            port: 0,
+            token: generate_token(),
+        }
+    }
+}
+
+pub fn generate_token() -> String {
+    rand::rng()
+        .sample_iter(Alphanumeric)
+        .take(TOKEN_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+impl NetworkSettings {
+    fn path(dir: &Path) -> PathBuf {
+        dir.join(FILE_NAME)
+    }
+
+    /// Reads `network.json` from the data directory, creating it with a new token when missing
+    pub fn load(dir: &Path) -> Self {
+        let path = Self::path(dir);
+        let settings = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<NetworkSettings>(&contents).ok());
+        match settings {
+            Some(settings) => settings,
+            None => {
+                info!("Creating network settings in {}", path.display());
+                let settings = Self::default();
+                if let Err(err) = settings.save(dir) {
+                    error!("Failed to save network settings: {:?}", err);
+                }
+                settings
+            }
+        }
+    }
+
+    /// Clients can't present an empty token, ws_client drops it from the URL
+    pub fn validate(&self) -> Result<()> {
+        if self.bind_address.parse::<IpAddr>().is_err() {
+            bail!("Invalid bind address {}", self.bind_address);
+        }
+        if self.token.is_empty() {
+            bail!("Token must not be empty");
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        fs::create_dir_all(dir)?;
+        fs::write(Self::path(dir), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// IPv6 addresses get brackets, an invalid address fails when binding
+    pub fn bind_addr(&self) -> String {
+        match self.bind_address.parse::<IpAddr>() {
+            Ok(ip) => SocketAddr::new(ip, self.port).to_string(),
+            Err(_) => format!("{}:{}", self.bind_address, self.port),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn creates_settings_with_a_token_once() {
+        let dir =
+            std::env::temp_dir().join(format!("iracehud-network-settings-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let settings = NetworkSettings::load(&dir);
+        assert_eq!(settings.bind_addr(), "127.0.0.1:0");
+        assert_eq!(settings.token.len(), TOKEN_LENGTH);
+        assert_eq!(NetworkSettings::load(&dir), settings);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rejects_invalid_address_and_empty_token() {
+        let settings = NetworkSettings::default();
+        assert!(settings.validate().is_ok());
+        let settings = NetworkSettings {
+            bind_address: "::".to_string(),
+            port: 9000,
+            ..settings
+        };
+        assert!(settings.validate().is_ok());
+        assert_eq!(settings.bind_addr(), "[::]:9000");
+        let settings = NetworkSettings {
+            bind_address: "localhost:9000".to_string(),
+            ..NetworkSettings::default()
+        };
+        assert!(settings.validate().is_err());
+        let settings = NetworkSettings {
+            token: String::new(),
+            ..NetworkSettings::default()
+        };
+        assert!(settings.validate().is_err());
+    }
+}
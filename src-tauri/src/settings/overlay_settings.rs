@@ -0,0 +1,77 @@
+use eyre::{Result, eyre};
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::settings::overlays::common_settings::HasCommonSettings;
+use crate::settings::overlays::{
+    fuel::FuelOverlaySettings, lap_times::LapTimesOverlaySettings, main::MainOverlaySettings,
+    proximity::ProximityOverlaySettings, relative::RelativeOverlaySettings,
+    standings::StandingsOverlaySettings, subtimer::SubTimerOverlaySettings,
+    telemetry::TelemetryOverlaySettings, telemetry_reference::TelemetryReferenceOverlaySettings,
+    timer::TimerOverlaySettings, track_map::TrackMapOverlaySettings,
+};
+use crate::util::settings_helper::{get_settings, set_settings};
+
+pub const AVAILABLE_OVERLAYS: [&str; 11] = [
+    "main",
+    "standings",
+    "lap_times",
+    "proximity",
+    "relative",
+    "subtimer",
+    "telemetry",
+    "telemetry_reference",
+    "timer",
+    "track_map",
+    "fuel",
+];
+
+fn get_typed<T: DeserializeOwned + Serialize>(overlay_name: &str) -> Result<serde_json::Value> {
+    Ok(serde_json::to_value(get_settings::<T>(overlay_name))?)
+}
+
+fn set_typed<T: DeserializeOwned + Serialize + HasCommonSettings>(
+    overlay_name: &str,
+    settings: serde_json::Value,
+) -> Result<()> {
+    let settings: T = serde_json::from_value(settings)?;
+    set_settings(overlay_name, settings);
+    Ok(())
+}
+
+/// Settings of the overlay by name, for clients that don't know the settings types
+pub fn get_overlay_settings(overlay_name: &str) -> Result<serde_json::Value> {
+    match overlay_name {
+        "main" => get_typed::<MainOverlaySettings>(overlay_name),
+        "standings" => get_typed::<StandingsOverlaySettings>(overlay_name),
+        "lap_times" => get_typed::<LapTimesOverlaySettings>(overlay_name),
+        "proximity" => get_typed::<ProximityOverlaySettings>(overlay_name),
+        "relative" => get_typed::<RelativeOverlaySettings>(overlay_name),
+        "subtimer" => get_typed::<SubTimerOverlaySettings>(overlay_name),
+        "telemetry" => get_typed::<TelemetryOverlaySettings>(overlay_name),
+        "telemetry_reference" => get_typed::<TelemetryReferenceOverlaySettings>(overlay_name),
+        "timer" => get_typed::<TimerOverlaySettings>(overlay_name),
+        "track_map" => get_typed::<TrackMapOverlaySettings>(overlay_name),
+        "fuel" => get_typed::<FuelOverlaySettings>(overlay_name),
+        _ => Err(eyre!("Unknown overlay type: {}", overlay_name)),
+    }
+}
+
+/// Validates the settings against the overlay settings type before saving them
+pub fn set_overlay_settings(overlay_name: &str, settings: serde_json::Value) -> Result<()> {
+    match overlay_name {
+        "main" => set_typed::<MainOverlaySettings>(overlay_name, settings),
+        "standings" => set_typed::<StandingsOverlaySettings>(overlay_name, settings),
+        "lap_times" => set_typed::<LapTimesOverlaySettings>(overlay_name, settings),
+        "proximity" => set_typed::<ProximityOverlaySettings>(overlay_name, settings),
+        "relative" => set_typed::<RelativeOverlaySettings>(overlay_name, settings),
+        "subtimer" => set_typed::<SubTimerOverlaySettings>(overlay_name, settings),
+        "telemetry" => set_typed::<TelemetryOverlaySettings>(overlay_name, settings),
+        "telemetry_reference" => {
+            set_typed::<TelemetryReferenceOverlaySettings>(overlay_name, settings)
+        }
+        "timer" => set_typed::<TimerOverlaySettings>(overlay_name, settings),
+        "track_map" => set_typed::<TrackMapOverlaySettings>(overlay_name, settings),
+        "fuel" => set_typed::<FuelOverlaySettings>(overlay_name, settings),
+        _ => Err(eyre!("Unknown overlay type: {}", overlay_name)),
+    }
+}
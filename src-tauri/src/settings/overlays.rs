@@ -1,4 +1,5 @@
 pub mod common_settings;
+pub mod fuel;
 pub mod lap_times;
 pub mod main;
 pub mod proximity;
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use super::common_settings::{CommonSettings, HasCommonSettings};
+
+#[derive(Default, Type, Serialize, Deserialize, Clone)]
+pub struct FuelOverlaySettings {
+    pub common_settings: CommonSettings,
+    /// Green laps the usage is averaged over
+    pub average_laps: u32,
+    /// Extra laps of fuel added on top of the fuel needed to finish
+    pub safety_margin_laps: f32,
+}
+
+impl HasCommonSettings for FuelOverlaySettings {
+    fn common_settings(&self) -> &CommonSettings {
+        &self.common_settings
+    }
+}
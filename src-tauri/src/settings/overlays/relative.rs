@@ -6,6 +6,9 @@ use super::common_settings::{CommonSettings, HasCommonSettings};
 #[derive(Default, Type, Serialize, Deserialize, Clone)]
 pub struct RelativeOverlaySettings {
     pub common_settings: CommonSettings,
+    /// Projected iRating change column in races
+    #[serde(default)]
+    pub show_irating_change: bool,
 }
 
 impl HasCommonSettings for RelativeOverlaySettings {
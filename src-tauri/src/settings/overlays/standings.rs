@@ -8,6 +8,19 @@ pub struct StandingsOverlaySettings {
     pub common_settings: CommonSettings,
     pub max_drivers: u32,
     pub top_drivers: u32,
+    // settings saved before multi-class standings don't have these
+    #[serde(default = "default_rows_per_class")]
+    pub rows_per_class: u32,
+    /// Class names shown in multi-class sessions, all classes when empty
+    #[serde(default)]
+    pub shown_classes: Vec<String>,
+    /// Projected iRating change column in races
+    #[serde(default)]
+    pub show_irating_change: bool,
+}
+
+fn default_rows_per_class() -> u32 {
+    5
 }
 
 impl HasCommonSettings for StandingsOverlaySettings {
@@ -0,0 +1,11 @@
+use std::sync::OnceLock;
+
+pub static SETTINGS_PROVIDER: OnceLock<Box<dyn SettingsProvider>> = OnceLock::new();
+
+/// Storage backend for overlay settings, each overlay is stored as a JSON value under its name
+pub trait SettingsProvider: Send + Sync {
+    /// Returns stored settings of the overlay, initializing them with defaults when missing
+    fn load(&self, overlay_name: &str) -> serde_json::Value;
+
+    fn save(&self, overlay_name: &str, settings: serde_json::Value);
+}
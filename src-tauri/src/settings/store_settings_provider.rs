@@ -0,0 +1,54 @@
+use std::fs::File;
+
+use tauri::{Manager, path::BaseDirectory};
+use tauri_plugin_store::StoreExt;
+
+use super::settings_provider::SettingsProvider;
+
+/// Settings kept in `tauri_plugin_store`, defaults are read from the bundled resources
+pub struct StoreSettingsProvider {
+    app: tauri::AppHandle,
+}
+
+impl StoreSettingsProvider {
+    pub fn new(app: tauri::AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+impl SettingsProvider for StoreSettingsProvider {
+    fn load(&self, overlay_name: &str) -> serde_json::Value {
+        let store = self
+            .app
+            .store(format!("{}_overlay.json", overlay_name))
+            .unwrap();
+        match store.get("settings") {
+            Some(settings) => settings,
+            None => {
+                let resource_path = self
+                    .app
+                    .path()
+                    .resolve(
+                        format!("data/default_settings/{}_overlay.json", overlay_name),
+                        BaseDirectory::Resource,
+                    )
+                    .unwrap();
+                let file = File::open(resource_path).unwrap();
+                let settings: serde_json::Value = serde_json::from_reader(file).unwrap();
+                let settings = settings["settings"].clone();
+                store.set("settings", settings.clone());
+                store.save().unwrap();
+                settings
+            }
+        }
+    }
+
+    fn save(&self, overlay_name: &str, settings: serde_json::Value) {
+        let store = self
+            .app
+            .store(format!("{}_overlay.json", overlay_name))
+            .unwrap();
+        store.set("settings", settings);
+        store.save().unwrap();
+    }
+}
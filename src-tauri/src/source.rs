@@ -0,0 +1,10 @@
+pub mod capture;
+pub mod capture_recorder;
+pub mod ibt_file;
+pub mod ibt_source;
+pub mod iracing_source;
+pub mod replay_source;
+pub mod synthetic_session;
+pub mod synthetic_source;
+pub mod telemetry_source;
+pub mod telemetry_tick;
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use simetry::iracing::Value;
+
+pub const CAPTURE_MAGIC: &[u8; 8] = b"IRHCAP01";
+pub const CAPTURE_EXTENSION: &str = "ihcap";
+
+/// Capture files are `CAPTURE_MAGIC` followed by a stream of MessagePack encoded records.
+/// `Variables` always comes first, every `Tick` stores its values in the same order.
+#[derive(Serialize, Deserialize, Debug)]
+pub enum CaptureRecord {
+    Variables(Vec<String>),
+    SessionInfo {
+        update: i32,
+        yaml: String,
+    },
+    Tick {
+        elapsed_ms: u64,
+        values: Vec<Option<Vec<CaptureValue>>>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum CaptureValue {
+    Char(u8),
+    Bool(bool),
+    Int(i32),
+    BitField(u32),
+    Float(f32),
+    Double(f64),
+}
+
+impl From<Value> for CaptureValue {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Char(value) => CaptureValue::Char(value),
+            Value::Bool(value) => CaptureValue::Bool(value),
+            Value::Int(value) => CaptureValue::Int(value),
+            Value::BitField(value) => CaptureValue::BitField(value),
+            Value::Float(value) => CaptureValue::Float(value),
+            Value::Double(value) => CaptureValue::Double(value),
+        }
+    }
+}
+
+impl From<CaptureValue> for Value {
+    fn from(value: CaptureValue) -> Self {
+        match value {
+            CaptureValue::Char(value) => Value::Char(value),
+            CaptureValue::Bool(value) => Value::Bool(value),
+            CaptureValue::Int(value) => Value::Int(value),
+            CaptureValue::BitField(value) => Value::BitField(value),
+            CaptureValue::Float(value) => Value::Float(value),
+            CaptureValue::Double(value) => Value::Double(value),
+        }
+    }
+}
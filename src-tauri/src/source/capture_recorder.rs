@@ -0,0 +1,82 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use eyre::Result;
+use log::{error, info};
+use yaml_rust::YamlEmitter;
+
+use crate::session::session_data::PROCESSED_VARIABLES;
+
+use super::capture::{CAPTURE_MAGIC, CaptureRecord, CaptureValue};
+use super::telemetry_tick::TelemetryTick;
+
+pub struct CaptureRecorder {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    started: Instant,
+    session_info_update: Option<i32>,
+}
+
+impl CaptureRecorder {
+    pub fn create(path: &Path) -> Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(CAPTURE_MAGIC)?;
+        let variables = PROCESSED_VARIABLES
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        rmp_serde::encode::write(&mut writer, &CaptureRecord::Variables(variables))?;
+        info!("Session capture started: {}", path.display());
+        Ok(Self {
+            path: path.to_path_buf(),
+            writer,
+            started: Instant::now(),
+            session_info_update: None,
+        })
+    }
+
+    pub fn record<T: TelemetryTick>(&mut self, tick: &T) -> Result<()> {
+        let session_info_update = tick.session_info_update();
+        if self.session_info_update != Some(session_info_update) {
+            let mut yaml = String::new();
+            match YamlEmitter::new(&mut yaml).dump(tick.session_info()) {
+                Ok(_) => {
+                    let record = CaptureRecord::SessionInfo {
+                        update: session_info_update,
+                        yaml,
+                    };
+                    rmp_serde::encode::write(&mut self.writer, &record)?;
+                }
+                Err(err) => error!("Failed to serialize session info: {:?}", err),
+            }
+            self.session_info_update = Some(session_info_update);
+        }
+
+        let values = PROCESSED_VARIABLES
+            .iter()
+            .map(|name| {
+                let count = tick.value_count(name)?;
+                Some(
+                    (0..count)
+                        .filter_map(|idx| tick.read_value(name, idx))
+                        .map(CaptureValue::from)
+                        .collect(),
+                )
+            })
+            .collect();
+        let record = CaptureRecord::Tick {
+            elapsed_ms: self.started.elapsed().as_millis() as u64,
+            values,
+        };
+        rmp_serde::encode::write(&mut self.writer, &record)?;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<PathBuf> {
+        self.writer.flush()?;
+        info!("Session capture finished: {}", self.path.display());
+        Ok(self.path)
+    }
+}
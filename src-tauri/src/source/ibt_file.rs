@@ -0,0 +1,378 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use eyre::{Result, bail, eyre};
+use simetry::iracing::{Value, VarType};
+use yaml_rust::{Yaml, YamlLoader};
+
+const IRSDK_VER: i32 = 2;
+const HEADER_LEN: usize = 112;
+const DISK_SUB_HEADER_LEN: usize = 32;
+const VAR_HEADER_LEN: usize = 144;
+const MAX_STRING: usize = 32;
+const MAX_DESC: usize = 64;
+
+#[derive(Debug, Clone, Default)]
+pub struct IbtHeader {
+    pub ver: i32,
+    pub status: i32,
+    pub tick_rate: i32,
+    pub session_info_update: i32,
+    pub session_info_len: i32,
+    pub session_info_offset: i32,
+    pub num_vars: i32,
+    pub var_header_offset: i32,
+    pub num_buf: i32,
+    pub buf_len: i32,
+    /// Offset of the first sample, .ibt files only use the first var buffer
+    pub buf_offset: i32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IbtDiskSubHeader {
+    pub session_start_date: i64,
+    pub session_start_time: f64,
+    pub session_end_time: f64,
+    pub session_lap_count: i32,
+    pub session_record_count: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct IbtVarHeader {
+    pub var_type: VarType,
+    pub offset: usize,
+    pub count: usize,
+    pub count_as_time: bool,
+    pub name: String,
+    pub desc: String,
+    pub unit: String,
+}
+
+impl IbtVarHeader {
+    /// Parses entry `idx` of this variable from a raw sample buffer
+    pub fn parse_value(&self, sample: &[u8], idx: usize) -> Option<Value> {
+        if idx >= self.count {
+            return None;
+        }
+        let size = self.var_type.byte_count();
+        let start = self.offset + idx * size;
+        let bytes = sample.get(start..start + size)?;
+        let value = match self.var_type {
+            VarType::Char => Value::Char(bytes[0]),
+            VarType::Bool => Value::Bool(bytes[0] != 0),
+            VarType::Int => Value::Int(i32::from_le_bytes(bytes.try_into().ok()?)),
+            VarType::BitField => Value::BitField(u32::from_le_bytes(bytes.try_into().ok()?)),
+            VarType::Float => Value::Float(f32::from_le_bytes(bytes.try_into().ok()?)),
+            VarType::Double => Value::Double(f64::from_le_bytes(bytes.try_into().ok()?)),
+        };
+        Some(value)
+    }
+}
+
+/// Reader for iRacing .ibt telemetry files
+pub struct IbtFile {
+    reader: BufReader<File>,
+    header: IbtHeader,
+    sub_header: IbtDiskSubHeader,
+    variables: HashMap<String, IbtVarHeader>,
+    session_info: Yaml,
+    sample_count: usize,
+}
+
+impl IbtFile {
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+
+        let mut raw_header = [0u8; HEADER_LEN + DISK_SUB_HEADER_LEN];
+        reader.read_exact(&mut raw_header)?;
+        let header = IbtHeader {
+            ver: read_i32(&raw_header, 0),
+            status: read_i32(&raw_header, 4),
+            tick_rate: read_i32(&raw_header, 8),
+            session_info_update: read_i32(&raw_header, 12),
+            session_info_len: read_i32(&raw_header, 16),
+            session_info_offset: read_i32(&raw_header, 20),
+            num_vars: read_i32(&raw_header, 24),
+            var_header_offset: read_i32(&raw_header, 28),
+            num_buf: read_i32(&raw_header, 32),
+            buf_len: read_i32(&raw_header, 36),
+            // var_buf[0] starts at 48 after 2 pad words, buf_offset follows tick_count
+            buf_offset: read_i32(&raw_header, 52),
+        };
+        if header.ver != IRSDK_VER {
+            bail!(
+                "Unsupported .ibt version: expected {}, found {}",
+                IRSDK_VER,
+                header.ver
+            );
+        }
+        if header.buf_len <= 0 {
+            bail!("Invalid .ibt sample length: {}", header.buf_len);
+        }
+        let var_header_offset = header_value(header.var_header_offset, "variable header offset")?;
+        let session_info_offset = header_value(header.session_info_offset, "session info offset")?;
+        let session_info_len = header_value(header.session_info_len, "session info length")?;
+        let buf_offset = header_value(header.buf_offset, "sample offset")?;
+        let buf_len = header.buf_len as u64;
+        let file_len = reader.get_ref().metadata()?.len();
+        if session_info_offset + session_info_len > file_len {
+            bail!(
+                "Invalid .ibt session info: {} bytes at {} past the end of the file",
+                session_info_len,
+                session_info_offset
+            );
+        }
+
+        let sub_header = IbtDiskSubHeader {
+            session_start_date: i64::from_le_bytes(
+                raw_header[HEADER_LEN..HEADER_LEN + 8].try_into()?,
+            ),
+            session_start_time: read_f64(&raw_header, HEADER_LEN + 8),
+            session_end_time: read_f64(&raw_header, HEADER_LEN + 16),
+            session_lap_count: read_i32(&raw_header, HEADER_LEN + 24),
+            session_record_count: read_i32(&raw_header, HEADER_LEN + 28),
+        };
+
+        reader.seek(SeekFrom::Start(var_header_offset))?;
+        let mut variables = HashMap::new();
+        for _ in 0..header.num_vars {
+            let mut raw = [0u8; VAR_HEADER_LEN];
+            reader.read_exact(&mut raw)?;
+            let var_type = match read_i32(&raw, 0) {
+                0 => VarType::Char,
+                1 => VarType::Bool,
+                2 => VarType::Int,
+                3 => VarType::BitField,
+                4 => VarType::Float,
+                5 => VarType::Double,
+                value => bail!("Invalid .ibt variable type: {}", value),
+            };
+            let name_start = 16;
+            let desc_start = name_start + MAX_STRING;
+            let unit_start = desc_start + MAX_DESC;
+            let offset = header_value(read_i32(&raw, 4), "variable offset")?;
+            let count = header_value(read_i32(&raw, 8), "variable count")?;
+            if offset + count * var_type.byte_count() as u64 > buf_len {
+                bail!(
+                    "Invalid .ibt variable: {} entries at {} past the sample",
+                    count,
+                    offset
+                );
+            }
+            let var = IbtVarHeader {
+                var_type,
+                offset: offset as usize,
+                count: count as usize,
+                count_as_time: raw[12] != 0,
+                name: decode_string(&raw[name_start..desc_start]),
+                desc: decode_string(&raw[desc_start..unit_start]),
+                unit: decode_string(&raw[unit_start..unit_start + MAX_STRING]),
+            };
+            variables.insert(var.name.clone(), var);
+        }
+
+        reader.seek(SeekFrom::Start(session_info_offset))?;
+        let mut raw_session_info = vec![0u8; session_info_len as usize];
+        reader.read_exact(&mut raw_session_info)?;
+        let session_info = YamlLoader::load_from_str(&decode_string(&raw_session_info))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| eyre!("Session info did not contain any items"))?;
+
+        let data_len = file_len.saturating_sub(buf_offset);
+        let available = (data_len / buf_len) as usize;
+        let sample_count = match sub_header.session_record_count {
+            value if value > 0 => (value as usize).min(available),
+            _ => available,
+        };
+
+        Ok(Self {
+            reader,
+            header,
+            sub_header,
+            variables,
+            session_info,
+            sample_count,
+        })
+    }
+
+    pub fn header(&self) -> &IbtHeader {
+        &self.header
+    }
+
+    pub fn sub_header(&self) -> &IbtDiskSubHeader {
+        &self.sub_header
+    }
+
+    pub fn variables(&self) -> &HashMap<String, IbtVarHeader> {
+        &self.variables
+    }
+
+    pub fn session_info(&self) -> &Yaml {
+        &self.session_info
+    }
+
+    pub fn sample_count(&self) -> usize {
+        self.sample_count
+    }
+
+    /// Reads the raw buffer of sample `idx`, `None` past the end of the file
+    pub fn read_sample(&mut self, idx: usize) -> Result<Option<Vec<u8>>> {
+        if idx >= self.sample_count {
+            return Ok(None);
+        }
+        let buf_len = self.header.buf_len as u64;
+        let position = self.header.buf_offset as u64 + idx as u64 * buf_len;
+        self.reader.seek(SeekFrom::Start(position))?;
+        let mut sample = vec![0u8; buf_len as usize];
+        self.reader.read_exact(&mut sample)?;
+        Ok(Some(sample))
+    }
+}
+
+/// Offsets, lengths and counts are signed in the file, a corrupt one must not become a huge size
+fn header_value(value: i32, name: &str) -> Result<u64> {
+    u64::try_from(value).map_err(|_| eyre!("Invalid .ibt {}: {}", name, value))
+}
+
+fn read_i32(data: &[u8], offset: usize) -> i32 {
+    i32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_f64(data: &[u8], offset: usize) -> f64 {
+    f64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+/// Decodes a NUL terminated CP1252 string, bytes outside ASCII are mapped as Latin-1
+fn decode_string(data: &[u8]) -> String {
+    data.iter()
+        .take_while(|byte| **byte != 0)
+        .map(|byte| char::from(*byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::ibt_source::IbtSource;
+    use crate::source::replay_source::ReplaySpeed;
+    use crate::source::telemetry_source::TelemetrySource;
+    use crate::source::telemetry_tick::TelemetryTick;
+
+    const SESSION_INFO: &str = "---\nWeekendInfo:\n TrackName: spa\n...\n";
+    const SAMPLE_LEN: usize = 16;
+
+    fn write_i32(raw: &mut [u8], offset: usize, value: i32) {
+        raw[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_str(raw: &mut [u8], offset: usize, value: &str) {
+        raw[offset..offset + value.len()].copy_from_slice(value.as_bytes());
+    }
+
+    /// An `Int` gear and a `Float` array of three lap distances, followed by the samples
+    fn write_ibt(name: &str, samples: &[(i32, [f32; 3])]) -> std::path::PathBuf {
+        let vars_offset = HEADER_LEN + DISK_SUB_HEADER_LEN;
+        let session_info_offset = vars_offset + 2 * VAR_HEADER_LEN;
+        let session_info = format!("{}\0", SESSION_INFO);
+        let buf_offset = session_info_offset + session_info.len();
+
+        let mut raw = vec![0u8; buf_offset];
+        write_i32(&mut raw, 0, IRSDK_VER);
+        write_i32(&mut raw, 8, 60);
+        write_i32(&mut raw, 12, 3);
+        write_i32(&mut raw, 16, session_info.len() as i32);
+        write_i32(&mut raw, 20, session_info_offset as i32);
+        write_i32(&mut raw, 24, 2);
+        write_i32(&mut raw, 28, vars_offset as i32);
+        write_i32(&mut raw, 32, 1);
+        write_i32(&mut raw, 36, SAMPLE_LEN as i32);
+        write_i32(&mut raw, 52, buf_offset as i32);
+        write_i32(&mut raw, HEADER_LEN + 28, samples.len() as i32);
+
+        for (idx, (var_type, offset, count, name, desc, unit)) in [
+            (2, 0, 1, "Gear", "Gear, -1 reverse", ""),
+            (4, 4, 3, "CarIdxLapDistPct", "Lap distance by car", "%"),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let start = vars_offset + idx * VAR_HEADER_LEN;
+            write_i32(&mut raw, start, var_type);
+            write_i32(&mut raw, start + 4, offset);
+            write_i32(&mut raw, start + 8, count);
+            write_str(&mut raw, start + 16, name);
+            write_str(&mut raw, start + 16 + MAX_STRING, desc);
+            write_str(&mut raw, start + 16 + MAX_STRING + MAX_DESC, unit);
+        }
+        write_str(&mut raw, session_info_offset, &session_info);
+
+        for (gear, lap_dist_pct) in samples {
+            raw.extend(gear.to_le_bytes());
+            for value in lap_dist_pct {
+                raw.extend(value.to_le_bytes());
+            }
+        }
+
+        let path =
+            std::env::temp_dir().join(format!("iracehud-{}-{}.ibt", name, std::process::id()));
+        std::fs::write(&path, raw).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn replays_samples_and_session_info() {
+        let samples = [(3, [0.1, 0.2, 0.3]), (-1, [0.4, 0.5, 0.6])];
+        let path = write_ibt("valid", &samples);
+
+        let file = IbtFile::open(&path).unwrap();
+        assert_eq!(file.sample_count(), 2);
+        let lap_dist_pct = &file.variables()["CarIdxLapDistPct"];
+        assert_eq!(lap_dist_pct.offset, 4);
+        assert_eq!(lap_dist_pct.desc, "Lap distance by car");
+        assert_eq!(lap_dist_pct.unit, "%");
+
+        let mut source = IbtSource::open(&path, ReplaySpeed::Stepped).unwrap();
+        let stepper = source.stepper();
+        for (gear, lap_dist_pct) in samples {
+            stepper.notify_one();
+            let tick = source.next_tick().await.unwrap();
+            assert_eq!(tick.read_name::<i32>("Gear"), Some(gear));
+            assert_eq!(tick.value_count("Gear"), Some(1));
+            assert_eq!(
+                tick.read_name::<Vec<f32>>("CarIdxLapDistPct"),
+                Some(lap_dist_pct.to_vec())
+            );
+            assert_eq!(tick.value_count("CarIdxLapDistPct"), Some(3));
+            assert_eq!(tick.read_value("CarIdxLapDistPct", 3), None);
+            assert_eq!(
+                tick.session_info()["WeekendInfo"]["TrackName"].as_str(),
+                Some("spa")
+            );
+            assert_eq!(tick.session_info_update(), 3);
+        }
+        assert!(source.next_tick().await.is_none());
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn rejects_corrupt_session_info() {
+        for (name, offset, len) in [
+            ("negative-len", 0, -1),
+            ("negative-offset", -16, 8),
+            ("past-end", 0, 4096),
+        ] {
+            let path = write_ibt(name, &[]);
+            let mut raw = std::fs::read(&path).unwrap();
+            write_i32(&mut raw, 16, len);
+            write_i32(&mut raw, 20, offset);
+            std::fs::write(&path, raw).unwrap();
+
+            let err = IbtFile::open(&path).err().unwrap();
+            assert!(err.to_string().starts_with("Invalid .ibt"), "{}", err);
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
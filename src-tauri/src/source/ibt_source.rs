@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use eyre::Result;
+use log::{error, info};
+use simetry::iracing::Value;
+use tokio::sync::Notify;
+use yaml_rust::Yaml;
+
+use super::ibt_file::{IbtFile, IbtVarHeader};
+use super::replay_source::{ReplayClock, ReplaySpeed};
+use super::telemetry_source::TelemetrySource;
+use super::telemetry_tick::TelemetryTick;
+
+const DEFAULT_TICK_RATE: i32 = 60;
+
+pub struct IbtTick {
+    variables: Arc<HashMap<String, IbtVarHeader>>,
+    sample: Vec<u8>,
+    session_info: Arc<Yaml>,
+    session_info_update: i32,
+}
+
+impl TelemetryTick for IbtTick {
+    fn read_value(&self, name: &str, idx: usize) -> Option<Value> {
+        self.variables.get(name)?.parse_value(&self.sample, idx)
+    }
+
+    fn value_count(&self, name: &str) -> Option<usize> {
+        self.variables.get(name).map(|var| var.count)
+    }
+
+    fn session_info(&self) -> &Yaml {
+        &self.session_info
+    }
+
+    fn session_info_update(&self) -> i32 {
+        self.session_info_update
+    }
+}
+
+/// Replays an .ibt file sample by sample at its recorded tick rate
+pub struct IbtSource {
+    file: IbtFile,
+    clock: ReplayClock,
+    variables: Arc<HashMap<String, IbtVarHeader>>,
+    session_info: Arc<Yaml>,
+    next_sample: usize,
+}
+
+impl IbtSource {
+    pub fn open(path: &Path, speed: ReplaySpeed) -> Result<Self> {
+        let file = IbtFile::open(path)?;
+        info!(
+            "Replaying .ibt file {} ({} samples)",
+            path.display(),
+            file.sample_count()
+        );
+        Ok(Self {
+            variables: Arc::new(file.variables().clone()),
+            session_info: Arc::new(file.session_info().clone()),
+            clock: ReplayClock::new(speed),
+            file,
+            next_sample: 0,
+        })
+    }
+
+    pub fn stepper(&self) -> Arc<Notify> {
+        self.clock.stepper()
+    }
+}
+
+impl TelemetrySource for IbtSource {
+    type Tick = IbtTick;
+
+    async fn next_tick(&mut self) -> Option<IbtTick> {
+        let sample_idx = self.next_sample;
+        let sample = match self.file.read_sample(sample_idx) {
+            Ok(Some(sample)) => sample,
+            Ok(None) => {
+                info!(".ibt replay finished");
+                return None;
+            }
+            Err(err) => {
+                error!("Failed to read .ibt sample {}: {:?}", sample_idx, err);
+                return None;
+            }
+        };
+        self.next_sample += 1;
+
+        let tick_rate = match self.file.header().tick_rate {
+            value if value > 0 => value,
+            _ => DEFAULT_TICK_RATE,
+        };
+        self.clock
+            .wait(Duration::from_secs_f64(
+                sample_idx as f64 / tick_rate as f64,
+            ))
+            .await;
+
+        Some(IbtTick {
+            variables: self.variables.clone(),
+            sample,
+            session_info: self.session_info.clone(),
+            session_info_update: self.file.header().session_info_update,
+        })
+    }
+}
@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use simetry::iracing::{Client, SimState, Value};
+use yaml_rust::Yaml;
+
+use super::telemetry_source::TelemetrySource;
+use super::telemetry_tick::TelemetryTick;
+
+const SESSION_UPDATE_PERIOD_MILLIS: u64 = 25;
+
+pub struct IRacingSource {
+    client: Client,
+    ticked: bool,
+}
+
+impl IRacingSource {
+    pub async fn connect(retry_delay: Duration) -> Self {
+        Self {
+            client: Client::connect(retry_delay).await,
+            ticked: false,
+        }
+    }
+}
+
+impl TelemetrySource for IRacingSource {
+    type Tick = SimState;
+
+    async fn next_tick(&mut self) -> Option<SimState> {
+        if self.ticked {
+            tokio::time::sleep(Duration::from_millis(SESSION_UPDATE_PERIOD_MILLIS)).await;
+        }
+        self.ticked = true;
+        self.client.next_sim_state().await
+    }
+}
+
+impl TelemetryTick for SimState {
+    fn read_value(&self, name: &str, idx: usize) -> Option<Value> {
+        self.read_name_at(name, idx)
+    }
+
+    fn value_count(&self, name: &str) -> Option<usize> {
+        self.variables().get(name).map(|var| var.count)
+    }
+
+    fn session_info(&self) -> &Yaml {
+        SimState::session_info(self)
+    }
+
+    fn session_info_update(&self) -> i32 {
+        self.header().session_info_update
+    }
+}
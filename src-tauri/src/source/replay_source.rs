@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, ErrorKind, Read};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use eyre::{Result, bail};
+use log::{error, info};
+use simetry::iracing::Value;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+use yaml_rust::{Yaml, YamlLoader};
+
+use super::capture::{CAPTURE_MAGIC, CaptureRecord};
+use super::telemetry_source::TelemetrySource;
+use super::telemetry_tick::TelemetryTick;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplaySpeed {
+    /// Plays back at the recorded pace scaled by the multiplier, `1.0` is real time
+    Multiplier(f64),
+    /// Emits a single tick each time the stepper is notified
+    Stepped,
+}
+
+impl FromStr for ReplaySpeed {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "step" => Ok(ReplaySpeed::Stepped),
+            value => match value.trim_end_matches('x').parse::<f64>() {
+                Ok(value) if value > 0.0 => Ok(ReplaySpeed::Multiplier(value)),
+                _ => Err(format!("Invalid replay speed: {}", s)),
+            },
+        }
+    }
+}
+
+/// Paces replayed ticks according to the requested `ReplaySpeed`
+pub struct ReplayClock {
+    speed: ReplaySpeed,
+    stepper: Arc<Notify>,
+    started: Option<Instant>,
+}
+
+impl ReplayClock {
+    pub fn new(speed: ReplaySpeed) -> Self {
+        Self {
+            speed,
+            stepper: Arc::new(Notify::new()),
+            started: None,
+        }
+    }
+
+    /// Handle used to advance a `ReplaySpeed::Stepped` replay by one tick
+    pub fn stepper(&self) -> Arc<Notify> {
+        self.stepper.clone()
+    }
+
+    /// Waits until a tick recorded `elapsed` after the start of the recording is due
+    pub async fn wait(&mut self, elapsed: Duration) {
+        match self.speed {
+            ReplaySpeed::Stepped => self.stepper.notified().await,
+            ReplaySpeed::Multiplier(multiplier) => {
+                let started = *self.started.get_or_insert_with(Instant::now);
+                tokio::time::sleep_until(started + elapsed.div_f64(multiplier)).await;
+            }
+        }
+    }
+}
+
+pub struct CapturedTick {
+    variables: Arc<HashMap<String, usize>>,
+    values: Vec<Option<Vec<Value>>>,
+    session_info: Arc<Yaml>,
+    session_info_update: i32,
+}
+
+impl TelemetryTick for CapturedTick {
+    fn read_value(&self, name: &str, idx: usize) -> Option<Value> {
+        let var_idx = self.variables.get(name)?;
+        self.values.get(*var_idx)?.as_ref()?.get(idx).cloned()
+    }
+
+    fn value_count(&self, name: &str) -> Option<usize> {
+        let var_idx = self.variables.get(name)?;
+        self.values
+            .get(*var_idx)?
+            .as_ref()
+            .map(|values| values.len())
+    }
+
+    fn session_info(&self) -> &Yaml {
+        &self.session_info
+    }
+
+    fn session_info_update(&self) -> i32 {
+        self.session_info_update
+    }
+}
+
+pub struct ReplaySource {
+    reader: BufReader<File>,
+    clock: ReplayClock,
+    variables: Arc<HashMap<String, usize>>,
+    session_info: Arc<Yaml>,
+    session_info_update: i32,
+}
+
+impl ReplaySource {
+    pub fn open(path: &Path, speed: ReplaySpeed) -> Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut magic = [0u8; CAPTURE_MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if &magic != CAPTURE_MAGIC {
+            bail!("Not a session capture file: {}", path.display());
+        }
+        let variables = match rmp_serde::decode::from_read(&mut reader)? {
+            CaptureRecord::Variables(variables) => variables,
+            _ => bail!("Session capture is missing the variables table"),
+        };
+        let variables = variables
+            .into_iter()
+            .enumerate()
+            .map(|(idx, name)| (name, idx))
+            .collect();
+        info!("Replaying session capture: {}", path.display());
+        Ok(Self {
+            reader,
+            clock: ReplayClock::new(speed),
+            variables: Arc::new(variables),
+            session_info: Arc::new(Yaml::Null),
+            session_info_update: 0,
+        })
+    }
+
+    pub fn stepper(&self) -> Arc<Notify> {
+        self.clock.stepper()
+    }
+
+    fn read_record(&mut self) -> Option<CaptureRecord> {
+        match rmp_serde::decode::from_read(&mut self.reader) {
+            Ok(record) => Some(record),
+            Err(rmp_serde::decode::Error::InvalidMarkerRead(err))
+                if err.kind() == ErrorKind::UnexpectedEof =>
+            {
+                info!("Session capture replay finished");
+                None
+            }
+            Err(err) => {
+                error!("Failed to read session capture record: {:?}", err);
+                None
+            }
+        }
+    }
+}
+
+impl TelemetrySource for ReplaySource {
+    type Tick = CapturedTick;
+
+    async fn next_tick(&mut self) -> Option<CapturedTick> {
+        loop {
+            match self.read_record()? {
+                CaptureRecord::Variables(_) => {
+                    error!("Unexpected variables table in session capture");
+                }
+                CaptureRecord::SessionInfo { update, yaml } => {
+                    match YamlLoader::load_from_str(&yaml) {
+                        Ok(mut items) if !items.is_empty() => {
+                            self.session_info = Arc::new(items.swap_remove(0));
+                        }
+                        Ok(_) => error!("Session info did not contain any items"),
+                        Err(err) => error!("Failed to parse session info: {:?}", err),
+                    }
+                    self.session_info_update = update;
+                }
+                CaptureRecord::Tick { elapsed_ms, values } => {
+                    self.clock.wait(Duration::from_millis(elapsed_ms)).await;
+                    let values = values
+                        .into_iter()
+                        .map(|values| {
+                            values.map(|values| values.into_iter().map(Value::from).collect())
+                        })
+                        .collect();
+                    return Some(CapturedTick {
+                        variables: self.variables.clone(),
+                        values,
+                        session_info: self.session_info.clone(),
+                        session_info_update: self.session_info_update,
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::session_data::PROCESSED_VARIABLES;
+    use crate::source::capture_recorder::CaptureRecorder;
+    use crate::source::synthetic_session::SyntheticConfig;
+    use crate::source::synthetic_source::SyntheticSource;
+
+    /// Name, entry count and entries of a variable
+    type VarContents = (&'static str, Option<usize>, Vec<Option<Value>>);
+
+    /// Everything `SessionData` reads from a tick
+    #[derive(Debug, PartialEq)]
+    struct TickContents {
+        values: Vec<VarContents>,
+        session_info: Yaml,
+        session_info_update: i32,
+    }
+
+    fn contents<T: TelemetryTick>(tick: &T) -> TickContents {
+        TickContents {
+            values: PROCESSED_VARIABLES
+                .iter()
+                .map(|name| {
+                    let count = tick.value_count(name);
+                    let values = (0..count.unwrap_or_default())
+                        .map(|idx| tick.read_value(name, idx))
+                        .collect();
+                    (*name, count, values)
+                })
+                .collect(),
+            session_info: tick.session_info().clone(),
+            session_info_update: tick.session_info_update(),
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_what_was_captured() {
+        let path = std::env::temp_dir().join(format!(
+            "iracehud-capture-{}.{}",
+            std::process::id(),
+            crate::source::capture::CAPTURE_EXTENSION
+        ));
+        let mut source = SyntheticSource::new(SyntheticConfig::default(), ReplaySpeed::Stepped);
+        let stepper = source.stepper();
+        let mut recorder = CaptureRecorder::create(&path).unwrap();
+
+        // a few ticks every 10 s until the first lap revises the session info
+        let mut recorded = Vec::new();
+        for tick_idx in 0..20_000 {
+            stepper.notify_one();
+            let tick = source.next_tick().await.unwrap();
+            let revised = recorded.last().is_some_and(|last: &TickContents| {
+                last.session_info_update != tick.session_info_update()
+            });
+            if tick_idx % 600 < 2 || revised {
+                recorder.record(&tick).unwrap();
+                recorded.push(contents(&tick));
+            }
+            if revised {
+                break;
+            }
+        }
+        recorder.finish().unwrap();
+        let updates = recorded
+            .iter()
+            .map(|tick| tick.session_info_update)
+            .collect::<Vec<_>>();
+        assert!(updates.first() != updates.last(), "{:?}", updates);
+
+        let mut replay = ReplaySource::open(&path, ReplaySpeed::Stepped).unwrap();
+        let stepper = replay.stepper();
+        for (idx, expected) in recorded.iter().enumerate() {
+            stepper.notify_one();
+            let tick = replay.next_tick().await.unwrap();
+            assert!(contents(&tick) == *expected, "tick {} differs", idx);
+        }
+        assert!(replay.next_tick().await.is_none());
+        let _ = std::fs::remove_file(path);
+    }
+}
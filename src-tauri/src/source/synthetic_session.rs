@@ -0,0 +1,1020 @@
+use std::f32::consts::PI;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+
+use crate::session::session_flags::SessionFlags;
+
+const PROFILE_SAMPLES: usize = 1000;
+const BRAKING_ZONE_PCT: f32 = 0.015;
+const ACCELERATION_ZONE_PCT: f32 = 0.035;
+const PIT_ENTRY_PCT: f32 = 0.94;
+const PIT_STALL_PCT: f32 = 0.985;
+const PIT_EXIT_PCT: f32 = 0.06;
+const PIT_SPEED: f32 = 22.0; // m/s
+const OFF_TRACK_SPEED_RATIO: f32 = 0.3;
+const GRID_SPACING_PCT: f32 = 0.002;
+const SIDE_BY_SIDE_DISTANCE: f32 = 5.0; // m
+const GEAR_COUNT: i32 = 6;
+const IDLE_RPM: f32 = 3000.0;
+const SHIFT_RPM: f32 = 7200.0;
+const BLINK_RPM: f32 = 7500.0;
+const SESSION_RESTART_SECS: f64 = 60.0;
+const REFUEL_RATE: f32 = 2.5; // l/s
+const FUEL_DENSITY: f32 = 0.75; // kg/l
+const LOCAL_YELLOW_PCT: f32 = 0.05;
+const BLUE_FLAG_PCT: f32 = 0.01;
+const TRACK_TEMP: f32 = 31.2; // C
+const TRACK_WARMING: f32 = 0.002; // C/s
+
+pub const AIR_TEMP: f32 = 22.4; // C
+pub const WIND_VEL: f32 = 2.1; // m/s
+pub const WIND_DIR: f32 = 1.57; // rad
+pub const SKIES_PARTLY_CLOUDY: i32 = 1;
+pub const TRACK_WETNESS_DRY: i32 = 1;
+
+pub const TRACK_SURFACE_NOT_IN_WORLD: i32 = -1;
+pub const TRACK_SURFACE_OFF_TRACK: i32 = 0;
+pub const TRACK_SURFACE_IN_PIT_STALL: i32 = 1;
+pub const TRACK_SURFACE_APPROACHING_PITS: i32 = 2;
+pub const TRACK_SURFACE_ON_TRACK: i32 = 3;
+
+const FIRST_NAMES: [&str; 16] = [
+    "Alex", "Sam", "Chris", "Jordan", "Taylor", "Morgan", "Jamie", "Robin", "Casey", "Riley",
+    "Avery", "Quinn", "Drew", "Kai", "Sasha", "Nico",
+];
+const LAST_NAMES: [&str; 16] = [
+    "Keller",
+    "Moreau",
+    "Silva",
+    "Novak",
+    "Berg",
+    "Costa",
+    "Lindqvist",
+    "Okafor",
+    "Tanaka",
+    "Fischer",
+    "Romano",
+    "Walsh",
+    "Petrov",
+    "Laine",
+    "Duarte",
+    "Hughes",
+];
+const LICENSE_CLASSES: [&str; 4] = ["D", "C", "B", "A"];
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SyntheticClass {
+    pub name: String,
+    pub short_name: String,
+    pub car_class_id: u32,
+    pub color: u32,
+    pub car_count: u32,
+    pub lap_time_secs: f32,
+    /// Relative pace difference between the fastest and the slowest car of the class
+    pub pace_spread: f32,
+}
+
+impl Default for SyntheticClass {
+    fn default() -> Self {
+        Self {
+            name: "GT3 Class".to_string(),
+            short_name: "GT3".to_string(),
+            car_class_id: 4083,
+            color: 0x33ceff,
+            car_count: 14,
+            lap_time_secs: 137.0,
+            pace_spread: 0.03,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SyntheticConfig {
+    pub seed: u64,
+    /// Session type as reported by iRacing, e.g. `Race`, `Practice` or `Lone Qualify`
+    pub session_type: String,
+    /// `0` for a timed session
+    pub session_laps: u32,
+    pub session_time_secs: f64,
+    pub track_id: u32,
+    pub track_name: String,
+    pub track_length_km: f32,
+    pub corner_count: usize,
+    pub classes: Vec<SyntheticClass>,
+    /// Index into `classes` of the class the player drives in
+    pub player_class: usize,
+    /// Chance of an off-track excursion per car per lap
+    pub off_track_chance: f64,
+    pub pit_stops: bool,
+    pub incident_limit: u32,
+    /// Player car tank size in liters, refilled at every pit stop
+    pub fuel_capacity: f32,
+    pub fuel_per_lap: f32,
+}
+
+impl Default for SyntheticConfig {
+    fn default() -> Self {
+        Self {
+            seed: 1,
+            session_type: "Race".to_string(),
+            session_laps: 20,
+            session_time_secs: 3600.0,
+            track_id: 18,
+            track_name: "Road America".to_string(),
+            track_length_km: 6.515,
+            corner_count: 14,
+            classes: vec![
+                SyntheticClass {
+                    name: "GTP Class".to_string(),
+                    short_name: "GTP".to_string(),
+                    car_class_id: 4029,
+                    color: 0xffda59,
+                    car_count: 6,
+                    lap_time_secs: 119.0,
+                    pace_spread: 0.02,
+                },
+                SyntheticClass::default(),
+            ],
+            player_class: 1,
+            off_track_chance: 0.03,
+            pit_stops: true,
+            incident_limit: 17,
+            fuel_capacity: 100.0,
+            fuel_per_lap: 3.4,
+        }
+    }
+}
+
+/// Speed profile of the synthetic track, sampled over the lap distance
+struct TrackProfile {
+    length: f32, // in m
+    speed_ratio: Vec<f32>,
+    time_fraction: Vec<f32>,
+    inverse_ratio_sum: f32,
+    corners: Vec<(f32, f32)>,
+}
+
+impl TrackProfile {
+    fn new(length: f32, corner_count: usize, rng: &mut StdRng) -> Self {
+        let mut corners = (0..corner_count)
+            .map(|idx| {
+                let slot = 1.0 / corner_count as f32;
+                let pct = (idx as f32 + rng.random_range(0.2..0.8)) * slot;
+                (pct, rng.random_range(0.3..0.8))
+            })
+            .collect::<Vec<(f32, f32)>>();
+        corners.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let speed_ratio = (0..PROFILE_SAMPLES)
+            .map(|idx| {
+                let pct = idx as f32 / PROFILE_SAMPLES as f32;
+                corners
+                    .iter()
+                    .map(|(corner_pct, min_ratio)| {
+                        let before = (corner_pct - pct).rem_euclid(1.0);
+                        let after = (pct - corner_pct).rem_euclid(1.0);
+                        let progress = if before < after {
+                            (before / BRAKING_ZONE_PCT).min(1.0)
+                        } else {
+                            (after / ACCELERATION_ZONE_PCT).min(1.0)
+                        };
+                        min_ratio + (1.0 - min_ratio) * progress.sqrt()
+                    })
+                    .fold(1.0, f32::min)
+            })
+            .collect::<Vec<f32>>();
+
+        let inverse_ratio_sum = speed_ratio.iter().map(|ratio| 1.0 / ratio).sum::<f32>();
+        let mut elapsed = 0.0;
+        let time_fraction = speed_ratio
+            .iter()
+            .map(|ratio| {
+                let fraction = elapsed / inverse_ratio_sum;
+                elapsed += 1.0 / ratio;
+                fraction
+            })
+            .collect();
+
+        Self {
+            length,
+            speed_ratio,
+            time_fraction,
+            inverse_ratio_sum,
+            corners,
+        }
+    }
+
+    fn sample(&self, pct: f32) -> usize {
+        ((pct.rem_euclid(1.0) * PROFILE_SAMPLES as f32) as usize).min(PROFILE_SAMPLES - 1)
+    }
+
+    fn speed_ratio(&self, pct: f32) -> f32 {
+        self.speed_ratio[self.sample(pct)]
+    }
+
+    /// Share of the lap time needed to get from the line to `pct`
+    fn time_fraction(&self, pct: f32) -> f32 {
+        self.time_fraction[self.sample(pct)]
+    }
+
+    /// Speed at `pct` of a car doing laps of `lap_time` seconds
+    fn speed(&self, pct: f32, lap_time: f32) -> f32 {
+        let top_speed = self.length * self.inverse_ratio_sum / PROFILE_SAMPLES as f32 / lap_time;
+        top_speed * self.speed_ratio(pct)
+    }
+
+    fn steering(&self, pct: f32) -> f32 {
+        let ratio = self.speed_ratio(pct);
+        let nearest = self
+            .corners
+            .iter()
+            .enumerate()
+            .min_by(|a, b| {
+                let a = (a.1.0 - pct)
+                    .rem_euclid(1.0)
+                    .min((pct - a.1.0).rem_euclid(1.0));
+                let b = (b.1.0 - pct)
+                    .rem_euclid(1.0)
+                    .min((pct - b.1.0).rem_euclid(1.0));
+                a.total_cmp(&b)
+            })
+            .map(|(idx, _)| idx)
+            .unwrap_or(0);
+        let direction = if nearest % 2 == 0 { 1.0 } else { -1.0 };
+        direction * (1.0 - ratio) * PI / 2.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CarState {
+    Grid,
+    Running,
+    OffTrack { until: f64 },
+    PitLane,
+    PitStall { until: f64 },
+    Finished,
+}
+
+#[derive(Debug, Clone)]
+pub struct SyntheticCar {
+    pub car_idx: usize,
+    pub class_idx: usize,
+    pub user_name: String,
+    pub car_number: String,
+    pub irating: u32,
+    pub lic_string: String,
+    pub lap_dist_pct: f32,
+    pub laps_completed: i32,
+    pub lap_started: f64,
+    pub last_lap_time: f32,
+    pub best_lap_time: f32,
+    pub speed: f32,
+    base_lap_time: f32,
+    lap_time: f32,
+    grid_idx: usize,
+    state: CarState,
+    started: bool,
+    pit_lap: Option<i32>,
+    off_track_pct: Option<f32>,
+}
+
+impl SyntheticCar {
+    pub fn track_surface(&self) -> i32 {
+        match self.state {
+            CarState::Grid | CarState::Running => TRACK_SURFACE_ON_TRACK,
+            CarState::OffTrack { .. } => TRACK_SURFACE_OFF_TRACK,
+            CarState::PitLane => TRACK_SURFACE_APPROACHING_PITS,
+            CarState::PitStall { .. } => TRACK_SURFACE_IN_PIT_STALL,
+            CarState::Finished => TRACK_SURFACE_NOT_IN_WORLD,
+        }
+    }
+
+    pub fn is_on_pit_road(&self) -> bool {
+        matches!(self.state, CarState::PitLane | CarState::PitStall { .. })
+    }
+
+    pub fn laps_started(&self) -> i32 {
+        match self.started {
+            true => self.laps_completed + 1,
+            false => 0,
+        }
+    }
+
+    pub fn total_completed(&self) -> f32 {
+        self.laps_completed as f32 + self.lap_dist_pct
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlayerInputs {
+    pub throttle: f32,
+    pub brake: f32,
+    pub abs_active: bool,
+    pub steering: f32,
+    pub gear: i32,
+    pub rpm: f32,
+}
+
+/// Deterministic multi-class session that produces the same data iRacing would report
+pub struct SyntheticSession {
+    pub config: SyntheticConfig,
+    pub cars: Vec<SyntheticCar>,
+    pub player_car_idx: usize,
+    pub session_time: f64,
+    pub session_tick: i32,
+    pub session_info_update: i32,
+    pub player_inputs: PlayerInputs,
+    pub player_incidents: i32,
+    pub player_lap_current_time: f32,
+    pub player_fuel: f32,
+    checkered_time: Option<f64>,
+    session_info_dirty: bool,
+    session_info_updated: f64,
+    track: TrackProfile,
+    rng: StdRng,
+}
+
+impl SyntheticSession {
+    pub fn new(mut config: SyntheticConfig) -> Self {
+        if config.classes.is_empty() {
+            config.classes.push(SyntheticClass::default());
+        }
+        config.player_class = config.player_class.min(config.classes.len() - 1);
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let track = TrackProfile::new(
+            config.track_length_km * 1000.0,
+            config.corner_count.max(1),
+            &mut rng,
+        );
+        let mut session = Self {
+            player_fuel: config.fuel_capacity,
+            config,
+            cars: Vec::new(),
+            player_car_idx: 0,
+            session_time: 0.0,
+            session_tick: 0,
+            session_info_update: 0,
+            player_inputs: PlayerInputs::default(),
+            player_incidents: 0,
+            player_lap_current_time: 0.0,
+            checkered_time: None,
+            session_info_dirty: true,
+            session_info_updated: f64::NEG_INFINITY,
+            track,
+            rng,
+        };
+        session.populate();
+        session
+    }
+
+    fn populate(&mut self) {
+        // Car index 0 is the pace car like in iRacing
+        let mut car_idx = 1;
+        let mut cars = Vec::new();
+        for (class_idx, class) in self.config.classes.iter().enumerate() {
+            for class_car in 0..class.car_count {
+                let pace = class.pace_spread * class_car as f32 / class.car_count.max(1) as f32;
+                let first_name = FIRST_NAMES[self.rng.random_range(0..FIRST_NAMES.len())];
+                let last_name = LAST_NAMES[self.rng.random_range(0..LAST_NAMES.len())];
+                let license = LICENSE_CLASSES[self.rng.random_range(0..LICENSE_CLASSES.len())];
+                cars.push(SyntheticCar {
+                    car_idx,
+                    class_idx,
+                    user_name: format!("{} {}", first_name, last_name),
+                    car_number: (car_idx * 7 % 100).to_string(),
+                    irating: self.rng.random_range(900..5500),
+                    lic_string: format!("{} {:.2}", license, self.rng.random_range(1.0..4.99)),
+                    lap_dist_pct: 0.0,
+                    laps_completed: 0,
+                    lap_started: 0.0,
+                    last_lap_time: -1.0,
+                    best_lap_time: -1.0,
+                    speed: 0.0,
+                    base_lap_time: class.lap_time_secs * (1.0 + pace),
+                    lap_time: class.lap_time_secs * (1.0 + pace),
+                    grid_idx: 0,
+                    state: CarState::Grid,
+                    started: false,
+                    pit_lap: None,
+                    off_track_pct: None,
+                });
+                car_idx += 1;
+            }
+        }
+
+        // Grid ordered by class pace with a bit of shuffling inside of each class
+        let grid_keys = cars
+            .iter()
+            .map(|car| car.base_lap_time * self.rng.random_range(0.99..1.01))
+            .collect::<Vec<f32>>();
+        let mut grid = grid_keys
+            .into_iter()
+            .zip(cars)
+            .collect::<Vec<(f32, SyntheticCar)>>();
+        grid.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut cars = grid.into_iter().map(|(_, car)| car).collect::<Vec<_>>();
+        for (grid_idx, car) in cars.iter_mut().enumerate() {
+            car.lap_dist_pct = 1.0 - GRID_SPACING_PCT * (grid_idx + 1) as f32;
+            car.grid_idx = grid_idx;
+        }
+
+        let player_class = self.config.player_class;
+        let player_class_cars = cars
+            .iter()
+            .filter(|car| car.class_idx == player_class)
+            .map(|car| car.car_idx)
+            .collect::<Vec<usize>>();
+        self.player_car_idx = player_class_cars
+            .get(player_class_cars.len() / 2)
+            .copied()
+            .unwrap_or(1);
+
+        cars.sort_by_key(|car| car.car_idx);
+        let expected_laps = self.expected_laps();
+        for car in cars.iter_mut() {
+            car.pit_lap = match self.config.pit_stops && expected_laps >= 6 {
+                true => Some(
+                    self.rng
+                        .random_range(expected_laps / 3..=expected_laps * 2 / 3),
+                ),
+                false => None,
+            };
+        }
+        self.cars = cars;
+    }
+
+    fn expected_laps(&self) -> i32 {
+        match self.config.session_laps {
+            0 => {
+                let fastest = self
+                    .config
+                    .classes
+                    .iter()
+                    .map(|class| class.lap_time_secs)
+                    .fold(f32::MAX, f32::min);
+                (self.config.session_time_secs / fastest as f64) as i32
+            }
+            laps => laps as i32,
+        }
+    }
+
+    pub fn car(&self, car_idx: usize) -> Option<&SyntheticCar> {
+        self.cars.iter().find(|car| car.car_idx == car_idx)
+    }
+
+    pub fn player(&self) -> Option<&SyntheticCar> {
+        self.car(self.player_car_idx)
+    }
+
+    pub fn car_count(&self) -> usize {
+        self.cars.iter().map(|car| car.car_idx).max().unwrap_or(0) + 1
+    }
+
+    pub fn track_length(&self) -> f32 {
+        self.track.length
+    }
+
+    pub fn is_race(&self) -> bool {
+        self.config.session_type == "Race"
+    }
+
+    pub fn session_time_remaining(&self) -> f64 {
+        (self.config.session_time_secs - self.session_time).max(0.0)
+    }
+
+    pub fn session_laps_remaining(&self) -> i32 {
+        let leader_laps = self
+            .cars
+            .iter()
+            .map(|car| car.laps_completed)
+            .max()
+            .unwrap_or(0);
+        match self.config.session_laps {
+            0 => 32767,
+            laps => (laps as i32 - leader_laps).max(0),
+        }
+    }
+
+    /// Estimated time for a car of the class to get from the line to `pct`
+    pub fn estimated_time(&self, car: &SyntheticCar) -> f32 {
+        let class_lap_time = self.config.classes[car.class_idx].lap_time_secs;
+        class_lap_time * self.track.time_fraction(car.lap_dist_pct)
+    }
+
+    /// Delta of the current player lap to a reference lap of `lap_time`
+    pub fn player_delta_to(&self, lap_time: f32) -> f32 {
+        match (self.player(), lap_time > 0.0) {
+            (Some(player), true) => {
+                self.player_lap_current_time
+                    - lap_time * self.track.time_fraction(player.lap_dist_pct)
+            }
+            _ => 0.0,
+        }
+    }
+
+    pub fn gear_shift_rpm(&self) -> f32 {
+        SHIFT_RPM
+    }
+
+    pub fn gear_blink_rpm(&self) -> f32 {
+        BLINK_RPM
+    }
+
+    pub fn class_est_lap_time(&self, class_idx: usize) -> f32 {
+        self.config.classes[class_idx].lap_time_secs
+    }
+
+    /// Returns true when the session info should be published again
+    pub fn take_session_info_update(&mut self) -> bool {
+        if self.session_info_dirty && self.session_time - self.session_info_updated >= 2.0 {
+            self.session_info_dirty = false;
+            self.session_info_updated = self.session_time;
+            self.session_info_update += 1;
+            return true;
+        }
+        false
+    }
+
+    pub fn step(&mut self, dt: f64) {
+        self.session_tick += 1;
+        self.session_time += dt;
+        let racing = !self.is_race() || self.session_time >= 3.0;
+        if let Some(checkered_time) = self.checkered_time
+            && self.session_time - checkered_time >= SESSION_RESTART_SECS
+        {
+            self.restart();
+            return;
+        }
+        if self.checkered_time.is_none() && self.is_session_over() {
+            self.checkered_time = Some(self.session_time);
+        }
+
+        let player_pct = self.player().map(|car| car.lap_dist_pct);
+        for idx in 0..self.cars.len() {
+            self.step_car(idx, dt, racing);
+        }
+        if let Some(player_pct) = player_pct {
+            self.step_player_fuel(player_pct, dt);
+        }
+
+        if let Some(player) = self.player().cloned() {
+            self.player_lap_current_time = match player.started {
+                true => (self.session_time - player.lap_started) as f32,
+                false => 0.0,
+            };
+            self.player_inputs = self.player_inputs(&player);
+        }
+    }
+
+    fn step_player_fuel(&mut self, previous_pct: f32, dt: f64) {
+        let Some(player) = self.player() else {
+            return;
+        };
+        self.player_fuel = match player.state {
+            CarState::PitStall { .. } => {
+                (self.player_fuel + REFUEL_RATE * dt as f32).min(self.config.fuel_capacity)
+            }
+            _ => {
+                let distance = (player.lap_dist_pct - previous_pct).rem_euclid(1.0);
+                (self.player_fuel - distance * self.config.fuel_per_lap).max(0.0)
+            }
+        };
+    }
+
+    /// Fuel flow of the player car in kg/h at the current speed
+    pub fn player_fuel_use_per_hour(&self) -> f32 {
+        let Some(player) = self.player() else {
+            return 0.0;
+        };
+        let laps_per_hour = player.speed / self.track.length * 3600.0;
+        laps_per_hour * self.config.fuel_per_lap * FUEL_DENSITY
+    }
+
+    fn is_session_over(&self) -> bool {
+        match self.config.session_laps {
+            0 => self.session_time >= self.config.session_time_secs,
+            laps => self
+                .cars
+                .iter()
+                .any(|car| car.laps_completed >= laps as i32),
+        }
+    }
+
+    /// Starts a new session with the next seed once the previous one is over
+    fn restart(&mut self) {
+        let session_info_update = self.session_info_update;
+        *self = Self::new(SyntheticConfig {
+            seed: self.config.seed.wrapping_add(1),
+            ..self.config.clone()
+        });
+        self.session_info_update = session_info_update;
+    }
+
+    fn step_car(&mut self, idx: usize, dt: f64, racing: bool) {
+        let session_time = self.session_time;
+        let checkered = self.checkered_time.is_some();
+        let off_track_chance = self.config.off_track_chance;
+        let car = &self.cars[idx];
+        let pct = car.lap_dist_pct;
+
+        let speed = match car.state {
+            CarState::Grid if !racing => 0.0,
+            CarState::Grid | CarState::Running => self.track.speed(pct, car.lap_time),
+            CarState::OffTrack { .. } => {
+                self.track.speed(pct, car.lap_time) * OFF_TRACK_SPEED_RATIO
+            }
+            CarState::PitLane => PIT_SPEED.min(self.track.speed(pct, car.lap_time)),
+            CarState::PitStall { .. } | CarState::Finished => 0.0,
+        };
+        let mut new_pct = pct + speed * dt as f32 / self.track.length;
+
+        let car = &mut self.cars[idx];
+        car.speed = speed;
+        car.state = match car.state {
+            CarState::Grid if speed > 0.0 => CarState::Running,
+            CarState::Running
+                if car
+                    .off_track_pct
+                    .is_some_and(|off_pct| pct < off_pct && new_pct >= off_pct) =>
+            {
+                car.off_track_pct = None;
+                if car.car_idx == self.player_car_idx {
+                    self.player_incidents += 1;
+                }
+                CarState::OffTrack {
+                    until: session_time + self.rng.random_range(2.0..6.0),
+                }
+            }
+            CarState::Running
+                if car.pit_lap == Some(car.laps_started())
+                    && pct < PIT_ENTRY_PCT
+                    && new_pct >= PIT_ENTRY_PCT =>
+            {
+                CarState::PitLane
+            }
+            CarState::OffTrack { until } if session_time >= until => CarState::Running,
+            CarState::PitLane
+                if car.pit_lap.is_some() && pct < PIT_STALL_PCT && new_pct >= PIT_STALL_PCT =>
+            {
+                car.pit_lap = None;
+                new_pct = PIT_STALL_PCT;
+                CarState::PitStall {
+                    until: session_time + self.rng.random_range(20.0..35.0),
+                }
+            }
+            CarState::PitStall { until } if session_time >= until => CarState::PitLane,
+            CarState::PitLane
+                if car.pit_lap.is_none() && pct < PIT_EXIT_PCT && new_pct >= PIT_EXIT_PCT =>
+            {
+                CarState::Running
+            }
+            state => state,
+        };
+
+        if new_pct >= 1.0 {
+            new_pct -= 1.0;
+            let crossed_at = session_time - (new_pct / (speed / self.track.length)) as f64;
+            if car.started {
+                car.laps_completed += 1;
+                car.last_lap_time = (crossed_at - car.lap_started) as f32;
+                if car.best_lap_time < 0.0 || car.last_lap_time < car.best_lap_time {
+                    car.best_lap_time = car.last_lap_time;
+                }
+                self.session_info_dirty = true;
+            }
+            car.started = true;
+            car.lap_started = crossed_at;
+            car.lap_time = car.base_lap_time * (1.0 + self.rng.random_range(-0.006..0.006));
+            car.off_track_pct = match self.rng.random_bool(off_track_chance) {
+                true => Some(self.rng.random_range(0.05..0.9)),
+                false => None,
+            };
+            if checkered && car.state != CarState::Finished {
+                car.state = CarState::Finished;
+            }
+        }
+        car.lap_dist_pct = new_pct;
+    }
+
+    fn player_inputs(&self, player: &SyntheticCar) -> PlayerInputs {
+        let pct = player.lap_dist_pct;
+        let ratio = self.track.speed_ratio(pct);
+        let next_ratio = self.track.speed_ratio(pct + 1.0 / PROFILE_SAMPLES as f32);
+        let top_speed = self.track.speed(pct, player.lap_time) / ratio;
+        let (throttle, brake) = match player.state {
+            CarState::PitStall { .. } | CarState::Finished => (0.0, 0.0),
+            CarState::Grid if player.speed == 0.0 => (0.2, 0.0),
+            CarState::PitLane => (0.35, 0.0),
+            _ if next_ratio < ratio => (0.0, ((ratio - next_ratio) * 150.0).clamp(0.3, 1.0)),
+            _ if next_ratio > ratio => (1.0, 0.0),
+            _ => (0.9, 0.0),
+        };
+        let gear =
+            ((player.speed / top_speed * GEAR_COUNT as f32).ceil() as i32).clamp(1, GEAR_COUNT);
+        let gear_speed = top_speed / GEAR_COUNT as f32;
+        let gear_progress = (player.speed - gear_speed * (gear - 1) as f32) / gear_speed;
+        PlayerInputs {
+            throttle,
+            brake,
+            abs_active: brake > 0.95,
+            steering: self.track.steering(pct),
+            gear,
+            rpm: IDLE_RPM + (BLINK_RPM - IDLE_RPM) * gear_progress.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Dry dynamic weather with the track warming up under the sun
+    pub fn track_temp(&self) -> f32 {
+        TRACK_TEMP + TRACK_WARMING * self.session_time as f32
+    }
+
+    /// `SessionFlags` as seen by the player, with a local yellow for cars off track just ahead
+    pub fn session_flags(&self) -> SessionFlags {
+        let mut flags = match self.checkered_time {
+            Some(_) => SessionFlags::CHECKERED,
+            None if self.is_race() && self.session_time < 3.0 => SessionFlags::START_SET,
+            None if self.is_last_lap() => SessionFlags::GREEN | SessionFlags::WHITE,
+            None => SessionFlags::GREEN,
+        };
+        if let Some(player) = self.player()
+            && self.cars.iter().any(|car| {
+                matches!(car.state, CarState::OffTrack { .. })
+                    && (car.lap_dist_pct - player.lap_dist_pct).rem_euclid(1.0) < LOCAL_YELLOW_PCT
+            })
+        {
+            flags |= SessionFlags::YELLOW;
+        }
+        flags
+    }
+
+    /// `CarIdxSessionFlags`, blue while a car is about to be lapped
+    pub fn car_session_flags(&self, car: &SyntheticCar) -> SessionFlags {
+        if car.state == CarState::Finished {
+            return SessionFlags::CHECKERED;
+        }
+        let is_being_lapped = self.cars.iter().any(|other| {
+            other.total_completed() - car.total_completed() > 0.5
+                && (car.lap_dist_pct - other.lap_dist_pct).rem_euclid(1.0) < BLUE_FLAG_PCT
+        });
+        match is_being_lapped {
+            true => SessionFlags::BLUE,
+            false => SessionFlags::empty(),
+        }
+    }
+
+    fn is_last_lap(&self) -> bool {
+        match self.config.session_laps {
+            0 => self.session_time >= self.config.session_time_secs,
+            laps => self
+                .cars
+                .iter()
+                .any(|car| car.laps_completed + 1 >= laps as i32),
+        }
+    }
+
+    /// Encodes `CarLeftRight` of the player from cars within a car length
+    pub fn car_left_right(&self) -> u32 {
+        let Some(player) = self.player() else {
+            return 0;
+        };
+        let (mut left, mut right) = (0, 0);
+        for car in self.cars.iter() {
+            if car.car_idx == player.car_idx || car.track_surface() != TRACK_SURFACE_ON_TRACK {
+                continue;
+            }
+            let diff = (car.lap_dist_pct - player.lap_dist_pct + 1.5).rem_euclid(1.0) - 0.5;
+            if (diff * self.track.length).abs() <= SIDE_BY_SIDE_DISTANCE {
+                match car.car_idx % 2 == 0 {
+                    true => left += 1,
+                    false => right += 1,
+                }
+            }
+        }
+        match (left, right) {
+            (0, 0) => 1,
+            (1, 0) => 2,
+            (0, 1) => 3,
+            (0, _) => 6,
+            (_, 0) => 5,
+            _ => 4,
+        }
+    }
+
+    pub fn session_info_yaml(&self) -> String {
+        let mut yaml = String::new();
+        yaml.push_str(&format!(
+            "---
+WeekendInfo:
+ TrackName: synthetic
+ TrackID: {track_id}
+ TrackLength: {track_length:.2} km
+ TrackDisplayName: {track_name}
+ TrackConfigName: Synthetic
+ TrackCity: Synthetic
+ TrackCountry: Synthetic
+ TrackNumTurns: {corners}
+ TrackWeatherType: Dynamic
+ TrackSkies: Partly Cloudy
+ TrackSurfaceTemp: 31.20 C
+ TrackAirTemp: 22.40 C
+ TrackWindVel: 2.10 m/s
+ TrackWindDir: 1.57 rad
+ TrackRelativeHumidity: 55 %
+ SeriesID: 0
+ SessionID: 0
+ SubSessionID: 0
+ EventType: {session_type}
+ Category: Road
+ SimMode: full
+ Official: 0
+ NumCarClasses: {class_count}
+ WeekendOptions:
+  NumStarters: {car_count}
+  IncidentLimit: {incident_limit}
+SessionInfo:
+ Sessions:
+ - SessionNum: 0
+   SessionLaps: {session_laps}
+   SessionTime: {session_time:.4} sec
+   SessionType: {session_type}
+   SessionName: {session_name}
+   ResultsOfficial: 0
+",
+            track_id = self.config.track_id,
+            track_length = self.config.track_length_km,
+            track_name = self.config.track_name,
+            corners = self.config.corner_count,
+            session_type = self.config.session_type,
+            class_count = self.config.classes.len(),
+            car_count = self.cars.len(),
+            incident_limit = self.config.incident_limit,
+            session_laps = match self.config.session_laps {
+                0 => "unlimited".to_string(),
+                laps => laps.to_string(),
+            },
+            session_time = self.config.session_time_secs,
+            session_name = self.config.session_type.to_uppercase(),
+        ));
+
+        let mut results = self
+            .cars
+            .iter()
+            .filter(|car| car.laps_completed > 0)
+            .collect::<Vec<&SyntheticCar>>();
+        match self.is_race() {
+            true => results.sort_by(|a, b| b.total_completed().total_cmp(&a.total_completed())),
+            false => results.sort_by(|a, b| a.best_lap_time.total_cmp(&b.best_lap_time)),
+        }
+        yaml.push_str("   ResultsPositions:\n");
+        {
+            let mut class_positions = vec![0; self.config.classes.len()];
+            for (position, car) in results.iter().enumerate() {
+                let class_position = class_positions[car.class_idx];
+                class_positions[car.class_idx] += 1;
+                yaml.push_str(&format!(
+                    "   - Position: {}
+     ClassPosition: {}
+     CarIdx: {}
+     Lap: {}
+     Time: 0.0000
+     FastestLap: 0
+     FastestTime: {:.4}
+     LastTime: {:.4}
+     LapsLed: 0
+     LapsComplete: {}
+     JokerLapsComplete: 0
+     LapsDriven: {:.3}
+     Incidents: 0
+     ReasonOutID: 0
+     ReasonOutStr: Running
+",
+                    position + 1,
+                    class_position,
+                    car.car_idx,
+                    car.laps_completed,
+                    car.best_lap_time,
+                    car.last_lap_time,
+                    car.laps_completed,
+                    car.total_completed(),
+                ));
+            }
+        }
+
+        if self.is_race() {
+            let mut grid = self.cars.iter().collect::<Vec<&SyntheticCar>>();
+            grid.sort_by_key(|car| car.grid_idx);
+            yaml.push_str("QualifyResultsInfo:\n Results:\n");
+            let mut class_positions = vec![0; self.config.classes.len()];
+            for (position, car) in grid.iter().enumerate() {
+                let class_position = class_positions[car.class_idx];
+                class_positions[car.class_idx] += 1;
+                yaml.push_str(&format!(
+                    " - Position: {}
+   ClassPosition: {}
+   CarIdx: {}
+   FastestLap: 0
+   FastestTime: 0.0000
+",
+                    position, class_position, car.car_idx,
+                ));
+            }
+        }
+
+        yaml.push_str(&format!(
+            "DriverInfo:
+ DriverCarIdx: {player_car_idx}
+ DriverCarIdleRPM: {idle_rpm:.3}
+ DriverCarRedLine: {red_line:.3}
+ DriverCarFuelKgPerLtr: {fuel_density:.3}
+ DriverCarFuelMaxLtr: {fuel_max_ltr:.3}
+ DriverCarMaxFuelPct: 1.000
+ DriverCarSLShiftRPM: {shift_rpm:.3}
+ DriverCarSLBlinkRPM: {blink_rpm:.3}
+ Drivers:
+ - CarIdx: 0
+   UserName: Pace Car
+   AbbrevName:
+   Initials:
+   UserID: -1
+   TeamID: 0
+   TeamName: Pace Car
+   CarNumber: \"0\"
+   CarNumberRaw: 0
+   CarScreenName: safety pcporsche911cup
+   CarScreenNameShort: safety pcporsche911cup
+   CarClassID: 11
+   CarClassShortName:
+   CarClassEstLapTime: 150.0000
+   CarClassColor: 0xffffff
+   CarIsPaceCar: 1
+   IRating: 0
+   LicString: R 0.00
+",
+            player_car_idx = self.player_car_idx,
+            idle_rpm = IDLE_RPM,
+            red_line = BLINK_RPM + 300.0,
+            shift_rpm = SHIFT_RPM,
+            blink_rpm = BLINK_RPM,
+            fuel_density = FUEL_DENSITY,
+            fuel_max_ltr = self.config.fuel_capacity,
+        ));
+        for car in self.cars.iter() {
+            let class = &self.config.classes[car.class_idx];
+            yaml.push_str(&format!(
+                " - CarIdx: {}
+   UserName: {}
+   UserID: {}
+   TeamID: 0
+   TeamName: {}
+   CarNumber: \"{}\"
+   CarNumberRaw: {}
+   CarID: {}
+   CarScreenName: {} Car
+   CarScreenNameShort: {}
+   CarClassID: {}
+   CarClassShortName: {}
+   CarClassRelSpeed: 0
+   CarClassEstLapTime: {:.4}
+   CarClassColor: 0x{:06x}
+   CarIsPaceCar: 0
+   IRating: {}
+   LicString: {}
+   ClubName: Synthetic
+   DivisionName: Division 1
+",
+                car.car_idx,
+                car.user_name,
+                100000 + car.car_idx,
+                car.user_name,
+                car.car_number,
+                car.car_number,
+                class.car_class_id,
+                class.short_name,
+                class.short_name,
+                class.car_class_id,
+                class.short_name,
+                class.lap_time_secs,
+                class.color,
+                car.irating,
+                car.lic_string,
+            ));
+        }
+
+        yaml.push_str(
+            "SplitTimeInfo:
+ Sectors:
+ - SectorNum: 0
+   SectorStartPct: 0.000000
+ - SectorNum: 1
+   SectorStartPct: 0.333333
+ - SectorNum: 2
+   SectorStartPct: 0.666667
+",
+        );
+        yaml
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use eyre::Result;
+use log::error;
+use simetry::iracing::Value;
+use tokio::sync::Notify;
+use yaml_rust::{Yaml, YamlLoader};
+
+use super::replay_source::{ReplayClock, ReplaySpeed};
+use super::synthetic_session::{
+    AIR_TEMP, SKIES_PARTLY_CLOUDY, SyntheticConfig, SyntheticSession, TRACK_WETNESS_DRY, WIND_DIR,
+    WIND_VEL,
+};
+use super::telemetry_source::TelemetrySource;
+use super::telemetry_tick::TelemetryTick;
+
+const SYNTHETIC_TICK_RATE: f64 = 60.0;
+
+pub struct SyntheticTick {
+    values: HashMap<&'static str, Vec<Value>>,
+    session_info: Arc<Yaml>,
+    session_info_update: i32,
+}
+
+impl SyntheticTick {
+    fn from_session(session: &SyntheticSession, session_info: Arc<Yaml>) -> Self {
+        let mut values = HashMap::new();
+        let mut scalar = |name: &'static str, value: Value| {
+            values.insert(name, vec![value]);
+        };
+
+        let player = session.player();
+        let inputs = session.player_inputs;
+        let player_laps_completed = player.map(|car| car.laps_completed).unwrap_or(0);
+        let player_lap = player.map(|car| car.laps_started()).unwrap_or(0);
+        let player_best_lap_time = player.map(|car| car.best_lap_time).unwrap_or(-1.0);
+        let player_last_lap_time = player.map(|car| car.last_lap_time).unwrap_or(-1.0);
+
+        scalar("SessionTick", Value::Int(session.session_tick));
+        scalar("IsOnTrack", Value::Bool(true));
+        scalar("IsOnTrackCar", Value::Bool(true));
+        // a restarted session gets the next seed
+        scalar("SessionUniqueID", Value::Int(session.config.seed as i32));
+        scalar("SessionNum", Value::Int(0));
+        scalar(
+            "SessionTimeTotal",
+            Value::Double(session.config.session_time_secs),
+        );
+        scalar(
+            "SessionLapsTotal",
+            Value::Int(match session.config.session_laps {
+                0 => 32767,
+                laps => laps as i32,
+            }),
+        );
+        scalar(
+            "PlayerCarMyIncidentCount",
+            Value::Int(session.player_incidents),
+        );
+        scalar(
+            "PlayerCarSLShiftRPM",
+            Value::Float(session.gear_shift_rpm()),
+        );
+        scalar(
+            "PlayerCarSLBlinkRPM",
+            Value::Float(session.gear_blink_rpm()),
+        );
+        scalar("SessionTime", Value::Double(session.session_time));
+        scalar("PlayerCarIdx", Value::Int(session.player_car_idx as i32));
+        scalar("CamCarIdx", Value::Int(session.player_car_idx as i32));
+        scalar(
+            "PlayerCarClass",
+            Value::Int(session.config.classes[session.config.player_class].car_class_id as i32),
+        );
+        scalar("Lap", Value::Int(player_lap));
+        scalar("RaceLaps", Value::Int(player_laps_completed));
+        scalar(
+            "LapCurrentLapTime",
+            Value::Float(session.player_lap_current_time),
+        );
+        scalar(
+            "LapDist",
+            Value::Float(
+                player.map(|car| car.lap_dist_pct).unwrap_or(0.0) * session.track_length(),
+            ),
+        );
+        scalar(
+            "LapDeltaToSessionLastlLap",
+            Value::Float(session.player_delta_to(player_last_lap_time)),
+        );
+        scalar(
+            "LapDeltaToBestLap",
+            Value::Float(session.player_delta_to(player_best_lap_time)),
+        );
+        scalar(
+            "LapDeltaToOptimalLap",
+            Value::Float(session.player_delta_to(player_best_lap_time)),
+        );
+        scalar(
+            "SessionTimeRemain",
+            Value::Double(session.session_time_remaining()),
+        );
+        scalar(
+            "SessionLapsRemainEx",
+            Value::Int(session.session_laps_remaining()),
+        );
+        scalar("Gear", Value::Int(inputs.gear));
+        scalar(
+            "Speed",
+            Value::Float(player.map(|car| car.speed).unwrap_or(0.0)),
+        );
+        scalar("RPM", Value::Float(inputs.rpm));
+        scalar("Brake", Value::Float(inputs.brake));
+        scalar("Throttle", Value::Float(inputs.throttle));
+        scalar("BrakeABSactive", Value::Bool(inputs.abs_active));
+        scalar("SteeringWheelAngle", Value::Float(inputs.steering));
+        scalar("CarLeftRight", Value::BitField(session.car_left_right()));
+        scalar(
+            "SessionFlags",
+            Value::BitField(session.session_flags().bits()),
+        );
+        scalar("LapLastLapTime", Value::Float(player_last_lap_time));
+        scalar(
+            "OnPitRoad",
+            Value::Bool(player.is_some_and(|car| car.is_on_pit_road())),
+        );
+        scalar("FuelLevel", Value::Float(session.player_fuel));
+        scalar(
+            "FuelLevelPct",
+            Value::Float(session.player_fuel / session.config.fuel_capacity.max(1.0)),
+        );
+        scalar(
+            "FuelUsePerHour",
+            Value::Float(session.player_fuel_use_per_hour()),
+        );
+        scalar("AirTemp", Value::Float(AIR_TEMP));
+        scalar("TrackTempCrew", Value::Float(session.track_temp()));
+        scalar("TrackWetness", Value::Int(TRACK_WETNESS_DRY));
+        scalar("Precipitation", Value::Float(0.0));
+        scalar("WindVel", Value::Float(WIND_VEL));
+        scalar("WindDir", Value::Float(WIND_DIR));
+        scalar("Skies", Value::Int(SKIES_PARTLY_CLOUDY));
+        scalar("FogLevel", Value::Float(0.0));
+
+        let car_count = session.car_count();
+        let mut lap_dist_pct = vec![Value::Float(-1.0); car_count];
+        let mut lap_completed = vec![Value::Int(-1); car_count];
+        let mut lap = vec![Value::Int(-1); car_count];
+        let mut est_time = vec![Value::Float(0.0); car_count];
+        let mut best_lap_time = vec![Value::Float(-1.0); car_count];
+        let mut last_lap_time = vec![Value::Float(-1.0); car_count];
+        let mut track_surface = vec![Value::Int(-1); car_count];
+        let mut session_flags = vec![Value::BitField(0); car_count];
+        for car in session.cars.iter() {
+            let idx = car.car_idx;
+            lap_dist_pct[idx] = Value::Float(car.lap_dist_pct);
+            lap_completed[idx] = Value::Int(car.laps_completed);
+            lap[idx] = Value::Int(car.laps_started());
+            est_time[idx] = Value::Float(session.estimated_time(car));
+            best_lap_time[idx] = Value::Float(car.best_lap_time);
+            last_lap_time[idx] = Value::Float(car.last_lap_time);
+            track_surface[idx] = Value::Int(car.track_surface());
+            session_flags[idx] = Value::BitField(session.car_session_flags(car).bits());
+        }
+        values.insert("CarIdxLapDistPct", lap_dist_pct);
+        values.insert("CarIdxLapCompleted", lap_completed);
+        values.insert("CarIdxLap", lap);
+        values.insert("CarIdxEstTime", est_time);
+        values.insert("CarIdxBestLapTime", best_lap_time);
+        values.insert("CarIdxLastLapTime", last_lap_time);
+        values.insert("CarIdxTrackSurface", track_surface);
+        values.insert("CarIdxSessionFlags", session_flags);
+
+        Self {
+            values,
+            session_info,
+            session_info_update: session.session_info_update,
+        }
+    }
+}
+
+impl TelemetryTick for SyntheticTick {
+    fn read_value(&self, name: &str, idx: usize) -> Option<Value> {
+        self.values.get(name)?.get(idx).cloned()
+    }
+
+    fn value_count(&self, name: &str) -> Option<usize> {
+        self.values.get(name).map(|values| values.len())
+    }
+
+    fn session_info(&self) -> &Yaml {
+        &self.session_info
+    }
+
+    fn session_info_update(&self) -> i32 {
+        self.session_info_update
+    }
+}
+
+/// Plays a generated session instead of the sim, used for demo mode and overlay development
+pub struct SyntheticSource {
+    session: SyntheticSession,
+    clock: ReplayClock,
+    session_info: Arc<Yaml>,
+    ticks: u64,
+}
+
+impl SyntheticSource {
+    pub fn new(config: SyntheticConfig, speed: ReplaySpeed) -> Self {
+        Self {
+            session: SyntheticSession::new(config),
+            clock: ReplayClock::new(speed),
+            session_info: Arc::new(Yaml::Null),
+            ticks: 0,
+        }
+    }
+
+    /// Loads the session config from a JSON file, missing fields fall back to the defaults
+    pub fn load_config(path: &std::path::Path) -> Result<SyntheticConfig> {
+        let file = std::fs::File::open(path)?;
+        Ok(serde_json::from_reader(file)?)
+    }
+
+    pub fn stepper(&self) -> Arc<Notify> {
+        self.clock.stepper()
+    }
+
+    fn update_session_info(&mut self) {
+        let yaml = self.session.session_info_yaml();
+        match YamlLoader::load_from_str(&yaml) {
+            Ok(mut docs) if !docs.is_empty() => self.session_info = Arc::new(docs.remove(0)),
+            Ok(_) => error!("Synthetic session info is empty"),
+            Err(err) => error!("Failed to parse synthetic session info: {:?}", err),
+        }
+    }
+}
+
+impl TelemetrySource for SyntheticSource {
+    type Tick = SyntheticTick;
+
+    async fn next_tick(&mut self) -> Option<SyntheticTick> {
+        self.clock
+            .wait(Duration::from_secs_f64(
+                self.ticks as f64 / SYNTHETIC_TICK_RATE,
+            ))
+            .await;
+        self.ticks += 1;
+        self.session.step(1.0 / SYNTHETIC_TICK_RATE);
+        if self.session.take_session_info_update() {
+            self.update_session_info();
+        }
+        Some(SyntheticTick::from_session(
+            &self.session,
+            self.session_info.clone(),
+        ))
+    }
+}
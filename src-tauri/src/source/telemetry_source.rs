@@ -0,0 +1,10 @@
+use super::telemetry_tick::TelemetryTick;
+
+/// Anything that can feed ticks into the session pipeline: the live sim, a recording or a generator
+pub trait TelemetrySource {
+    type Tick: TelemetryTick;
+
+    /// Returns the next tick or `None` once the source is disconnected or exhausted
+    #[allow(async_fn_in_trait)]
+    async fn next_tick(&mut self) -> Option<Self::Tick>;
+}
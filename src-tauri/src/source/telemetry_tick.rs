@@ -0,0 +1,97 @@
+use simetry::iracing::Value;
+use yaml_rust::Yaml;
+
+/// A single sample of sim telemetry, as consumed by `SessionData::process_tick`
+pub trait TelemetryTick {
+    /// Reads entry `idx` of the variable `name`
+    fn read_value(&self, name: &str, idx: usize) -> Option<Value>;
+
+    /// Number of entries of the variable `name` (1 for scalars, car count for `CarIdx*` arrays)
+    fn value_count(&self, name: &str) -> Option<usize>;
+
+    fn session_info(&self) -> &Yaml;
+
+    fn session_info_update(&self) -> i32;
+
+    fn read_name<T: TickValue>(&self, name: &str) -> Option<T>
+    where
+        Self: Sized,
+    {
+        T::read(self, name)
+    }
+}
+
+pub trait TickValue: Sized {
+    fn read<T: TelemetryTick + ?Sized>(tick: &T, name: &str) -> Option<Self>;
+}
+
+pub trait ScalarValue: Sized {
+    fn from_value(value: Value) -> Option<Self>;
+}
+
+impl ScalarValue for bool {
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::Char(value) => Some(value != 0),
+            Value::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl ScalarValue for i32 {
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::Char(value) => Some(value as i32),
+            Value::Int(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl ScalarValue for u32 {
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::BitField(value) => Some(value),
+            // some enums (e.g. CarLeftRight) are declared as bitfields, others as ints
+            Value::Int(value) => Some(value as u32),
+            _ => None,
+        }
+    }
+}
+
+impl ScalarValue for f32 {
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::Float(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl ScalarValue for f64 {
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::Float(value) => Some(value as f64),
+            Value::Double(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl<V: ScalarValue> TickValue for V {
+    fn read<T: TelemetryTick + ?Sized>(tick: &T, name: &str) -> Option<Self> {
+        V::from_value(tick.read_value(name, 0)?)
+    }
+}
+
+impl<V: ScalarValue> TickValue for Vec<V> {
+    fn read<T: TelemetryTick + ?Sized>(tick: &T, name: &str) -> Option<Self> {
+        let count = tick.value_count(name)?;
+        Some(
+            (0..count)
+                .filter_map(|idx| tick.read_value(name, idx).and_then(V::from_value))
+                .collect(),
+        )
+    }
+}
@@ -1,8 +1,11 @@
 pub mod active;
+pub mod class_standings;
 pub mod current_time;
 pub mod delta_best_time;
 pub mod delta_last_time;
 pub mod fastest_lap;
+pub mod flags;
+pub mod fuel;
 pub mod gap_next;
 pub mod gap_prev;
 pub mod gear;
@@ -16,19 +19,26 @@ pub mod laps_total;
 pub mod player_car_class;
 pub mod player_lap_times;
 pub mod position;
+pub mod position_history;
 pub mod positions_total;
 pub mod proximity;
+pub mod qualify_standings;
 pub mod race_laps;
+pub mod rating_projection;
 pub mod relative;
 pub mod rpm;
+pub mod sector_times;
 pub mod session_state;
 pub mod session_time;
 pub mod session_time_total;
 pub mod session_type;
+pub mod spectating;
 pub mod speed;
+pub mod spotter;
 pub mod standings;
 pub mod strength_of_field;
 pub mod telemetry_graph;
 pub mod telemetry_reference;
 pub mod track_id;
 pub mod track_map;
+pub mod weather;
@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Serializer};
+use specta::Type;
+
+use crate::emitter::emittable_event::{EmittableEvent, EmittableValue};
+use crate::session::driver::Driver;
+use crate::session::session_data::SessionData;
+use crate::settings::overlays::standings::StandingsOverlaySettings;
+use crate::telemetry::standings::{StandingsDriver, select_drivers};
+use crate::util::format_laptime::format_laptime;
+use crate::util::get_strength_of_field::get_class_strength_of_field;
+use crate::util::settings_helper::get_settings;
+
+#[derive(Default, Type, PartialEq)]
+pub struct ClassStandingsBlock {
+    car_class_id: u32,
+    class_name: String,
+    class_color: u32,
+    strength_of_field: u32,
+    drivers_count: u32,
+    leader: String,
+    best_lap: String,
+    is_player_class: bool,
+    drivers: Vec<StandingsDriver>,
+}
+
+impl Serialize for ClassStandingsBlock {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(9))?;
+        map.serialize_entry("car_class_id", &self.car_class_id)?;
+        map.serialize_entry("class_name", &self.class_name)?;
+        map.serialize_entry("class_color", &self.class_color)?;
+        map.serialize_entry("strength_of_field", &self.strength_of_field)?;
+        map.serialize_entry("drivers_count", &self.drivers_count)?;
+        map.serialize_entry("leader", &self.leader)?;
+        map.serialize_entry("best_lap", &self.best_lap)?;
+        map.serialize_entry("is_player_class", &self.is_player_class)?;
+        map.serialize_entry("drivers", &self.drivers)?;
+        map.end()
+    }
+}
+
+/// Standings of every class, fastest class first
+#[derive(Default, Type, PartialEq)]
+pub struct ClassStandings(Vec<ClassStandingsBlock>);
+
+impl Serialize for ClassStandings {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl EmittableEvent for ClassStandings {
+    fn is_ready(&self, session: &SessionData) -> bool {
+        session.active
+            && session.position != 0
+            && !session.drivers.is_empty()
+            && session.processed_slow
+    }
+
+    fn get_event(&self, session: &SessionData) -> Box<dyn EmittableValue> {
+        let settings = get_settings::<StandingsOverlaySettings>("standings");
+        let rows_per_class = settings.rows_per_class.max(1) as usize;
+        let top_drivers_count = (settings.top_drivers as usize).min(rows_per_class - 1);
+
+        let mut classes: BTreeMap<u32, Vec<Driver>> = BTreeMap::new();
+        for driver in session.drivers.values() {
+            classes
+                .entry(driver.car_class_id)
+                .or_default()
+                .push(driver.clone());
+        }
+
+        let mut blocks = classes
+            .into_values()
+            .filter(|drivers| {
+                settings.shown_classes.is_empty()
+                    || settings
+                        .shown_classes
+                        .iter()
+                        .any(|name| name.eq_ignore_ascii_case(&drivers[0].car_class_name))
+            })
+            .map(|mut drivers| {
+                drivers.sort_by_key(|driver| driver.position);
+                let first = &drivers[0];
+                let is_player_class = first.car_class_id == session.player_car_class;
+                let leader = drivers
+                    .iter()
+                    .find(|driver| driver.is_class_leader)
+                    .map(|driver| driver.team_name.clone())
+                    .unwrap_or_default();
+                let best_lap = drivers
+                    .iter()
+                    .map(|driver| driver.best_lap_time)
+                    .filter(|best_lap_time| best_lap_time.is_positive())
+                    .min_by(|a, b| a.partial_cmp(b).unwrap());
+                let mut block = ClassStandingsBlock {
+                    car_class_id: first.car_class_id,
+                    class_name: first.car_class_name.clone(),
+                    class_color: first.car_class_color,
+                    strength_of_field: get_class_strength_of_field(session, first.car_class_id),
+                    drivers_count: drivers.len() as u32,
+                    leader,
+                    best_lap: format_laptime(best_lap.unwrap_or_default()),
+                    is_player_class,
+                    drivers: Vec::new(),
+                };
+                let est_lap_time = first.car_class_est_lap_time;
+
+                let player_position = is_player_class.then_some(session.class_position as usize);
+                let (selected_drivers, split_after) =
+                    select_drivers(drivers, player_position, rows_per_class, top_drivers_count);
+                block.drivers = selected_drivers
+                    .iter()
+                    .map(|driver| {
+                        StandingsDriver::new(
+                            driver,
+                            session,
+                            (driver.class_position as usize == top_drivers_count) && split_after,
+                        )
+                    })
+                    .collect();
+                (est_lap_time, block)
+            })
+            .collect::<Vec<_>>();
+        blocks.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        Box::new(ClassStandings(
+            blocks.into_iter().map(|(_, block)| block).collect(),
+        ))
+    }
+
+    fn is_forced(&self) -> bool {
+        true
+    }
+}
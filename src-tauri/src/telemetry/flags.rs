@@ -0,0 +1,45 @@
+use serde::{Serialize, Serializer};
+use specta::Type;
+
+use crate::emitter::emittable_event::{EmittableEvent, EmittableValue};
+use crate::session::session_data::SessionData;
+
+/// Only changes on flag transitions, so every emit is a transition
+#[derive(Default, Type, PartialEq)]
+pub struct Flags {
+    flag: String,
+    previous_flag: String,
+    session_flags: Vec<String>,
+    player_flags: Vec<String>,
+}
+
+impl Serialize for Flags {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(4))?;
+        map.serialize_entry("flag", &self.flag)?;
+        map.serialize_entry("previous_flag", &self.previous_flag)?;
+        map.serialize_entry("session_flags", &self.session_flags)?;
+        map.serialize_entry("player_flags", &self.player_flags)?;
+        map.end()
+    }
+}
+
+impl EmittableEvent for Flags {
+    fn get_event(&self, session: &SessionData) -> Box<dyn EmittableValue> {
+        let player_flags = session
+            .player_car_id
+            .and_then(|car_id| session.drivers.get(&car_id))
+            .map(|driver| driver.session_flags.names())
+            .unwrap_or_default();
+        Box::new(Flags {
+            flag: session.flag.flag.to_string(),
+            previous_flag: session.flag.previous_flag.to_string(),
+            session_flags: session.session_flags.names(),
+            player_flags,
+        })
+    }
+}
@@ -0,0 +1,94 @@
+use serde::{Serialize, Serializer};
+use specta::Type;
+
+use crate::emitter::emittable_event::{EmittableEvent, EmittableValue};
+use crate::session::session_data::SessionData;
+use crate::settings::overlays::fuel::FuelOverlaySettings;
+use crate::util::get_laps_to_finish::get_laps_to_finish;
+use crate::util::settings_helper::get_settings;
+
+fn format_fuel(value: Option<f32>, precision: usize) -> String {
+    match value {
+        Some(value) if value.is_finite() => format!("{:.*}", precision, value),
+        _ => "–".to_string(),
+    }
+}
+
+#[derive(Default, Type, PartialEq)]
+pub struct Fuel {
+    fuel_level: String,
+    fuel_level_pct: u32,
+    last_lap_usage: String,
+    average_usage: String,
+    laps_remaining: String,
+    laps_to_finish: String,
+    fuel_to_finish: String,
+    fuel_to_add: String,
+    pit_stops_needed: u32,
+    is_enough: bool,
+}
+
+impl Serialize for Fuel {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(10))?;
+        map.serialize_entry("fuel_level", &self.fuel_level)?;
+        map.serialize_entry("fuel_level_pct", &self.fuel_level_pct)?;
+        map.serialize_entry("last_lap_usage", &self.last_lap_usage)?;
+        map.serialize_entry("average_usage", &self.average_usage)?;
+        map.serialize_entry("laps_remaining", &self.laps_remaining)?;
+        map.serialize_entry("laps_to_finish", &self.laps_to_finish)?;
+        map.serialize_entry("fuel_to_finish", &self.fuel_to_finish)?;
+        map.serialize_entry("fuel_to_add", &self.fuel_to_add)?;
+        map.serialize_entry("pit_stops_needed", &self.pit_stops_needed)?;
+        map.serialize_entry("is_enough", &self.is_enough)?;
+        map.end()
+    }
+}
+
+impl EmittableEvent for Fuel {
+    fn is_ready(&self, session: &SessionData) -> bool {
+        session.active && session.fuel.fuel_level_pct > 0.0
+    }
+
+    fn get_event(&self, session: &SessionData) -> Box<dyn EmittableValue> {
+        let settings = get_settings::<FuelOverlaySettings>("fuel");
+        let fuel = &session.fuel;
+        let average_usage = fuel.average_green_lap_usage(settings.average_laps as usize);
+        let laps_remaining = average_usage.map(|usage| fuel.fuel_level / usage);
+        let laps_to_finish = get_laps_to_finish(session);
+        let fuel_to_finish = match (average_usage, laps_to_finish) {
+            (Some(usage), Some(laps)) => Some((laps + settings.safety_margin_laps) * usage),
+            _ => None,
+        };
+        let fuel_to_add =
+            fuel_to_finish.map(|fuel_to_finish| (fuel_to_finish - fuel.fuel_level).max(0.0));
+
+        let driver_info = &session.session_info.driver_info;
+        let tank_capacity = driver_info
+            .driver_car_fuel_max_ltr
+            .map(|max_ltr| max_ltr * driver_info.driver_car_max_fuel_pct.unwrap_or(1.0))
+            .filter(|capacity| *capacity > 0.0);
+        let pit_stops_needed = match (fuel_to_add, tank_capacity) {
+            (Some(fuel_to_add), Some(capacity)) => (fuel_to_add / capacity).ceil() as u32,
+            (Some(fuel_to_add), None) if fuel_to_add > 0.0 => 1,
+            _ => 0,
+        };
+
+        Box::new(Fuel {
+            fuel_level: format_fuel(Some(fuel.fuel_level), 1),
+            fuel_level_pct: (fuel.fuel_level_pct * 100.0).round() as u32,
+            last_lap_usage: format_fuel(fuel.last_lap().map(|lap| lap.used), 2),
+            average_usage: format_fuel(average_usage, 2),
+            laps_remaining: format_fuel(laps_remaining, 1),
+            laps_to_finish: format_fuel(laps_to_finish, 1),
+            fuel_to_finish: format_fuel(fuel_to_finish, 1),
+            fuel_to_add: format_fuel(fuel_to_add, 1),
+            pit_stops_needed,
+            is_enough: fuel_to_add.is_some_and(|fuel_to_add| fuel_to_add == 0.0),
+        })
+    }
+}
@@ -0,0 +1,92 @@
+use serde::{Serialize, Serializer};
+use specta::Type;
+
+use crate::emitter::emittable_event::{EmittableEvent, EmittableValue};
+use crate::session::driver::Driver;
+use crate::session::session_data::SessionData;
+use crate::session::session_type::SessionType;
+
+#[derive(Default, Type, PartialEq)]
+pub struct PositionHistoryDriver {
+    car_id: u32,
+    user_name: String,
+    car_number: String,
+    is_player: bool,
+    start_position: Option<u32>,
+    position: u32,
+    positions_gained: i32,
+    /// Class position at the end of every completed lap
+    lap_positions: Vec<u32>,
+}
+
+impl Serialize for PositionHistoryDriver {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(8))?;
+        map.serialize_entry("car_id", &self.car_id)?;
+        map.serialize_entry("user_name", &self.user_name)?;
+        map.serialize_entry("car_number", &self.car_number)?;
+        map.serialize_entry("is_player", &self.is_player)?;
+        map.serialize_entry("start_position", &self.start_position)?;
+        map.serialize_entry("position", &self.position)?;
+        map.serialize_entry("positions_gained", &self.positions_gained)?;
+        map.serialize_entry("lap_positions", &self.lap_positions)?;
+        map.end()
+    }
+}
+
+impl PositionHistoryDriver {
+    fn new(driver: &Driver) -> Self {
+        Self {
+            car_id: driver.car_id,
+            user_name: driver.user_name.clone(),
+            car_number: driver.car_number.clone(),
+            is_player: driver.is_focus,
+            start_position: driver.position_history.start_position,
+            position: driver.class_position,
+            positions_gained: driver
+                .position_history
+                .positions_gained(driver.class_position),
+            lap_positions: driver.position_history.laps.clone(),
+        }
+    }
+}
+
+/// Lap by lap positions of the player class for the position chart
+#[derive(Default, Type)]
+pub struct PositionHistory(Vec<PositionHistoryDriver>);
+
+impl Serialize for PositionHistory {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl EmittableEvent for PositionHistory {
+    fn is_ready(&self, session: &SessionData) -> bool {
+        session.active
+            && session.session_type == SessionType::Race
+            && !session.drivers.is_empty()
+            && session.processed_slow
+    }
+
+    fn get_event(&self, session: &SessionData) -> Box<dyn EmittableValue> {
+        let mut drivers = session
+            .drivers
+            .values()
+            .filter(|driver| driver.car_class_id == session.player_car_class)
+            .collect::<Vec<&Driver>>();
+        drivers.sort_by_key(|driver| driver.class_position);
+        let drivers = drivers
+            .into_iter()
+            .map(PositionHistoryDriver::new)
+            .collect::<Vec<PositionHistoryDriver>>();
+        Box::new(drivers)
+    }
+}
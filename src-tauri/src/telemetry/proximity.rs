@@ -8,6 +8,9 @@ use crate::session::session_data::SessionData;
 pub struct Proximity {
     is_left: bool,
     is_right: bool,
+    state: String,
+    overlap_pct: u32,
+    is_ahead: bool,
 }
 
 impl Serialize for Proximity {
@@ -16,9 +19,12 @@ impl Serialize for Proximity {
         S: Serializer,
     {
         use serde::ser::SerializeMap;
-        let mut map = serializer.serialize_map(Some(2))?;
+        let mut map = serializer.serialize_map(Some(5))?;
         map.serialize_entry("is_left", &self.is_left)?;
         map.serialize_entry("is_right", &self.is_right)?;
+        map.serialize_entry("state", &self.state)?;
+        map.serialize_entry("overlap_pct", &self.overlap_pct)?;
+        map.serialize_entry("is_ahead", &self.is_ahead)?;
         map.end()
     }
 }
@@ -28,6 +34,9 @@ impl EmittableEvent for Proximity {
         let proximity = Proximity {
             is_left: session.is_left,
             is_right: session.is_right,
+            state: session.spotter.state.to_string(),
+            overlap_pct: (session.spotter.overlap * 100.0).round() as u32,
+            is_ahead: session.spotter.is_ahead,
         };
         Box::new(proximity)
     }
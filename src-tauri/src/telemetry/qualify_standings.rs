@@ -0,0 +1,180 @@
+use serde::{Serialize, Serializer};
+use specta::Type;
+
+use crate::emitter::emittable_event::{EmittableEvent, EmittableValue};
+use crate::session::driver::Driver;
+use crate::session::session_data::SessionData;
+use crate::session::session_type::SessionType;
+use crate::settings::overlays::standings::StandingsOverlaySettings;
+use crate::telemetry::standings::select_drivers;
+use crate::util::format_irating::format_irating;
+use crate::util::format_laptime::format_laptime;
+use crate::util::settings_helper::get_settings;
+use crate::util::signed_duration::SignedDuration;
+
+#[derive(Default, Type, PartialEq)]
+pub struct QualifyStandingsDriver {
+    car_id: u32,
+    position: u32,
+    user_name: String,
+    car_number: String,
+    irating: String,
+    license: String,
+    best_lap: String,
+    pole_gap: String,
+    laps_run: u32,
+    is_on_hot_lap: bool,
+    // position the car would take if it finished the current lap at its best sector pace
+    provisional_position: Option<u32>,
+    is_player: bool,
+    is_in_pits: bool,
+    split_after: bool,
+}
+
+impl Serialize for QualifyStandingsDriver {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(14))?;
+        map.serialize_entry("car_id", &self.car_id)?;
+        map.serialize_entry("position", &self.position)?;
+        map.serialize_entry("user_name", &self.user_name)?;
+        map.serialize_entry("car_number", &self.car_number)?;
+        map.serialize_entry("irating", &self.irating)?;
+        map.serialize_entry("license", &self.license)?;
+        map.serialize_entry("best_lap", &self.best_lap)?;
+        map.serialize_entry("pole_gap", &self.pole_gap)?;
+        map.serialize_entry("laps_run", &self.laps_run)?;
+        map.serialize_entry("is_on_hot_lap", &self.is_on_hot_lap)?;
+        map.serialize_entry("provisional_position", &self.provisional_position)?;
+        map.serialize_entry("is_player", &self.is_player)?;
+        map.serialize_entry("is_in_pits", &self.is_in_pits)?;
+        map.serialize_entry("split_after", &self.split_after)?;
+        map.end()
+    }
+}
+
+/// Best lap standings of the player class for qualifying and practice sessions
+#[derive(Default, Type, PartialEq)]
+pub struct QualifyStandings(Vec<QualifyStandingsDriver>);
+
+impl Serialize for QualifyStandings {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+fn format_pole_gap(best_lap_time: SignedDuration, pole: Option<SignedDuration>) -> String {
+    match pole {
+        Some(pole) if best_lap_time.is_positive() && best_lap_time > pole => {
+            format!("+{:.3}", (best_lap_time - pole).as_secs_f32())
+        }
+        _ => "-".to_string(),
+    }
+}
+
+/// A timed lap started from the track, out laps and laps through the pits don't count
+fn is_on_hot_lap(driver: &Driver) -> bool {
+    driver.lap_history.last_lap().is_some()
+        && !driver.is_in_pits
+        && !driver.is_off_world
+        && !driver.lap_history.current_lap_in_pits()
+}
+
+impl EmittableEvent for QualifyStandings {
+    fn is_ready(&self, session: &SessionData) -> bool {
+        session.active
+            && matches!(
+                session.session_type,
+                SessionType::Qualify | SessionType::Practice
+            )
+            && !session.drivers.is_empty()
+            && session.processed_slow
+    }
+
+    fn get_event(&self, session: &SessionData) -> Box<dyn EmittableValue> {
+        let settings = get_settings::<StandingsOverlaySettings>("standings");
+        let top_drivers_count = settings.top_drivers as usize;
+
+        let mut drivers = session
+            .drivers
+            .values()
+            .filter(|driver| driver.car_class_id == session.player_car_class)
+            .cloned()
+            .collect::<Vec<Driver>>();
+        drivers.sort_by_key(|driver| driver.position);
+        let best_lap_times = drivers
+            .iter()
+            .map(|driver| (driver.car_id, driver.best_lap_time))
+            .filter(|(_, best_lap_time)| best_lap_time.is_positive())
+            .collect::<Vec<(u32, SignedDuration)>>();
+        let pole = best_lap_times
+            .iter()
+            .map(|(_, best_lap_time)| *best_lap_time)
+            .min_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let (selected_drivers, split_after) = select_drivers(
+            drivers,
+            Some(session.class_position as usize),
+            settings.max_drivers as usize,
+            top_drivers_count,
+        );
+
+        let drivers = selected_drivers
+            .iter()
+            .map(|driver| {
+                let is_on_hot_lap = is_on_hot_lap(driver);
+                let provisional_position = driver
+                    .sector_timing
+                    .projected_lap_time(session.session_time)
+                    .filter(|projected| {
+                        is_on_hot_lap
+                            && (!driver.best_lap_time.is_positive()
+                                || *projected < driver.best_lap_time)
+                    })
+                    .map(|projected| {
+                        best_lap_times
+                            .iter()
+                            .filter(|(car_id, best_lap_time)| {
+                                *car_id != driver.car_id && *best_lap_time < projected
+                            })
+                            .count() as u32
+                            + 1
+                    });
+                let laps_run = session
+                    .results_positions_mapping
+                    .get(&driver.car_id)
+                    .map(|idx| session.results_positions[*idx].laps_complete)
+                    .unwrap_or(0)
+                    .max(driver.laps_completed);
+                QualifyStandingsDriver {
+                    car_id: driver.car_id,
+                    position: driver.class_position,
+                    user_name: driver.team_name.clone(),
+                    car_number: driver.car_number.clone(),
+                    irating: format_irating(driver.irating),
+                    license: driver.lic_string.clone(),
+                    best_lap: format_laptime(driver.best_lap_time),
+                    pole_gap: format_pole_gap(driver.best_lap_time, pole),
+                    laps_run,
+                    is_on_hot_lap,
+                    provisional_position,
+                    is_player: driver.is_focus,
+                    is_in_pits: driver.is_in_pits,
+                    split_after: (driver.class_position as usize == top_drivers_count)
+                        && split_after,
+                }
+            })
+            .collect::<Vec<QualifyStandingsDriver>>();
+        Box::new(QualifyStandings(drivers))
+    }
+
+    fn is_forced(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,87 @@
+use serde::{Serialize, Serializer};
+use specta::Type;
+
+use crate::emitter::emittable_event::{EmittableEvent, EmittableValue};
+use crate::session::rating_projection::safety_rating_change;
+use crate::session::session_data::SessionData;
+use crate::session::session_type::SessionType;
+
+/// Player's ratings if the race finished in the current order
+#[derive(Default, Type, PartialEq)]
+pub struct RatingProjection {
+    irating: u32,
+    irating_change: i32,
+    projected_irating: u32,
+    safety_rating: f32,
+    safety_rating_change: f32,
+    projected_safety_rating: f32,
+    incidents: u32,
+    corners: u32,
+}
+
+impl Serialize for RatingProjection {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(8))?;
+        map.serialize_entry("irating", &self.irating)?;
+        map.serialize_entry("irating_change", &self.irating_change)?;
+        map.serialize_entry("projected_irating", &self.projected_irating)?;
+        map.serialize_entry("safety_rating", &self.safety_rating)?;
+        map.serialize_entry("safety_rating_change", &self.safety_rating_change)?;
+        map.serialize_entry("projected_safety_rating", &self.projected_safety_rating)?;
+        map.serialize_entry("incidents", &self.incidents)?;
+        map.serialize_entry("corners", &self.corners)?;
+        map.end()
+    }
+}
+
+fn round_rating(value: f32) -> f32 {
+    (value * 100.0).round() / 100.0
+}
+
+impl EmittableEvent for RatingProjection {
+    fn is_ready(&self, session: &SessionData) -> bool {
+        session.active
+            && session.session_type == SessionType::Race
+            && session
+                .player_car_id
+                .is_some_and(|car_id| session.drivers.contains_key(&car_id))
+            && session.processed_slow
+    }
+
+    fn get_event(&self, session: &SessionData) -> Box<dyn EmittableValue> {
+        let player = &session.drivers[&session.player_car_id.unwrap()];
+        // license is formatted like "A 3.45"
+        let safety_rating = player
+            .lic_string
+            .split_whitespace()
+            .nth(1)
+            .and_then(|value| value.parse::<f32>().ok())
+            .unwrap_or(0.0);
+        let corners_per_lap = session
+            .session_info
+            .weekend_info
+            .track_num_turns
+            .unwrap_or(0);
+        // the grid is behind the line, only count completed laps
+        let corners = player.laps_completed * corners_per_lap;
+        let safety_rating_change =
+            round_rating(safety_rating_change(session.incidents, corners as f32));
+
+        Box::new(RatingProjection {
+            irating: player.irating,
+            irating_change: player.irating_change,
+            projected_irating: player.irating.saturating_add_signed(player.irating_change),
+            safety_rating,
+            safety_rating_change,
+            projected_safety_rating: round_rating(
+                (safety_rating + safety_rating_change).clamp(0.0, 4.99),
+            ),
+            incidents: session.incidents,
+            corners,
+        })
+    }
+}
@@ -4,7 +4,7 @@ use specta::Type;
 use crate::emitter::emittable_event::{EmittableEvent, EmittableValue};
 use crate::session::driver::Driver;
 use crate::session::session_data::SessionData;
-use crate::util::format_irating::format_irating;
+use crate::util::format_irating::{format_irating, format_irating_change};
 use crate::util::get_relative_gap::get_relative_gap;
 
 // TODO: Make this configurable
@@ -18,6 +18,7 @@ pub struct RelativeDriver {
     user_name: String,
     car_number: String,
     irating: String,
+    irating_change: String,
     license: String,
     player_relative_gap: String,
     is_player: bool,
@@ -28,6 +29,8 @@ pub struct RelativeDriver {
     car_class_color: u32,
     is_ahead: bool,
     is_behind: bool,
+    laps_since_pit: u32,
+    pit_stops: u32,
 }
 
 impl Serialize for RelativeDriver {
@@ -36,12 +39,13 @@ impl Serialize for RelativeDriver {
         S: Serializer,
     {
         use serde::ser::SerializeMap;
-        let mut map = serializer.serialize_map(Some(15))?;
+        let mut map = serializer.serialize_map(Some(18))?;
         map.serialize_entry("car_id", &self.car_id)?;
         map.serialize_entry("position", &self.position)?;
         map.serialize_entry("user_name", &self.user_name)?;
         map.serialize_entry("car_number", &self.car_number)?;
         map.serialize_entry("irating", &self.irating)?;
+        map.serialize_entry("irating_change", &self.irating_change)?;
         map.serialize_entry("license", &self.license)?;
         map.serialize_entry("player_relative_gap", &self.player_relative_gap)?;
         map.serialize_entry("is_player", &self.is_player)?;
@@ -52,6 +56,8 @@ impl Serialize for RelativeDriver {
         map.serialize_entry("car_class_color", &self.car_class_color)?;
         map.serialize_entry("is_ahead", &self.is_ahead)?;
         map.serialize_entry("is_behind", &self.is_behind)?;
+        map.serialize_entry("laps_since_pit", &self.laps_since_pit)?;
+        map.serialize_entry("pit_stops", &self.pit_stops)?;
         map.end()
     }
 }
@@ -64,9 +70,10 @@ impl RelativeDriver {
             user_name: driver.user_name.clone(),
             car_number: driver.car_number.clone(),
             irating: format_irating(driver.irating),
+            irating_change: format_irating_change(driver.irating_change),
             license: driver.lic_string.clone(),
             player_relative_gap: get_relative_gap(driver.position, session),
-            is_player: driver.is_player,
+            is_player: driver.is_focus,
             is_in_pits: driver.is_in_pits,
             is_off_track: driver.is_off_track,
             is_off_world: driver.is_off_world,
@@ -74,6 +81,10 @@ impl RelativeDriver {
             car_class_color: driver.car_class_color,
             is_ahead: driver.ahead_behind == 1,
             is_behind: driver.ahead_behind == -1,
+            laps_since_pit: driver
+                .pit_history
+                .laps_since_last_stop(driver.laps_completed),
+            pit_stops: driver.pit_history.stop_count(),
         }
     }
 }
@@ -92,25 +103,30 @@ impl Serialize for Relative {
 
 impl EmittableEvent for Relative {
     fn is_ready(&self, session: &SessionData) -> bool {
-        session.active && !session.drivers.is_empty() && !session.driver_positions.is_empty()
+        session.active
+            && !session.driver_positions.is_empty()
+            && session
+                .focus_car_id
+                .is_some_and(|car_id| session.drivers.contains_key(&car_id))
     }
 
     fn get_event(&self, session: &SessionData) -> Box<dyn EmittableValue> {
         let mut drivers: Vec<Driver> = session
             .drivers
             .values()
-            .filter(|driver| driver.is_player || !driver.is_off_world)
+            .filter(|driver| driver.is_focus || !driver.is_off_world)
             .cloned()
             .collect();
         drivers.sort_by(|a, b| {
             a.player_relative_gap
                 .partial_cmp(&b.player_relative_gap)
                 .unwrap()
+                .then(a.car_id.cmp(&b.car_id))
         });
         let player_idx = drivers
             .iter()
             .enumerate()
-            .find(|(_, driver)| driver.is_player)
+            .find(|(_, driver)| driver.is_focus)
             .unwrap()
             .0;
         let mut result: Vec<RelativeDriver> =
@@ -0,0 +1,105 @@
+use serde::{Serialize, Serializer};
+use specta::Type;
+
+use crate::emitter::emittable_event::{EmittableEvent, EmittableValue};
+use crate::session::session_data::SessionData;
+use crate::util::format_sector_time::format_sector_time;
+use crate::util::signed_duration::SignedDuration;
+
+#[derive(Default, Type, PartialEq)]
+pub struct SectorTime {
+    sector_num: u32,
+    is_current: bool,
+    current: String,
+    last: String,
+    best: String,
+    // purple for the class session best, green for a personal best, yellow otherwise
+    status: String,
+}
+
+impl Serialize for SectorTime {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(6))?;
+        map.serialize_entry("sector_num", &self.sector_num)?;
+        map.serialize_entry("is_current", &self.is_current)?;
+        map.serialize_entry("current", &self.current)?;
+        map.serialize_entry("last", &self.last)?;
+        map.serialize_entry("best", &self.best)?;
+        map.serialize_entry("status", &self.status)?;
+        map.end()
+    }
+}
+
+fn sector_status(
+    time: Option<SignedDuration>,
+    best: Option<SignedDuration>,
+    class_best: Option<SignedDuration>,
+) -> String {
+    match time {
+        None => "none",
+        Some(time) if class_best.is_some_and(|class_best| time <= class_best) => "purple",
+        Some(time) if best.is_some_and(|best| time <= best) => "green",
+        Some(_) => "yellow",
+    }
+    .to_string()
+}
+
+#[derive(Default, Type, PartialEq)]
+pub struct SectorTimes(Vec<SectorTime>);
+
+impl Serialize for SectorTimes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl EmittableEvent for SectorTimes {
+    fn is_ready(&self, session: &SessionData) -> bool {
+        session.active
+            && !session.session_info.split_time_info.sectors.is_empty()
+            && session
+                .focus_car_id
+                .is_some_and(|car_id| session.drivers.contains_key(&car_id))
+    }
+
+    fn get_event(&self, session: &SessionData) -> Box<dyn EmittableValue> {
+        let player = session
+            .focus_car_id
+            .and_then(|car_id| session.drivers.get(&car_id));
+        let Some(player) = player else {
+            return Box::new(SectorTimes::default());
+        };
+        let timing = &player.sector_timing;
+        let class_best = session.class_best_sector_times.get(&player.car_class_id);
+        let sectors = session
+            .session_info
+            .split_time_info
+            .sectors
+            .iter()
+            .enumerate()
+            .map(|(idx, sector)| {
+                let current = timing.current_lap.get(idx).copied().flatten();
+                let last = timing.last_lap.get(idx).copied().flatten();
+                let best = timing.best.get(idx).copied().flatten();
+                let class_best =
+                    class_best.and_then(|class_best| class_best.get(idx).copied().flatten());
+                SectorTime {
+                    sector_num: sector.sector_num,
+                    is_current: timing.current_sector() == idx,
+                    current: format_sector_time(current),
+                    last: format_sector_time(last),
+                    best: format_sector_time(best),
+                    status: sector_status(current.or(last), best, class_best),
+                }
+            })
+            .collect();
+        Box::new(SectorTimes(sectors))
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{Serialize, Serializer};
+use specta::Type;
+
+use crate::emitter::emittable_event::{EmittableEvent, EmittableValue};
+use crate::session::session_data::SessionData;
+
+/// Whether the overlays follow the camera car instead of the player
+#[derive(Default, Type, PartialEq)]
+pub struct Spectating {
+    is_spectating: bool,
+    car_id: Option<u32>,
+    user_name: String,
+    car_number: String,
+}
+
+impl Serialize for Spectating {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(4))?;
+        map.serialize_entry("is_spectating", &self.is_spectating)?;
+        map.serialize_entry("car_id", &self.car_id)?;
+        map.serialize_entry("user_name", &self.user_name)?;
+        map.serialize_entry("car_number", &self.car_number)?;
+        map.end()
+    }
+}
+
+impl EmittableEvent for Spectating {
+    fn is_ready(&self, _session: &SessionData) -> bool {
+        true
+    }
+
+    fn get_event(&self, session: &SessionData) -> Box<dyn EmittableValue> {
+        let focus_car = session
+            .focus_car_id
+            .and_then(|car_id| session.drivers.get(&car_id));
+        Box::new(Spectating {
+            is_spectating: session.is_spectating,
+            car_id: session.focus_car_id,
+            user_name: focus_car
+                .map(|driver| driver.user_name.clone())
+                .unwrap_or_default(),
+            car_number: focus_car
+                .map(|driver| driver.car_number.clone())
+                .unwrap_or_default(),
+        })
+    }
+}
@@ -0,0 +1,37 @@
+use serde::{Serialize, Serializer};
+use specta::Type;
+
+use crate::emitter::emittable_event::{EmittableEvent, EmittableValue};
+use crate::session::session_data::SessionData;
+
+/// Only changes on spotter transitions, the overlap lives in `Proximity`
+#[derive(Default, Type, PartialEq)]
+pub struct Spotter {
+    state: String,
+    previous_state: String,
+    changed_at: f64, // session time in s
+}
+
+impl Serialize for Spotter {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("state", &self.state)?;
+        map.serialize_entry("previous_state", &self.previous_state)?;
+        map.serialize_entry("changed_at", &self.changed_at)?;
+        map.end()
+    }
+}
+
+impl EmittableEvent for Spotter {
+    fn get_event(&self, session: &SessionData) -> Box<dyn EmittableValue> {
+        Box::new(Spotter {
+            state: session.spotter.state.to_string(),
+            previous_state: session.spotter.previous_state.to_string(),
+            changed_at: session.spotter.changed_at.as_secs_f64(),
+        })
+    }
+}
@@ -1,12 +1,11 @@
 use serde::{Serialize, Serializer};
 use specta::Type;
 
-use crate::APP_HANDLE;
 use crate::emitter::emittable_event::{EmittableEvent, EmittableValue};
 use crate::session::driver::Driver;
 use crate::session::session_data::SessionData;
 use crate::settings::overlays::standings::StandingsOverlaySettings;
-use crate::util::format_irating::format_irating;
+use crate::util::format_irating::{format_irating, format_irating_change};
 use crate::util::format_laptime::format_laptime;
 use crate::util::get_gap::get_gap;
 use crate::util::settings_helper::get_settings;
@@ -18,6 +17,7 @@ pub struct StandingsDriver {
     user_name: String,
     car_number: String,
     irating: String,
+    irating_change: String,
     license: String,
     leader_gap: String,
     best_lap: String,
@@ -28,6 +28,11 @@ pub struct StandingsDriver {
     split_after: bool,
     is_off_world: bool,
     is_off_track: bool,
+    laps_since_pit: u32,
+    pit_stops: u32,
+    start_position: Option<u32>,
+    positions_gained: i32,
+    stint_positions_gained: i32,
 }
 
 impl Serialize for StandingsDriver {
@@ -36,12 +41,13 @@ impl Serialize for StandingsDriver {
         S: Serializer,
     {
         use serde::ser::SerializeMap;
-        let mut map = serializer.serialize_map(Some(15))?;
+        let mut map = serializer.serialize_map(Some(21))?;
         map.serialize_entry("car_id", &self.car_id)?;
         map.serialize_entry("position", &self.position)?;
         map.serialize_entry("user_name", &self.user_name)?;
         map.serialize_entry("car_number", &self.car_number)?;
         map.serialize_entry("irating", &self.irating)?;
+        map.serialize_entry("irating_change", &self.irating_change)?;
         map.serialize_entry("license", &self.license)?;
         map.serialize_entry("leader_gap", &self.leader_gap)?;
         map.serialize_entry("best_lap", &self.best_lap)?;
@@ -52,10 +58,50 @@ impl Serialize for StandingsDriver {
         map.serialize_entry("split_after", &self.split_after)?;
         map.serialize_entry("is_off_world", &self.is_off_world)?;
         map.serialize_entry("is_off_track", &self.is_off_track)?;
+        map.serialize_entry("laps_since_pit", &self.laps_since_pit)?;
+        map.serialize_entry("pit_stops", &self.pit_stops)?;
+        map.serialize_entry("start_position", &self.start_position)?;
+        map.serialize_entry("positions_gained", &self.positions_gained)?;
+        map.serialize_entry("stint_positions_gained", &self.stint_positions_gained)?;
         map.end()
     }
 }
 
+impl StandingsDriver {
+    pub fn new(driver: &Driver, session: &SessionData, split_after: bool) -> Self {
+        let laps_since_pit = driver
+            .pit_history
+            .laps_since_last_stop(driver.laps_completed);
+        let history = &driver.position_history;
+        StandingsDriver {
+            car_id: driver.car_id,
+            position: driver.class_position,
+            user_name: driver.team_name.clone(),
+            car_number: driver.car_number.clone(),
+            irating: format_irating(driver.irating),
+            irating_change: format_irating_change(driver.irating_change),
+            license: driver.lic_string.clone(),
+            leader_gap: get_gap(driver.position, session, true),
+            best_lap: format_laptime(driver.best_lap_time),
+            last_lap: format_laptime(driver.last_lap_time),
+            is_player: driver.is_focus,
+            is_leader: driver.is_class_leader,
+            is_in_pits: driver.is_in_pits,
+            split_after,
+            is_off_world: driver.is_off_world,
+            is_off_track: driver.is_off_track,
+            laps_since_pit,
+            pit_stops: driver.pit_history.stop_count(),
+            start_position: history.start_position,
+            positions_gained: history.positions_gained(driver.class_position),
+            stint_positions_gained: history.positions_gained_since(
+                driver.laps_completed.saturating_sub(laps_since_pit),
+                driver.class_position,
+            ),
+        }
+    }
+}
+
 #[derive(Default, Type)]
 pub struct Standings(Vec<StandingsDriver>);
 
@@ -68,9 +114,84 @@ impl Serialize for Standings {
     }
 }
 
+/// Keeps the top drivers and fills the rest with drivers around the player, `drivers` sorted by position
+pub fn select_drivers(
+    mut drivers: Vec<Driver>,
+    player_position: Option<usize>,
+    max_drivers_count: usize,
+    top_drivers_count: usize,
+) -> (Vec<Driver>, bool) {
+    if drivers.len() <= max_drivers_count {
+        return (drivers, false);
+    }
+    let Some(player_position) = player_position.filter(|position| *position > 0) else {
+        drivers.truncate(max_drivers_count);
+        return (drivers, false);
+    };
+    let mut taken_positions = 0;
+    let top_drivers = drivers
+        .iter()
+        .take(top_drivers_count)
+        .cloned()
+        .collect::<Vec<Driver>>();
+    taken_positions += top_drivers_count;
+    let player_already_taken = player_position <= top_drivers_count;
+    if !player_already_taken {
+        taken_positions += 1;
+    }
+
+    let mut drivers_before: Vec<Driver> = Vec::new();
+    let mut drivers_after: Vec<Driver> = Vec::new();
+    let mut take_before = true;
+    let mut delta: usize = 1;
+
+    while taken_positions < max_drivers_count {
+        if take_before {
+            if player_position < delta {
+                take_before = false;
+                continue;
+            }
+            let position = player_position - delta;
+            if position <= top_drivers_count {
+                take_before = false;
+                continue;
+            }
+            drivers_before.push(drivers[position - 1].clone());
+            taken_positions += 1;
+        } else {
+            let position = player_position + delta;
+            if position <= top_drivers_count {
+                delta += 1;
+                continue;
+            }
+            if position > drivers.len() {
+                take_before = true;
+                delta += 1;
+                continue;
+            }
+            drivers_after.push(drivers[position - 1].clone());
+            taken_positions += 1;
+            delta += 1;
+        }
+        take_before = !take_before;
+    }
+
+    let mut selected_drivers = vec![];
+    selected_drivers.extend(top_drivers);
+    drivers_before.reverse();
+    selected_drivers.extend(drivers_before);
+    if !player_already_taken {
+        selected_drivers.push(drivers[player_position - 1].clone());
+    }
+    selected_drivers.extend(drivers_after);
+    let split_after =
+        selected_drivers[top_drivers_count].class_position as usize != top_drivers_count + 1;
+    (selected_drivers, split_after)
+}
+
 impl EmittableEvent for Standings {
     fn is_ready(&self, session: &SessionData) -> bool {
-        // TODO: qualify implementation
+        // qualifying and practice boards are in `QualifyStandings`
         session.active
             && session.position != 0
             && !session.drivers.is_empty()
@@ -78,9 +199,7 @@ impl EmittableEvent for Standings {
     }
 
     fn get_event(&self, session: &SessionData) -> Box<dyn EmittableValue> {
-        let app_handle = APP_HANDLE.get().unwrap();
-        let settings = get_settings::<StandingsOverlaySettings>(app_handle.clone(), "standings");
-        let max_drivers_count = settings.max_drivers as usize;
+        let settings = get_settings::<StandingsOverlaySettings>("standings");
         let top_drivers_count = settings.top_drivers as usize;
 
         let mut drivers = session
@@ -90,91 +209,21 @@ impl EmittableEvent for Standings {
             .cloned()
             .collect::<Vec<Driver>>();
         drivers.sort_by(|a, b| a.position.cmp(&b.position));
-        let player_position = session.class_position as usize;
-        let mut split_after = false;
-
-        let selected_drivers = if drivers.len() <= max_drivers_count {
-            drivers
-        } else {
-            let mut taken_positions = 0;
-            let top_drivers = drivers
-                .iter()
-                .take(top_drivers_count)
-                .cloned()
-                .collect::<Vec<Driver>>();
-            taken_positions += top_drivers_count;
-            let player_already_taken = player_position <= top_drivers_count;
-            if !player_already_taken {
-                taken_positions += 1;
-            }
-
-            let mut drivers_before: Vec<Driver> = Vec::new();
-            let mut drivers_after: Vec<Driver> = Vec::new();
-            let mut take_before = true;
-            let mut delta: usize = 1;
-
-            while taken_positions < max_drivers_count {
-                if take_before {
-                    if player_position < delta {
-                        take_before = false;
-                        continue;
-                    }
-                    let position = player_position - delta;
-                    if position <= top_drivers_count {
-                        take_before = false;
-                        continue;
-                    }
-                    drivers_before.push(drivers[position - 1].clone());
-                    taken_positions += 1;
-                } else {
-                    let position = player_position + delta;
-                    if position <= top_drivers_count {
-                        delta += 1;
-                        continue;
-                    }
-                    if position > drivers.len() {
-                        take_before = true;
-                        delta += 1;
-                        continue;
-                    }
-                    drivers_after.push(drivers[position - 1].clone());
-                    taken_positions += 1;
-                    delta += 1;
-                }
-                take_before = !take_before;
-            }
-
-            let mut selected_drivers = vec![];
-            selected_drivers.extend(top_drivers);
-            drivers_before.reverse();
-            selected_drivers.extend(drivers_before);
-            if !player_already_taken {
-                selected_drivers.push(drivers[player_position - 1].clone());
-            }
-            selected_drivers.extend(drivers_after);
-            split_after = selected_drivers[top_drivers_count].class_position as usize
-                != top_drivers_count + 1;
-            selected_drivers
-        };
+        let (selected_drivers, split_after) = select_drivers(
+            drivers,
+            Some(session.class_position as usize),
+            settings.max_drivers as usize,
+            top_drivers_count,
+        );
 
         let drivers = selected_drivers
             .iter()
-            .map(|driver| StandingsDriver {
-                car_id: driver.car_id,
-                position: driver.class_position,
-                user_name: driver.team_name.clone(),
-                car_number: driver.car_number.clone(),
-                irating: format_irating(driver.irating),
-                license: driver.lic_string.clone(),
-                leader_gap: get_gap(driver.position, session, true),
-                best_lap: format_laptime(driver.best_lap_time),
-                last_lap: format_laptime(driver.last_lap_time),
-                is_player: driver.is_player,
-                is_leader: driver.is_leader,
-                is_in_pits: driver.is_in_pits,
-                split_after: (driver.class_position as usize == top_drivers_count) && split_after,
-                is_off_world: driver.is_off_world,
-                is_off_track: driver.is_off_track,
+            .map(|driver| {
+                StandingsDriver::new(
+                    driver,
+                    session,
+                    (driver.class_position as usize == top_drivers_count) && split_after,
+                )
             })
             .collect::<Vec<StandingsDriver>>();
         Box::new(drivers)
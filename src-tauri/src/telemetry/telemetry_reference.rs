@@ -4,11 +4,11 @@ use log::info;
 use rand::Rng;
 use serde::{Serialize, Serializer};
 use specta::Type;
-use tauri::Manager;
+use sqlx::{Pool, Sqlite};
 
+use crate::db::DATABASE;
 use crate::emitter::emittable_event::{EmittableEvent, EmittableValue};
 use crate::session::session_data::SessionData;
-use crate::{APP_HANDLE, db};
 
 static RECORDING_META_DATA: OnceLock<RecordingMetaData> = OnceLock::new();
 static RECORDING_DATA: OnceLock<Mutex<RecordingData>> = OnceLock::new();
@@ -136,13 +136,9 @@ impl EmittableEvent for TelemetryReference {
             data
         };
 
-        let db: tauri::State<'_, db::DatabaseState> = APP_HANDLE
-            .get()
-            .unwrap()
-            .try_state::<db::DatabaseState>()
-            .unwrap();
+        let db = DATABASE.get().unwrap();
 
-        let mut tx = db.0.begin().await.unwrap();
+        let mut tx = db.pool.begin().await.unwrap();
 
         let stmt = r#"
             INSERT OR REPLACE INTO telemetry_reference_data
@@ -197,3 +193,54 @@ impl EmittableEvent for TelemetryReference {
         info!("Telemetry reference recording stopped");
     }
 }
+
+pub async fn get_reference_points(pool: &Pool<Sqlite>, track_id: u32) -> TelemetryReferenceOutput {
+    let stmt = r#"
+        SELECT recording_id
+        FROM telemetry_reference_meta
+        WHERE track_id = $1;
+    "#;
+
+    // fetch recording_id if it exists
+    let recording_id = sqlx::query_scalar::<_, u32>(stmt)
+        .bind(track_id)
+        .fetch_optional(pool)
+        .await
+        .unwrap();
+
+    if recording_id.is_none() {
+        return TelemetryReferenceOutput::default();
+    }
+
+    let recording_id = recording_id.unwrap();
+    let stmt = r#"
+        SELECT lap_dist, throttle, brake, steering_angle, gear
+        FROM telemetry_reference_data
+        WHERE recording_id = $1
+        ORDER BY lap_dist ASC;
+    "#;
+
+    let reference = sqlx::query_as::<_, TelemetryReference>(stmt)
+        .bind(recording_id)
+        .fetch_all(pool)
+        .await
+        .unwrap();
+
+    let stmt = r#"
+        SELECT lap_dist
+        FROM telemetry_reference_brake_points
+        WHERE recording_id = $1
+        ORDER BY lap_dist ASC;
+    "#;
+
+    let brake_points = sqlx::query_as::<_, BrakePoint>(stmt)
+        .bind(recording_id)
+        .fetch_all(pool)
+        .await
+        .unwrap();
+
+    TelemetryReferenceOutput {
+        reference,
+        brake_points,
+    }
+}
@@ -55,7 +55,7 @@ impl TrackMapDriver {
             position: driver.position,
             class_position,
             is_leader: driver.is_leader,
-            is_player: driver.is_player,
+            is_player: driver.is_focus,
             is_player_class: driver.is_player_class,
             lap_dist_pct: driver.lap_dist_pct,
             is_in_pits: driver.is_in_pits,
@@ -75,8 +75,9 @@ impl EmittableEvent for TrackMap {
     }
 
     fn get_event(&self, session: &SessionData) -> Box<dyn EmittableValue> {
-        let drivers: Vec<TrackMapDriver> =
-            session.drivers.values().map(TrackMapDriver::new).collect();
+        let mut drivers: Vec<&Driver> = session.drivers.values().collect();
+        drivers.sort_by_key(|driver| driver.car_id);
+        let drivers: Vec<TrackMapDriver> = drivers.into_iter().map(TrackMapDriver::new).collect();
 
         // Serialize the vector of drivers directly
         Box::new(drivers)
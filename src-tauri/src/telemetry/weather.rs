@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use serde::{Serialize, Serializer};
+use specta::Type;
+
+use crate::emitter::emittable_event::{EmittableEvent, EmittableValue};
+use crate::session::session_data::SessionData;
+
+const TREND_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Default, Type, PartialEq)]
+pub struct Weather {
+    weather_type: String,
+    air_temp: f32,   // in C
+    track_temp: f32, // in C
+    track_wetness: String,
+    precipitation_pct: u32,
+    wind_vel: f32, // in m/s
+    wind_dir: u32, // in degrees
+    skies: String,
+    fog_level_pct: u32,
+    /// Over the last 5 minutes, `falling` is a drying track
+    wetness_trend: String,
+    precipitation_trend: String,
+    track_temp_trend: String,
+    /// As set up for the session, the values above are the live conditions
+    weekend_skies: String,
+    weekend_air_temp: f32,   // in C
+    weekend_track_temp: f32, // in C
+    relative_humidity_pct: u32,
+}
+
+impl Serialize for Weather {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(16))?;
+        map.serialize_entry("weather_type", &self.weather_type)?;
+        map.serialize_entry("air_temp", &self.air_temp)?;
+        map.serialize_entry("track_temp", &self.track_temp)?;
+        map.serialize_entry("track_wetness", &self.track_wetness)?;
+        map.serialize_entry("precipitation_pct", &self.precipitation_pct)?;
+        map.serialize_entry("wind_vel", &self.wind_vel)?;
+        map.serialize_entry("wind_dir", &self.wind_dir)?;
+        map.serialize_entry("skies", &self.skies)?;
+        map.serialize_entry("fog_level_pct", &self.fog_level_pct)?;
+        map.serialize_entry("wetness_trend", &self.wetness_trend)?;
+        map.serialize_entry("precipitation_trend", &self.precipitation_trend)?;
+        map.serialize_entry("track_temp_trend", &self.track_temp_trend)?;
+        map.serialize_entry("weekend_skies", &self.weekend_skies)?;
+        map.serialize_entry("weekend_air_temp", &self.weekend_air_temp)?;
+        map.serialize_entry("weekend_track_temp", &self.weekend_track_temp)?;
+        map.serialize_entry("relative_humidity_pct", &self.relative_humidity_pct)?;
+        map.end()
+    }
+}
+
+fn round_tenth(value: f32) -> f32 {
+    (value * 10.0).round() / 10.0
+}
+
+impl EmittableEvent for Weather {
+    fn is_ready(&self, session: &SessionData) -> bool {
+        session.active && session.processed_slow
+    }
+
+    fn get_event(&self, session: &SessionData) -> Box<dyn EmittableValue> {
+        let weather = &session.weather;
+        Box::new(Weather {
+            weather_type: weather.weekend.weather_type.clone(),
+            air_temp: round_tenth(weather.air_temp),
+            track_temp: round_tenth(weather.track_temp),
+            track_wetness: weather.track_wetness.to_string(),
+            precipitation_pct: (weather.precipitation * 100.0).round() as u32,
+            wind_vel: round_tenth(weather.wind_vel),
+            wind_dir: (weather.wind_dir.to_degrees().rem_euclid(360.0)).round() as u32,
+            skies: weather.skies.to_string(),
+            fog_level_pct: (weather.fog_level * 100.0).round() as u32,
+            wetness_trend: weather.wetness_trend(TREND_WINDOW).to_string(),
+            precipitation_trend: weather.precipitation_trend(TREND_WINDOW).to_string(),
+            track_temp_trend: weather.track_temp_trend(TREND_WINDOW).to_string(),
+            weekend_skies: weather.weekend.skies.clone(),
+            weekend_air_temp: round_tenth(weather.weekend.air_temp),
+            weekend_track_temp: round_tenth(weather.weekend.track_temp),
+            relative_humidity_pct: (weather.weekend.relative_humidity * 100.0).round() as u32,
+        })
+    }
+}
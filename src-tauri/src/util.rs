@@ -1,8 +1,11 @@
 pub mod format_delta;
 pub mod format_irating;
 pub mod format_laptime;
+pub mod format_sector_time;
 pub mod get_gap;
+pub mod get_laps_to_finish;
 pub mod get_relative_gap;
 pub mod get_strength_of_field;
 pub mod settings_helper;
 pub mod signed_duration;
+pub mod yaml_value;
@@ -1,3 +1,7 @@
 pub fn format_irating(irating: u32) -> String {
     format!("{:.1}k", irating as f32 / 1000.0)
 }
+
+pub fn format_irating_change(change: i32) -> String {
+    format!("{change:+}")
+}
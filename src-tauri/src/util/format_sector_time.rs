@@ -0,0 +1,14 @@
+use super::signed_duration::SignedDuration;
+
+pub fn format_sector_time(sector_time: Option<SignedDuration>) -> String {
+    match sector_time {
+        Some(value) if value.is_positive() && value.as_secs() >= 60 => {
+            let ss = value.as_secs();
+            format!("{}:{:02}.{:03}", ss / 60, ss % 60, value.subsec_millis())
+        }
+        Some(value) if value.is_positive() => {
+            format!("{}.{:03}", value.as_secs(), value.subsec_millis())
+        }
+        _ => "–".to_string(),
+    }
+}
@@ -15,7 +15,7 @@ pub fn get_gap(position: u32, session: &SessionData, is_leader: bool) -> String
     match driver {
         None => "-".to_string(),
         Some(driver) => {
-            if is_leader && driver.is_leader {
+            if is_leader && driver.is_class_leader {
                 return "-".to_string();
             }
             let gap = match is_leader {
@@ -0,0 +1,48 @@
+use crate::session::driver::Driver;
+use crate::session::session_data::SessionData;
+use crate::session::session_type::SessionType;
+
+const PACE_LAPS: usize = 3;
+
+fn get_pace(driver: &Driver) -> Option<f32> {
+    let pace = driver
+        .lap_history
+        .average_lap_time(PACE_LAPS)
+        .or(Some(driver.best_lap_time).filter(|time| time.as_secs_f32() > 0.0))
+        .unwrap_or(driver.car_class_est_lap_time)
+        .as_secs_f32();
+    (pace > 0.0).then_some(pace)
+}
+
+/// Laps the player still has to drive until the checkered flag, including the current one.
+/// In races the session ends with the overall leader, which is projected with its recent pace.
+pub fn get_laps_to_finish(session: &SessionData) -> Option<f32> {
+    let player = session.drivers.get(&session.player_car_id?)?;
+    let leader = match session.session_type {
+        SessionType::Race => session.drivers.get(session.driver_positions.first()?)?,
+        _ => player,
+    };
+    let player_pace = get_pace(player)?;
+    let leader_pace = get_pace(leader)?;
+    let leader_completed = leader.total_completed;
+
+    let lap_limit = match session.laps_total {
+        0 => None,
+        laps_total => Some(laps_total as f32),
+    };
+    let time_remaining = session.session_time_remaining.as_secs_f32();
+    let time_limit = match session.session_time_total.is_zero() {
+        true => None,
+        // the leader finishes the lap during which the time runs out
+        false => Some((leader_completed + time_remaining.max(0.0) / leader_pace).floor() + 1.0),
+    };
+    let leader_laps = match (lap_limit, time_limit) {
+        (Some(lap_limit), Some(time_limit)) => lap_limit.min(time_limit),
+        (limit, None) | (None, limit) => limit?,
+    };
+
+    let leader_time_to_finish = (leader_laps - leader_completed).max(0.0) * leader_pace;
+    let player_completed = player.total_completed;
+    let player_laps = (player_completed + leader_time_to_finish / player_pace).ceil();
+    Some((player_laps - player_completed).max(0.0))
+}
@@ -5,11 +5,22 @@ use crate::session::session_data::SessionData;
 static BR1: f32 = 1600. / LN_2;
 
 pub fn get_strength_of_field(session: &SessionData) -> u32 {
-    let sum_of_exp = session
+    get_class_strength_of_field(session, session.player_car_class)
+}
+
+pub fn get_class_strength_of_field(session: &SessionData, car_class_id: u32) -> u32 {
+    let (count, sum_of_exp) = session
         .drivers
         .values()
-        .filter(|driver| driver.car_class_id == session.player_car_class)
-        .map(|driver| (-(driver.irating as f32) / BR1).exp())
-        .sum::<f32>();
-    (BR1 * (session.positions_total as f32 / sum_of_exp).ln()) as u32
+        .filter(|driver| driver.car_class_id == car_class_id)
+        .fold((0, 0.0), |(count, sum_of_exp), driver| {
+            (
+                count + 1,
+                sum_of_exp + (-(driver.irating as f32) / BR1).exp(),
+            )
+        });
+    if count == 0 {
+        return 0;
+    }
+    (BR1 * (count as f32 / sum_of_exp).ln()) as u32
 }
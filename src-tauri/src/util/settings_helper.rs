@@ -1,67 +1,37 @@
-use std::fs::File;
-
 use serde::{Serialize, de::DeserializeOwned};
-use tauri::{Manager, path::BaseDirectory};
-use tauri_plugin_store::StoreExt;
 
 use crate::{
     emitter::settings_emitter::emit_settings_update,
     settings::overlays::common_settings::{CommonSettings, HasCommonSettings},
+    settings::settings_provider::{SETTINGS_PROVIDER, SettingsProvider},
 };
 
-pub fn get_settings<T: DeserializeOwned>(app: tauri::AppHandle, overlay_name: &str) -> T {
-    let store = app.store(format!("{}_overlay.json", overlay_name)).unwrap();
-    let settings = store.get("settings");
-    let settings: serde_json::Value = match settings {
-        Some(settings) => settings,
-        None => {
-            let resource_path = app
-                .path()
-                .resolve(
-                    format!("data/default_settings/{}_overlay.json", overlay_name),
-                    BaseDirectory::Resource,
-                )
-                .unwrap();
-            let file = File::open(resource_path).unwrap();
-            let settings: serde_json::Value = serde_json::from_reader(file).unwrap();
-            let settings = settings["settings"].clone();
-            store.set("settings", settings.clone());
-            store.save().unwrap();
-            settings
-        }
-    };
-    serde_json::from_value(settings).unwrap()
+fn settings_provider() -> &'static dyn SettingsProvider {
+    SETTINGS_PROVIDER
+        .get()
+        .expect("Settings provider not initialized")
+        .as_ref()
+}
+
+pub fn get_settings<T: DeserializeOwned>(overlay_name: &str) -> T {
+    serde_json::from_value(settings_provider().load(overlay_name)).unwrap()
 }
 
 pub fn get_common_settings<T: DeserializeOwned + HasCommonSettings>(
-    app: tauri::AppHandle,
     overlay_name: &str,
 ) -> CommonSettings {
-    let settings = get_settings::<T>(app, overlay_name);
+    let settings = get_settings::<T>(overlay_name);
     settings.common_settings().clone()
 }
 
-pub fn set_common_settings<T: Serialize>(
-    app: tauri::AppHandle,
-    overlay_name: &str,
-    common_settings: CommonSettings,
-) {
-    let store = app.store(format!("{}_overlay.json", overlay_name)).unwrap();
-    let old_settings = store.get("settings");
-    let mut settings = old_settings.unwrap();
+pub fn set_common_settings<T: Serialize>(overlay_name: &str, common_settings: CommonSettings) {
+    let mut settings = settings_provider().load(overlay_name);
     settings["common_settings"] = serde_json::to_value(&common_settings).unwrap();
-    store.set("settings", serde_json::to_value(&settings).unwrap());
-    store.save().unwrap();
+    settings_provider().save(overlay_name, settings);
 }
 
-pub fn set_settings<T: Serialize + HasCommonSettings>(
-    app: tauri::AppHandle,
-    overlay_name: &str,
-    settings: T,
-) {
-    let store = app.store(format!("{}_overlay.json", overlay_name)).unwrap();
-    store.set("settings", serde_json::to_value(&settings).unwrap());
-    store.save().unwrap();
+pub fn set_settings<T: Serialize + HasCommonSettings>(overlay_name: &str, settings: T) {
+    settings_provider().save(overlay_name, serde_json::to_value(&settings).unwrap());
 
     // Broadcast settings update via WebSocket
     emit_settings_update(overlay_name);
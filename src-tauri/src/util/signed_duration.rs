@@ -104,6 +104,12 @@ impl std::ops::Add for SignedDuration {
     }
 }
 
+impl std::iter::Sum for SignedDuration {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |sum, value| sum + value)
+    }
+}
+
 impl std::ops::Sub for SignedDuration {
     type Output = Self;
 
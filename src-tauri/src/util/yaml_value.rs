@@ -0,0 +1,95 @@
+use yaml_rust::Yaml;
+
+// Session info values are loosely typed, e.g. `TrackLength: 6.51 km`, `SessionLaps: unlimited`
+// or `CarNumber: 12` instead of `CarNumber: "12"`, so every getter accepts several representations
+
+pub fn yaml_string(yaml: &Yaml) -> Option<String> {
+    match yaml {
+        Yaml::String(value) | Yaml::Real(value) => Some(value.clone()),
+        Yaml::Integer(value) => Some(value.to_string()),
+        Yaml::Boolean(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// Parses the leading number of values with units, e.g. `6.51 km` or `3600.0000 sec`
+pub fn yaml_f64(yaml: &Yaml) -> Option<f64> {
+    match yaml {
+        Yaml::Real(_) => yaml.as_f64(),
+        Yaml::Integer(value) => Some(*value as f64),
+        Yaml::String(value) => value.split_whitespace().next()?.parse().ok(),
+        _ => None,
+    }
+}
+
+pub fn yaml_f32(yaml: &Yaml) -> Option<f32> {
+    yaml_f64(yaml).map(|value| value as f32)
+}
+
+pub fn yaml_i64(yaml: &Yaml) -> Option<i64> {
+    match yaml {
+        Yaml::Integer(value) => Some(*value),
+        Yaml::String(value) => {
+            let value = value.split_whitespace().next()?;
+            match value.strip_prefix("0x") {
+                Some(hex) => i64::from_str_radix(hex, 16).ok(),
+                None => value.parse().ok(),
+            }
+        }
+        _ => None,
+    }
+}
+
+pub fn yaml_u32(yaml: &Yaml) -> Option<u32> {
+    yaml_i64(yaml).and_then(|value| u32::try_from(value).ok())
+}
+
+pub fn yaml_bool(yaml: &Yaml) -> Option<bool> {
+    match yaml {
+        Yaml::Boolean(value) => Some(*value),
+        _ => yaml_i64(yaml).map(|value| value != 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(value: &str) -> Yaml {
+        Yaml::String(value.to_string())
+    }
+
+    #[test]
+    fn parses_values_with_units() {
+        assert_eq!(yaml_f32(&string("6.51 km")), Some(6.51));
+        assert_eq!(yaml_f64(&string("3600.0000 sec")), Some(3600.0));
+        assert_eq!(yaml_f64(&Yaml::Integer(3)), Some(3.0));
+        assert_eq!(yaml_f64(&Yaml::Real("0.25".to_string())), Some(0.25));
+        assert_eq!(yaml_u32(&string("55 %")), Some(55));
+        assert_eq!(yaml_f64(&string("")), None);
+    }
+
+    #[test]
+    fn parses_hex_and_integer_strings() {
+        assert_eq!(yaml_u32(&string("0xffda00")), Some(0xffda00));
+        assert_eq!(yaml_i64(&string("-1")), Some(-1));
+        assert_eq!(yaml_u32(&string("-1")), None);
+        assert_eq!(yaml_u32(&string("unlimited")), None);
+        assert_eq!(yaml_f64(&string("unlimited")), None);
+    }
+
+    #[test]
+    fn converts_scalars_to_strings_and_bools() {
+        assert_eq!(yaml_string(&Yaml::Integer(12)), Some("12".to_string()));
+        assert_eq!(
+            yaml_string(&Yaml::Real("1.50".to_string())),
+            Some("1.50".to_string())
+        );
+        assert_eq!(yaml_string(&Yaml::Null), None);
+        assert_eq!(yaml_bool(&Yaml::Integer(1)), Some(true));
+        assert_eq!(yaml_bool(&Yaml::Integer(0)), Some(false));
+        assert_eq!(yaml_bool(&string("1")), Some(true));
+        assert_eq!(yaml_bool(&Yaml::Boolean(true)), Some(true));
+        assert_eq!(yaml_bool(&string("yes")), None);
+    }
+}
@@ -0,0 +1,131 @@
+use eyre::Result;
+use serde::{Deserialize, Serialize, Serializer};
+use tokio_tungstenite::tungstenite::Message;
+
+/// Request sent by a client over the WebSocket, JSON in text frames or MessagePack in binary ones
+///
+/// `{"id": 1, "command": "get_settings", "overlay": "relative"}`
+#[derive(Deserialize, Debug, PartialEq)]
+pub struct ClientMessage {
+    /// Echoed back in the response so the client can match it to the request
+    #[serde(default)]
+    pub id: Option<u32>,
+    #[serde(flatten)]
+    pub command: ClientCommand,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum ClientCommand {
+    Subscribe {
+        event: String,
+    },
+    Unsubscribe {
+        event: String,
+    },
+    GetSettings {
+        overlay: String,
+    },
+    SetSettings {
+        overlay: String,
+        settings: serde_json::Value,
+    },
+    LockUnlockOverlays,
+    GetOverlaysLocked,
+    RecordTelemetry,
+    GetTelemetryReferencePoints {
+        track_id: u32,
+    },
+    GetClientStats,
+}
+
+impl ClientMessage {
+    /// Returns `None` for frames that are not requests, e.g. pings
+    pub fn from_ws(message: &Message) -> Option<Result<Self>> {
+        match message {
+            Message::Text(text) => Some(serde_json::from_str(text.as_str()).map_err(Into::into)),
+            Message::Binary(data) => Some(rmp_serde::from_slice(data).map_err(Into::into)),
+            _ => None,
+        }
+    }
+}
+
+/// Sent back to the client as the `response` event
+#[derive(Debug, PartialEq)]
+pub struct ControlResponse {
+    pub id: Option<u32>,
+    pub result: Result<serde_json::Value, String>,
+}
+
+impl Serialize for ControlResponse {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("id", &self.id)?;
+        match &self.result {
+            Ok(value) => {
+                map.serialize_entry("result", value)?;
+                map.serialize_entry("error", &None::<String>)?;
+            }
+            Err(err) => {
+                map.serialize_entry("result", &serde_json::Value::Null)?;
+                map.serialize_entry("error", err)?;
+            }
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_json_requests() {
+        let message = Message::text(r#"{"id": 7, "command": "subscribe", "event": "relative"}"#);
+        let message = ClientMessage::from_ws(&message).unwrap().unwrap();
+        assert_eq!(
+            message,
+            ClientMessage {
+                id: Some(7),
+                command: ClientCommand::Subscribe {
+                    event: "relative".to_string()
+                },
+            }
+        );
+
+        let message = Message::text(r#"{"command": "lock_unlock_overlays"}"#);
+        let message = ClientMessage::from_ws(&message).unwrap().unwrap();
+        assert_eq!(message.id, None);
+        assert_eq!(message.command, ClientCommand::LockUnlockOverlays);
+    }
+
+    #[test]
+    fn parses_msgpack_requests() {
+        let request = serde_json::json!({
+            "id": 1,
+            "command": "set_settings",
+            "overlay": "timer",
+            "settings": {"delta_enabled": true},
+        });
+        let message = Message::binary(rmp_serde::to_vec_named(&request).unwrap());
+        let message = ClientMessage::from_ws(&message).unwrap().unwrap();
+        assert_eq!(
+            message.command,
+            ClientCommand::SetSettings {
+                overlay: "timer".to_string(),
+                settings: serde_json::json!({"delta_enabled": true}),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_commands() {
+        let message = Message::text(r#"{"id": 1, "command": "restart"}"#);
+        assert!(ClientMessage::from_ws(&message).unwrap().is_err());
+        assert!(ClientMessage::from_ws(&Message::Ping(Default::default())).is_none());
+    }
+}
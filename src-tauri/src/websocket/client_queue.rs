@@ -0,0 +1,227 @@
+use std::{
+    collections::VecDeque,
+    net::SocketAddr,
+    sync::Mutex as StdMutex,
+    time::{Duration, Instant},
+};
+
+use eyre::{Result, eyre};
+use serde::Serialize;
+use tokio::sync::Notify;
+
+pub const DEFAULT_MAX_QUEUED_MESSAGES: usize = 256;
+pub const DEFAULT_MAX_QUEUE_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub struct QueuedMessage {
+    /// Only messages with an event name are coalesced, responses never are
+    event: Option<String>,
+    pub data: Vec<u8>,
+    pub queued_at: Instant,
+}
+
+/// Queue and latency figures of one WebSocket client, returned by `get_client_stats`
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ClientStats {
+    pub client: String,
+    pub queued: usize,
+    pub max_queued: usize,
+    pub sent: u64,
+    /// Messages replaced by a newer value of the same event before being sent
+    pub coalesced: u64,
+    pub last_latency_ms: f64,
+    pub average_latency_ms: f64,
+    pub max_latency_ms: f64,
+}
+
+#[derive(Default, Debug)]
+struct QueueState {
+    messages: VecDeque<QueuedMessage>,
+    closed: bool,
+    max_queued: usize,
+    sent: u64,
+    coalesced: u64,
+    last_latency: Duration,
+    total_latency: Duration,
+    max_latency: Duration,
+}
+
+/// Bounded outgoing queue of one client, keeps only the latest value per event
+///
+/// A client that falls too far behind, by queued messages or by the age of the oldest one,
+/// is closed instead of buffering without bound
+#[derive(Debug)]
+pub struct ClientQueue {
+    client: SocketAddr,
+    max_messages: usize,
+    max_delay: Duration,
+    state: StdMutex<QueueState>,
+    notify: Notify,
+    on_close: Notify,
+}
+
+impl ClientQueue {
+    pub fn new(client: SocketAddr, max_messages: usize, max_delay: Duration) -> Self {
+        Self {
+            client,
+            max_messages,
+            max_delay,
+            state: StdMutex::new(QueueState::default()),
+            notify: Notify::new(),
+            on_close: Notify::new(),
+        }
+    }
+
+    /// Fails and closes the queue once the client falls too far behind
+    pub fn push(&self, event: Option<&str>, data: Vec<u8>) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        // the connection is going away, its handler cleans up
+        if state.closed {
+            return Ok(());
+        }
+        let now = Instant::now();
+        if let Some(oldest) = state.messages.front()
+            && now.duration_since(oldest.queued_at) > self.max_delay
+        {
+            self.close_state(&mut state);
+            return Err(eyre!(
+                "Oldest message is queued for more than {:?}",
+                self.max_delay
+            ));
+        }
+        // keeps its place and enqueue time, so a stalled client still ages out
+        if let Some(event) = event
+            && let Some(queued) = state
+                .messages
+                .iter_mut()
+                .find(|message| message.event.as_deref() == Some(event))
+        {
+            queued.data = data;
+            state.coalesced += 1;
+            return Ok(());
+        }
+        if state.messages.len() >= self.max_messages {
+            self.close_state(&mut state);
+            return Err(eyre!("More than {} messages queued", self.max_messages));
+        }
+        state.messages.push_back(QueuedMessage {
+            event: event.map(str::to_string),
+            data,
+            queued_at: now,
+        });
+        state.max_queued = state.max_queued.max(state.messages.len());
+        drop(state);
+        self.notify.notify_one();
+        Ok(())
+    }
+
+    /// Waits for the next message, `None` once the queue is closed
+    pub async fn pop(&self) -> Option<QueuedMessage> {
+        loop {
+            {
+                let mut state = self.state.lock().unwrap();
+                if state.closed {
+                    return None;
+                }
+                if let Some(message) = state.messages.pop_front() {
+                    return Some(message);
+                }
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    /// Latency is the time from queueing the message until it was written to the socket
+    pub fn record_sent(&self, queued_at: Instant) {
+        let latency = queued_at.elapsed();
+        let mut state = self.state.lock().unwrap();
+        state.sent += 1;
+        state.last_latency = latency;
+        state.total_latency += latency;
+        state.max_latency = state.max_latency.max(latency);
+    }
+
+    pub fn close(&self) {
+        self.close_state(&mut self.state.lock().unwrap());
+    }
+
+    fn close_state(&self, state: &mut QueueState) {
+        state.closed = true;
+        state.messages.clear();
+        self.notify.notify_one();
+        self.on_close.notify_waiters();
+    }
+
+    /// Resolves once the queue is closed, even while a send to the client is stuck
+    pub async fn closed(&self) {
+        let notified = self.on_close.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+        if self.state.lock().unwrap().closed {
+            return;
+        }
+        notified.await;
+    }
+
+    pub fn stats(&self) -> ClientStats {
+        let state = self.state.lock().unwrap();
+        let average_latency = match state.sent {
+            0 => Duration::ZERO,
+            sent => state.total_latency / sent as u32,
+        };
+        ClientStats {
+            client: self.client.to_string(),
+            queued: state.messages.len(),
+            max_queued: state.max_queued,
+            sent: state.sent,
+            coalesced: state.coalesced,
+            last_latency_ms: state.last_latency.as_secs_f64() * 1000.0,
+            average_latency_ms: average_latency.as_secs_f64() * 1000.0,
+            max_latency_ms: state.max_latency.as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_queue(max_messages: usize, max_delay: Duration) -> ClientQueue {
+        ClientQueue::new("127.0.0.1:1234".parse().unwrap(), max_messages, max_delay)
+    }
+
+    #[tokio::test]
+    async fn keeps_only_the_latest_value_per_event() {
+        let queue = new_queue(2, DEFAULT_MAX_QUEUE_DELAY);
+        queue.push(Some("standings"), vec![1]).unwrap();
+        queue.push(None, vec![2]).unwrap();
+        queue.push(Some("standings"), vec![3]).unwrap();
+
+        let first = queue.pop().await.unwrap();
+        assert_eq!(first.data, [3]);
+        queue.record_sent(first.queued_at);
+        assert_eq!(queue.pop().await.unwrap().data, [2]);
+
+        let stats = queue.stats();
+        assert_eq!(stats.sent, 1);
+        assert_eq!(stats.coalesced, 1);
+        assert_eq!(stats.max_queued, 2);
+        assert_eq!(stats.queued, 0);
+    }
+
+    #[tokio::test]
+    async fn closes_when_the_client_falls_behind() {
+        let queue = new_queue(2, DEFAULT_MAX_QUEUE_DELAY);
+        queue.push(None, vec![1]).unwrap();
+        queue.push(None, vec![2]).unwrap();
+        assert!(queue.push(None, vec![3]).is_err());
+        assert!(queue.pop().await.is_none());
+        queue.closed().await;
+
+        let queue = new_queue(10, Duration::from_millis(10));
+        queue.push(Some("relative"), vec![1]).unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(queue.push(Some("relative"), vec![2]).is_err());
+        assert!(queue.pop().await.is_none());
+    }
+}
@@ -0,0 +1,113 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use eyre::{Result, eyre};
+use futures_util::future::BoxFuture;
+use log::{debug, info};
+use serde_json::Value;
+
+use crate::db::DATABASE;
+use crate::pipeline::Pipeline;
+use crate::settings::overlay_settings::{get_overlay_settings, set_overlay_settings};
+use crate::telemetry::telemetry_reference::get_reference_points;
+
+use super::client_message::ClientCommand;
+use super::server::WS_SERVER;
+
+/// Overlay windows are owned by the desktop app, the headless server has none
+pub trait OverlayLock: Send + Sync {
+    /// Returns whether the overlays are locked after toggling
+    fn lock_unlock(&self) -> BoxFuture<'_, bool>;
+
+    fn is_locked(&self) -> BoxFuture<'_, bool>;
+}
+
+/// Runs the commands WebSocket clients send, the same actions the settings window does over IPC
+pub struct ControlHandler {
+    pipeline: Arc<Pipeline>,
+    overlay_lock: Option<Box<dyn OverlayLock>>,
+}
+
+impl std::fmt::Debug for ControlHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ControlHandler")
+            .field("overlay_lock", &self.overlay_lock.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ControlHandler {
+    pub fn new(pipeline: Arc<Pipeline>) -> Self {
+        Self {
+            pipeline,
+            overlay_lock: None,
+        }
+    }
+
+    pub fn with_overlay_lock(mut self, overlay_lock: Box<dyn OverlayLock>) -> Self {
+        self.overlay_lock = Some(overlay_lock);
+        self
+    }
+
+    fn overlay_lock(&self) -> Result<&dyn OverlayLock> {
+        self.overlay_lock
+            .as_deref()
+            .ok_or_else(|| eyre!("Overlays are not available"))
+    }
+
+    pub async fn handle(&self, client: SocketAddr, command: ClientCommand) -> Result<Value> {
+        match command {
+            ClientCommand::Subscribe { event } => {
+                debug!("Subscribing {} to {}", client, event);
+                self.pipeline
+                    .emitter
+                    .lock()
+                    .await
+                    .subscribe(client, &event)?;
+                Ok(Value::Null)
+            }
+            ClientCommand::Unsubscribe { event } => {
+                self.pipeline
+                    .emitter
+                    .lock()
+                    .await
+                    .unsubscribe(client, &event)?;
+                Ok(Value::Null)
+            }
+            ClientCommand::GetSettings { overlay } => get_overlay_settings(&overlay),
+            ClientCommand::SetSettings { overlay, settings } => {
+                set_overlay_settings(&overlay, settings)?;
+                Ok(Value::Null)
+            }
+            ClientCommand::LockUnlockOverlays => {
+                Ok(Value::Bool(self.overlay_lock()?.lock_unlock().await))
+            }
+            ClientCommand::GetOverlaysLocked => Ok(Value::Bool(match &self.overlay_lock {
+                Some(overlay_lock) => overlay_lock.is_locked().await,
+                None => true,
+            })),
+            ClientCommand::RecordTelemetry => {
+                info!("Recording telemetry");
+                self.pipeline.emitter.lock().await.enable_recording();
+                Ok(Value::Null)
+            }
+            ClientCommand::GetTelemetryReferencePoints { track_id } => {
+                let db = DATABASE
+                    .get()
+                    .ok_or_else(|| eyre!("Database not initialized"))?;
+                let points = get_reference_points(&db.pool, track_id).await;
+                Ok(serde_json::to_value(points)?)
+            }
+            ClientCommand::GetClientStats => {
+                let ws_server = WS_SERVER
+                    .get()
+                    .ok_or_else(|| eyre!("WebSocket server not initialized"))?;
+                Ok(serde_json::to_value(ws_server.client_stats())?)
+            }
+        }
+    }
+
+    pub async fn disconnect(&self, client: SocketAddr) {
+        self.pipeline.emitter.lock().await.remove_client(client);
+    }
+}
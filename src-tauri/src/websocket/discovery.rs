@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::net::{IpAddr, SocketAddr, UdpSocket};
+use std::path::Path;
+
+use eyre::Result;
+use serde::Serialize;
+use specta::Type;
+
+use crate::settings::overlay_settings::AVAILABLE_OVERLAYS;
+
+/// How clients on the local network reach the server
+#[derive(Clone, Debug, PartialEq)]
+pub struct Discovery {
+    pub host: String,
+    pub port: u16,
+    pub token: Option<String>,
+    /// The built frontend is served over HTTP on the same port
+    pub serves_http: bool,
+    /// Why the configured address couldn't be used, the server listens on loopback instead
+    pub bind_error: Option<String>,
+}
+
+/// What is written to `discovery.json` and shown in the settings, the URLs are the QR code payload
+#[derive(Serialize, Type, Clone, Debug, PartialEq)]
+pub struct DiscoveryInfo {
+    pub host: String,
+    pub port: u16,
+    pub token: Option<String>,
+    pub url: String,
+    /// Empty unless the frontend is served
+    pub stream_urls: BTreeMap<String, String>,
+    pub bind_error: Option<String>,
+}
+
+/// Address of this PC on the local network, no packets are sent to connect a UDP socket
+fn local_network_ip() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.168.0.1:9").ok()?;
+    Some(socket.local_addr().ok()?.ip())
+}
+
+impl Discovery {
+    pub fn new(local_addr: SocketAddr, token: Option<String>, serves_http: bool) -> Self {
+        let ip = match local_addr.ip() {
+            ip if ip.is_unspecified() => local_network_ip().unwrap_or(ip),
+            ip => ip,
+        };
+        Self {
+            host: ip.to_string(),
+            port: local_addr.port(),
+            token,
+            serves_http,
+            bind_error: None,
+        }
+    }
+
+    pub fn url(&self) -> String {
+        match &self.token {
+            Some(token) => format!("ws://{}:{}/?token={}", self.host, self.port, token),
+            None => format!("ws://{}:{}/", self.host, self.port),
+        }
+    }
+
+    /// Transparent overlay pages to add as OBS browser sources, by overlay name
+    pub fn stream_urls(&self) -> BTreeMap<String, String> {
+        if !self.serves_http {
+            return BTreeMap::new();
+        }
+        AVAILABLE_OVERLAYS
+            .iter()
+            .map(|overlay| {
+                let url = match &self.token {
+                    Some(token) => format!(
+                        "http://{}:{}/stream/{}?token={}",
+                        self.host, self.port, overlay, token
+                    ),
+                    None => format!("http://{}:{}/stream/{}", self.host, self.port, overlay),
+                };
+                (overlay.to_string(), url)
+            })
+            .collect()
+    }
+
+    pub fn info(&self) -> DiscoveryInfo {
+        DiscoveryInfo {
+            host: self.host.clone(),
+            port: self.port,
+            token: self.token.clone(),
+            url: self.url(),
+            stream_urls: self.stream_urls(),
+            bind_error: self.bind_error.clone(),
+        }
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(&self.info())?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_stream_urls_only_when_serving_http() {
+        let addr = "127.0.0.1:9000".parse().unwrap();
+        let info = Discovery::new(addr, Some("s3cret".to_string()), true).info();
+        assert_eq!(info.url, "ws://127.0.0.1:9000/?token=s3cret");
+        assert_eq!(
+            info.stream_urls["relative"],
+            "http://127.0.0.1:9000/stream/relative?token=s3cret"
+        );
+        assert_eq!(info.stream_urls.len(), AVAILABLE_OVERLAYS.len());
+
+        let json = serde_json::to_value(&info).unwrap();
+        assert!(json.get("serves_http").is_none());
+        assert_eq!(
+            json["stream_urls"]["relative"],
+            info.stream_urls["relative"]
+        );
+
+        let info = Discovery::new(addr, None, false).info();
+        assert_eq!(info.url, "ws://127.0.0.1:9000/");
+        assert!(info.stream_urls.is_empty());
+    }
+}
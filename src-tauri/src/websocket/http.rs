@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use eyre::{Result, eyre};
+use log::debug;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use super::static_files::StaticFiles;
+
+const MAX_HEAD_SIZE: usize = 8192;
+const HEAD_TIMEOUT: Duration = Duration::from_secs(5);
+const PEEK_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Request line and headers, left in the socket so the WebSocket handshake can read them again
+pub async fn peek_head(stream: &TcpStream) -> Result<String> {
+    let mut buf = vec![0; MAX_HEAD_SIZE];
+    tokio::time::timeout(HEAD_TIMEOUT, async {
+        loop {
+            let len = stream.peek(&mut buf).await?;
+            if len == 0 {
+                return Err(eyre!("Connection closed before the request"));
+            }
+            if let Some(end) = buf[..len].windows(4).position(|w| w == b"\r\n\r\n") {
+                return Ok(String::from_utf8(buf[..end + 4].to_vec())?);
+            }
+            if len == buf.len() {
+                return Err(eyre!("Request head is too large"));
+            }
+            tokio::time::sleep(PEEK_INTERVAL).await;
+        }
+    })
+    .await?
+}
+
+pub fn is_websocket_upgrade(head: &str) -> bool {
+    head.lines().skip(1).any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("upgrade")
+                && value.trim().eq_ignore_ascii_case("websocket")
+        })
+    })
+}
+
+/// Answers a plain HTTP request with a static file and closes the connection
+pub async fn serve(
+    mut stream: TcpStream,
+    head: &str,
+    static_files: &dyn StaticFiles,
+) -> Result<()> {
+    let mut consumed = vec![0; head.len()];
+    stream.read_exact(&mut consumed).await?;
+
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let target = request_line.next().unwrap_or("/");
+    let path = target.split(['?', '#']).next().unwrap_or("/");
+    debug!("HTTP {} {}", method, path);
+
+    let (status, content_type, body) = match method {
+        "GET" | "HEAD" => match static_files.get(path) {
+            Some(file) => ("200 OK", file.content_type, file.bytes),
+            None => (
+                "404 Not Found",
+                "text/plain".to_string(),
+                b"Not found".to_vec(),
+            ),
+        },
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain".to_string(),
+            b"Method not allowed".to_vec(),
+        ),
+    };
+    let response_head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    );
+    stream.write_all(response_head.as_bytes()).await?;
+    if method != "HEAD" {
+        stream.write_all(&body).await?;
+    }
+    stream.shutdown().await?;
+    Ok(())
+}
@@ -0,0 +1,359 @@
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    path::PathBuf,
+    sync::{Arc, Mutex as StdMutex, OnceLock},
+    time::Duration,
+};
+
+use futures_util::{SinkExt, StreamExt};
+use log::{error, info, warn};
+use rmp_serde::Serializer;
+use serde::Serialize;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::{
+    accept_hdr_async,
+    tungstenite::{
+        Message,
+        handshake::server::{ErrorResponse, Request, Response},
+        http::StatusCode,
+    },
+};
+
+use crate::emitter::ws_event::WsEvent;
+
+use super::client_message::{ClientMessage, ControlResponse};
+use super::client_queue::{
+    ClientQueue, ClientStats, DEFAULT_MAX_QUEUE_DELAY, DEFAULT_MAX_QUEUED_MESSAGES,
+};
+use super::control_handler::ControlHandler;
+use super::discovery::Discovery;
+use super::http;
+use super::static_files::StaticFiles;
+
+type WsClients = Arc<StdMutex<HashMap<SocketAddr, Arc<ClientQueue>>>>;
+
+const FALLBACK_ADDRESS: &str = "127.0.0.1:0";
+
+static WS_DISCOVERY: OnceLock<Discovery> = OnceLock::new();
+pub static WS_SERVER: OnceLock<WebSocketServer> = OnceLock::new();
+
+#[derive(Clone)]
+pub struct WebSocketServer {
+    clients: WsClients,
+    control_handler: Option<Arc<ControlHandler>>,
+    token: Option<Arc<str>>,
+    discovery_path: Option<PathBuf>,
+    static_files: Option<Arc<dyn StaticFiles>>,
+    max_queued_messages: usize,
+    max_queue_delay: Duration,
+}
+
+impl Default for WebSocketServer {
+    fn default() -> Self {
+        Self {
+            clients: Arc::new(StdMutex::new(HashMap::new())),
+            control_handler: None,
+            token: None,
+            discovery_path: None,
+            static_files: None,
+            max_queued_messages: DEFAULT_MAX_QUEUED_MESSAGES,
+            max_queue_delay: DEFAULT_MAX_QUEUE_DELAY,
+        }
+    }
+}
+
+impl std::fmt::Debug for WebSocketServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebSocketServer")
+            .field("clients", &self.clients)
+            .field("control_handler", &self.control_handler)
+            .field("discovery_path", &self.discovery_path)
+            .field("serves_http", &self.static_files.is_some())
+            .finish()
+    }
+}
+
+/// Token from the `token` query parameter, browsers can't set headers on WebSockets,
+/// or from an `Authorization: Bearer` header
+fn request_token(request: &Request) -> Option<&str> {
+    let query_token = request.uri().query().and_then(|query| {
+        query
+            .split('&')
+            .find_map(|param| param.strip_prefix("token="))
+    });
+    query_token.or_else(|| {
+        request
+            .headers()
+            .get("Authorization")?
+            .to_str()
+            .ok()?
+            .strip_prefix("Bearer ")
+    })
+}
+
+/// Doesn't stop at the first differing byte, the server may be reachable from the local network
+fn token_matches(given: Option<&str>, expected: &str) -> bool {
+    let Some(given) = given else {
+        return false;
+    };
+    let (given, expected) = (given.as_bytes(), expected.as_bytes());
+    let diff = given
+        .iter()
+        .zip(expected)
+        .fold(given.len() ^ expected.len(), |diff, (a, b)| {
+            diff | usize::from(a ^ b)
+        });
+    std::hint::black_box(diff) == 0
+}
+
+impl WebSocketServer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Without a handler client requests are ignored
+    pub fn with_control_handler(mut self, control_handler: ControlHandler) -> Self {
+        self.control_handler = Some(Arc::new(control_handler));
+        self
+    }
+
+    /// Clients have to send the token during the handshake
+    pub fn with_token(mut self, token: &str) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Written once the server listens, so clients on other PCs know where to connect
+    pub fn with_discovery_file(mut self, path: PathBuf) -> Self {
+        self.discovery_path = Some(path);
+        self
+    }
+
+    /// Plain HTTP requests on the same port get the built frontend, no token needed for it
+    pub fn with_static_files(mut self, static_files: impl StaticFiles + 'static) -> Self {
+        self.static_files = Some(Arc::new(static_files));
+        self
+    }
+
+    /// Clients with more distinct messages queued, or an older one, are disconnected
+    pub fn with_queue_limits(mut self, max_messages: usize, max_delay: Duration) -> Self {
+        self.max_queued_messages = max_messages;
+        self.max_queue_delay = max_delay;
+        self
+    }
+
+    pub fn get_port() -> Option<u16> {
+        WS_DISCOVERY.get().map(|discovery| discovery.port)
+    }
+
+    pub fn get_discovery() -> Option<&'static Discovery> {
+        WS_DISCOVERY.get()
+    }
+
+    /// Falls back to a random loopback port when the configured address can't be bound, so the
+    /// app's own overlays keep working, the error is reported in the discovery
+    pub async fn run(&self, addr: &str) {
+        let mut bind_error = None;
+        let listener = match TcpListener::bind(addr).await {
+            Ok(listener) => listener,
+            Err(err) => {
+                error!("Failed to bind WebSocket server to {}: {}", addr, err);
+                bind_error = Some(format!("Failed to listen on {}: {}", addr, err));
+                match TcpListener::bind(FALLBACK_ADDRESS).await {
+                    Ok(listener) => listener,
+                    Err(err) => {
+                        error!(
+                            "Failed to bind WebSocket server to {}: {}",
+                            FALLBACK_ADDRESS, err
+                        );
+                        return;
+                    }
+                }
+            }
+        };
+        let local_addr = listener.local_addr().unwrap();
+        let mut discovery = Discovery::new(
+            local_addr,
+            self.token.as_deref().map(str::to_string),
+            self.static_files.is_some(),
+        );
+        discovery.bind_error = bind_error;
+        if let Some(path) = &self.discovery_path
+            && let Err(err) = discovery.write(path)
+        {
+            error!("Failed to write discovery file: {:?}", err);
+        }
+        WS_DISCOVERY
+            .set(discovery)
+            .expect("Failed to set WebSocket discovery");
+        info!("WebSocket server listening on: {}", local_addr);
+
+        while let Ok((stream, addr)) = listener.accept().await {
+            let server = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = server.handle_connection(stream, addr).await {
+                    error!("Error in connection handler: {}", e);
+                }
+            });
+        }
+    }
+
+    async fn handle_connection(&self, stream: TcpStream, addr: SocketAddr) -> eyre::Result<()> {
+        if let Some(static_files) = &self.static_files {
+            let head = http::peek_head(&stream).await?;
+            if !http::is_websocket_upgrade(&head) {
+                return http::serve(stream, &head, static_files.as_ref()).await;
+            }
+        }
+
+        // the error response type is given by tungstenite
+        #[allow(clippy::result_large_err)]
+        let check_token = |request: &Request, response: Response| match &self.token {
+            Some(token) if !token_matches(request_token(request), token) => {
+                warn!("Rejected WebSocket connection from {}: invalid token", addr);
+                let mut response = ErrorResponse::new(Some("Invalid token".to_string()));
+                *response.status_mut() = StatusCode::UNAUTHORIZED;
+                Err(response)
+            }
+            _ => Ok(response),
+        };
+        let ws_stream = accept_hdr_async(stream, check_token).await?;
+        let (mut ws_sender, mut ws_receiver) = ws_stream.split();
+        let queue = Arc::new(ClientQueue::new(
+            addr,
+            self.max_queued_messages,
+            self.max_queue_delay,
+        ));
+
+        {
+            self.clients.lock().unwrap().insert(addr, queue.clone());
+            info!("New WebSocket connection: {}", addr);
+        }
+
+        let send_queue = queue.clone();
+        let send_task = tokio::spawn(async move {
+            while let Some(message) = send_queue.pop().await {
+                if let Err(e) = ws_sender.send(Message::Binary(message.data.into())).await {
+                    error!("Error sending message to {}: {}", addr, e);
+                    send_queue.close();
+                    break;
+                }
+                send_queue.record_sent(message.queued_at);
+            }
+        });
+
+        loop {
+            let message = tokio::select! {
+                result = ws_receiver.next() => match result {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(message)) => message,
+                },
+                _ = queue.closed() => break,
+            };
+            let Some(response) = self.handle_message(addr, &message).await else {
+                continue;
+            };
+            let ws_event = WsEvent {
+                event: "response",
+                data: &response,
+            };
+            if let Some(buf) = Self::encode(&ws_event)
+                && let Err(e) = queue.push(None, buf)
+            {
+                warn!("Disconnecting slow WebSocket client {}: {}", addr, e);
+                break;
+            }
+        }
+
+        queue.close();
+        self.clients.lock().unwrap().remove(&addr);
+        if let Some(control_handler) = &self.control_handler {
+            control_handler.disconnect(addr).await;
+        }
+        info!("WebSocket connection closed: {}", addr);
+        send_task.abort();
+        Ok(())
+    }
+
+    /// Runs a client request, `None` for frames that need no response
+    async fn handle_message(&self, addr: SocketAddr, message: &Message) -> Option<ControlResponse> {
+        let control_handler = self.control_handler.as_ref()?;
+        match ClientMessage::from_ws(message)? {
+            Ok(request) => Some(ControlResponse {
+                id: request.id,
+                result: control_handler
+                    .handle(addr, request.command)
+                    .await
+                    .map_err(|err| err.to_string()),
+            }),
+            Err(err) => {
+                warn!("Invalid request from {}: {}", addr, err);
+                Some(ControlResponse {
+                    id: None,
+                    result: Err(format!("Invalid request: {}", err)),
+                })
+            }
+        }
+    }
+
+    fn encode<T: Serialize>(data: &T) -> Option<Vec<u8>> {
+        let mut buf = Vec::new();
+        data.serialize(&mut Serializer::new(&mut buf)).ok()?;
+        Some(buf)
+    }
+
+    /// A newer value of the same event replaces the one still queued for a client
+    pub fn broadcast(&self, ws_event: &WsEvent) {
+        let clients = self.clients.lock().unwrap();
+        if let Some(buf) = Self::encode(ws_event) {
+            for (addr, queue) in clients.iter() {
+                Self::push(*addr, queue, ws_event.event, buf.clone());
+            }
+        }
+    }
+
+    /// Sends to the given clients only, e.g. the ones subscribed to the event
+    pub fn send_to(&self, receivers: &[SocketAddr], ws_event: &WsEvent) {
+        let clients = self.clients.lock().unwrap();
+        if let Some(buf) = Self::encode(ws_event) {
+            for addr in receivers {
+                if let Some(queue) = clients.get(addr) {
+                    Self::push(*addr, queue, ws_event.event, buf.clone());
+                }
+            }
+        }
+    }
+
+    fn push(addr: SocketAddr, queue: &ClientQueue, event: &str, buf: Vec<u8>) {
+        if let Err(e) = queue.push(Some(event), buf) {
+            warn!("Disconnecting slow WebSocket client {}: {}", addr, e);
+        }
+    }
+
+    pub fn client_stats(&self) -> Vec<ClientStats> {
+        let mut stats = self
+            .clients
+            .lock()
+            .unwrap()
+            .values()
+            .map(|queue| queue.stats())
+            .collect::<Vec<_>>();
+        stats.sort_by(|a, b| a.client.cmp(&b.client));
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_only_the_exact_token() {
+        assert!(token_matches(Some("s3cret"), "s3cret"));
+        assert!(!token_matches(Some("s3creT"), "s3cret"));
+        assert!(!token_matches(Some("s3cre"), "s3cret"));
+        assert!(!token_matches(Some("s3cret!"), "s3cret"));
+        assert!(!token_matches(None, "s3cret"));
+    }
+}
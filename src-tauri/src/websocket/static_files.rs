@@ -0,0 +1,107 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+pub struct StaticFile {
+    pub bytes: Vec<u8>,
+    pub content_type: String,
+}
+
+/// Built frontend served over HTTP next to the WebSocket, e.g. for OBS browser sources
+pub trait StaticFiles: Send + Sync {
+    /// `path` is the URL path, `/stream/relative` resolves to `stream/relative.html`
+    fn get(&self, path: &str) -> Option<StaticFile>;
+}
+
+/// Candidate file names of a URL path the way the static adapter lays out prerendered routes,
+/// `None` if the path tries to leave the frontend directory
+pub fn resolve_candidates(path: &str) -> Option<Vec<String>> {
+    let path = path.trim_matches('/');
+    if Path::new(path)
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return None;
+    }
+    if path.is_empty() {
+        return Some(vec!["index.html".to_string()]);
+    }
+    Some(vec![
+        path.to_string(),
+        format!("{}.html", path),
+        format!("{}/index.html", path),
+    ])
+}
+
+pub fn content_type(path: &str) -> &'static str {
+    match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+        Some("html") => "text/html; charset=utf-8",
+        Some("js") => "text/javascript",
+        Some("css") => "text/css",
+        Some("json") => "application/json",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("ico") => "image/x-icon",
+        Some("otf") => "font/otf",
+        Some("ttf") => "font/ttf",
+        Some("woff") => "font/woff",
+        Some("woff2") => "font/woff2",
+        Some("mp3") => "audio/mpeg",
+        Some("wav") => "audio/wav",
+        Some("ogg") => "audio/ogg",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Frontend build directory on disk, for the headless server
+pub struct DirStaticFiles {
+    dir: PathBuf,
+}
+
+impl DirStaticFiles {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+}
+
+impl StaticFiles for DirStaticFiles {
+    fn get(&self, path: &str) -> Option<StaticFile> {
+        resolve_candidates(path)?.into_iter().find_map(|candidate| {
+            let file_path = self.dir.join(&candidate);
+            if !file_path.is_file() {
+                return None;
+            }
+            Some(StaticFile {
+                bytes: fs::read(file_path).ok()?,
+                content_type: content_type(&candidate).to_string(),
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_prerendered_routes() {
+        assert_eq!(resolve_candidates("/").unwrap(), ["index.html"]);
+        assert_eq!(
+            resolve_candidates("/stream/relative").unwrap(),
+            [
+                "stream/relative",
+                "stream/relative.html",
+                "stream/relative/index.html"
+            ]
+        );
+        assert_eq!(
+            content_type("track_info_data/track_info.json"),
+            "application/json"
+        );
+    }
+
+    #[test]
+    fn rejects_paths_outside_the_frontend() {
+        assert!(resolve_candidates("/../network.json").is_none());
+        assert!(resolve_candidates("/stream/../../discovery.json").is_none());
+    }
+}
@@ -0,0 +1,21 @@
+//! Fixtures shared by the WebSocket integration tests, every test binary runs a single server
+//! because its discovery is a process-wide static.
+
+use std::time::Duration;
+
+use iracehud_lib::websocket::server::{WS_SERVER, WebSocketServer};
+
+/// Runs the server on `addr` and returns its port once it listens, emitted events reach it too
+pub async fn start_server(server: WebSocketServer, addr: &str) -> u16 {
+    let _ = WS_SERVER.set(server.clone());
+    let addr = addr.to_string();
+    tokio::spawn(async move {
+        server.run(&addr).await;
+    });
+    loop {
+        match WebSocketServer::get_port() {
+            Some(port) => return port,
+            None => tokio::time::sleep(Duration::from_millis(10)).await,
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Session data has to survive the player leaving the car, follow the camera while spectating
+//! and only reset on a new session.
+
+use simetry::iracing::Value;
+use yaml_rust::Yaml;
+
+use iracehud_lib::session::session_data::{ProcessTickResult, SessionData};
+use iracehud_lib::source::replay_source::ReplaySpeed;
+use iracehud_lib::source::synthetic_session::SyntheticConfig;
+use iracehud_lib::source::synthetic_source::{SyntheticSource, SyntheticTick};
+use iracehud_lib::source::telemetry_source::TelemetrySource;
+use iracehud_lib::source::telemetry_tick::TelemetryTick;
+
+const TICK_RATE: u32 = 60;
+const SLOW_VAR_RESET_TICKS: u32 = 50;
+
+/// Synthetic tick with some of the variables replaced
+struct OverriddenTick<'a> {
+    tick: &'a SyntheticTick,
+    overrides: &'a [(&'static str, Value)],
+}
+
+impl TelemetryTick for OverriddenTick<'_> {
+    fn read_value(&self, name: &str, idx: usize) -> Option<Value> {
+        match self
+            .overrides
+            .iter()
+            .find(|(override_name, _)| *override_name == name)
+        {
+            Some((_, value)) => Some(value.clone()),
+            None => self.tick.read_value(name, idx),
+        }
+    }
+
+    fn value_count(&self, name: &str) -> Option<usize> {
+        self.tick.value_count(name)
+    }
+
+    fn session_info(&self) -> &Yaml {
+        self.tick.session_info()
+    }
+
+    fn session_info_update(&self) -> i32 {
+        self.tick.session_info_update()
+    }
+}
+
+/// Runs a race, calling `overrides` with the session time in seconds for every tick
+async fn run_race(
+    seconds: u32,
+    overrides: impl Fn(u32) -> Vec<(&'static str, Value)>,
+) -> (SessionData, Vec<ProcessTickResult>) {
+    let mut source = SyntheticSource::new(
+        SyntheticConfig {
+            session_laps: 3,
+            ..Default::default()
+        },
+        ReplaySpeed::Stepped,
+    );
+    let stepper = source.stepper();
+    let mut session = SessionData::default();
+    let mut results = Vec::new();
+    for tick in 0..seconds * TICK_RATE {
+        stepper.notify_one();
+        let sim_state = source.next_tick().await.unwrap();
+        let overrides = overrides(tick / TICK_RATE);
+        let sim_state = OverriddenTick {
+            tick: &sim_state,
+            overrides: &overrides,
+        };
+        results.push(session.process_tick(&sim_state, tick % SLOW_VAR_RESET_TICKS == 0, false));
+    }
+    (session, results)
+}
+
+#[tokio::test]
+async fn keeps_data_across_garage_visits() {
+    let (reference, _) = run_race(200, |_| vec![]).await;
+    let (session, results) = run_race(200, |seconds| match seconds {
+        150..160 => vec![("IsOnTrack", Value::Bool(false))],
+        _ => vec![],
+    })
+    .await;
+
+    assert!(!reference.player_lap_times.is_empty());
+    assert_eq!(
+        session.player_lap_times.len(),
+        reference.player_lap_times.len()
+    );
+    assert_eq!(session.drivers.len(), reference.drivers.len());
+    assert!(!results.contains(&ProcessTickResult::StateChanged));
+}
+
+#[tokio::test]
+async fn resets_data_on_a_new_session() {
+    let (session, results) = run_race(200, |seconds| match seconds {
+        190.. => vec![("SessionNum", Value::Int(1))],
+        _ => vec![],
+    })
+    .await;
+
+    assert!(session.player_lap_times.is_empty());
+    assert_eq!(
+        results
+            .iter()
+            .filter(|result| **result == ProcessTickResult::StateChanged)
+            .count(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn follows_the_camera_car_while_spectating() {
+    let (reference, _) = run_race(200, |_| vec![]).await;
+    let player_car_id = reference.player_car_id.unwrap();
+    let cam_car_id = *reference
+        .drivers
+        .keys()
+        .find(|car_id| **car_id != player_car_id)
+        .unwrap();
+    let (session, results) = run_race(200, |seconds| match seconds {
+        150.. => vec![
+            ("IsOnTrack", Value::Bool(false)),
+            ("CamCarIdx", Value::Int(cam_car_id as i32)),
+        ],
+        _ => vec![],
+    })
+    .await;
+
+    assert!(session.active);
+    assert!(session.is_spectating);
+    assert_eq!(session.focus_car_id, Some(cam_car_id));
+    assert_eq!(session.player_car_id, Some(player_car_id));
+    assert!(session.drivers[&cam_car_id].is_focus);
+    assert!(!session.drivers[&player_car_id].is_focus);
+    assert_eq!(session.position, session.drivers[&cam_car_id].position);
+    assert!(!results.contains(&ProcessTickResult::StateChanged));
+}
@@ -0,0 +1,154 @@
+//! Golden-file tests for the payloads of every `TelemetryEvent`.
+//!
+//! A synthetic session is run through `SessionData` and at a few checkpoints every event is
+//! serialized and compared with `tests/snapshots/<event>.snap`. Run with `UPDATE_SNAPSHOTS=1`
+//! to regenerate the snapshots after an intended payload change and review the diff.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{Local, TimeZone};
+use strum::IntoEnumIterator;
+
+use iracehud_lib::emitter::emittable_event::{EmittableEvent, TelemetryEvent};
+use iracehud_lib::session::session_data::SessionData;
+use iracehud_lib::settings::file_settings_provider::FileSettingsProvider;
+use iracehud_lib::settings::settings_provider::SETTINGS_PROVIDER;
+use iracehud_lib::source::replay_source::ReplaySpeed;
+use iracehud_lib::source::synthetic_session::SyntheticConfig;
+use iracehud_lib::source::synthetic_source::SyntheticSource;
+use iracehud_lib::source::telemetry_source::TelemetrySource;
+
+const TICK_RATE: u32 = 60;
+// Same cadence as the pipeline
+const SLOW_VAR_RESET_TICKS: u32 = 50;
+
+struct Scenario {
+    name: &'static str,
+    config: SyntheticConfig,
+    /// Session times in seconds, sampled at the first slow tick within that second
+    checkpoints: &'static [u32],
+}
+
+fn scenarios() -> Vec<Scenario> {
+    vec![
+        Scenario {
+            name: "race",
+            config: SyntheticConfig {
+                session_laps: 3,
+                ..Default::default()
+            },
+            checkpoints: &[5, 95, 260, 400],
+        },
+        Scenario {
+            name: "practice",
+            config: SyntheticConfig {
+                session_type: "Practice".to_string(),
+                session_laps: 0,
+                session_time_secs: 900.0,
+                ..Default::default()
+            },
+            checkpoints: &[30, 300],
+        },
+    ]
+}
+
+type Snapshots = BTreeMap<String, Vec<(String, String)>>;
+
+fn init_settings() {
+    let dir = std::env::temp_dir().join(format!("iracehud-tests-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let _ = SETTINGS_PROVIDER.set(Box::new(FileSettingsProvider::new(dir)));
+}
+
+/// Serializes the payload as JSON and checks that the MessagePack sent to the overlays
+/// decodes to the same value, i.e. structs are encoded as maps rather than arrays
+fn serialize_event(event: &TelemetryEvent, session: &SessionData, key: &str) -> String {
+    if !event.is_ready(session) {
+        return "not ready".to_string();
+    }
+    let value = event.get_event(session);
+    let json = serde_json::to_value(value.as_ref()).unwrap();
+    let msgpack = rmp_serde::to_vec(value.as_ref()).unwrap();
+    let decoded: serde_json::Value = rmp_serde::from_slice(&msgpack).unwrap();
+    assert_eq!(
+        json, decoded,
+        "MessagePack payload of {} at {} differs from JSON",
+        event, key
+    );
+    serde_json::to_string_pretty(value.as_ref()).unwrap()
+}
+
+async fn collect_snapshots(snapshots: &mut Snapshots, scenario: Scenario) {
+    let mut source = SyntheticSource::new(scenario.config, ReplaySpeed::Stepped);
+    let stepper = source.stepper();
+    let mut session = SessionData::default();
+    let fixed_time = Local.with_ymd_and_hms(2025, 6, 1, 14, 30, 0).unwrap();
+
+    let last_checkpoint = *scenario.checkpoints.iter().max().unwrap();
+    for tick in 0..(last_checkpoint + 1) * TICK_RATE {
+        stepper.notify_one();
+        let sim_state = source.next_tick().await.unwrap();
+        let should_process_slow = tick % SLOW_VAR_RESET_TICKS == 0;
+        session.process_tick(&sim_state, should_process_slow, false);
+
+        let seconds = tick / TICK_RATE;
+        let is_checkpoint = scenario.checkpoints.contains(&seconds)
+            && tick - seconds * TICK_RATE < SLOW_VAR_RESET_TICKS
+            && should_process_slow;
+        if !is_checkpoint {
+            continue;
+        }
+        session.current_time = fixed_time;
+        let key = format!("{}@{}s", scenario.name, seconds);
+        for event in TelemetryEvent::iter() {
+            let payload = serialize_event(&event, &session, &key);
+            snapshots
+                .entry(event.to_string())
+                .or_default()
+                .push((key.clone(), payload));
+        }
+    }
+}
+
+fn render_snapshot(checkpoints: &[(String, String)]) -> String {
+    let mut result = String::new();
+    for (key, payload) in checkpoints {
+        result.push_str(&format!("--- {}\n{}\n", key, payload));
+    }
+    result
+}
+
+#[tokio::test]
+async fn telemetry_event_payloads_match_snapshots() {
+    init_settings();
+
+    let mut snapshots = Snapshots::new();
+    for scenario in scenarios() {
+        collect_snapshots(&mut snapshots, scenario).await;
+    }
+
+    let snapshots_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots");
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let mut mismatched = Vec::new();
+    for event in TelemetryEvent::iter() {
+        let name = event.to_string();
+        let actual = render_snapshot(&snapshots[&name]);
+        let path = snapshots_dir.join(format!("{}.snap", name));
+        if update {
+            fs::create_dir_all(&snapshots_dir).unwrap();
+            fs::write(&path, &actual).unwrap();
+            continue;
+        }
+        let expected = fs::read_to_string(&path).unwrap_or_default();
+        if expected != actual {
+            mismatched.push(name);
+        }
+    }
+    assert!(
+        mismatched.is_empty(),
+        "payloads differ from snapshots for {:?}, rerun with UPDATE_SNAPSHOTS=1 to accept",
+        mismatched
+    );
+}
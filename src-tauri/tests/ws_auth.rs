@@ -0,0 +1,55 @@
+//! Clients have to present the token during the handshake, the discovery file tells them where
+//! to connect.
+
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::{Error, http::StatusCode};
+
+use iracehud_lib::websocket::server::WebSocketServer;
+
+mod common;
+
+const TOKEN: &str = "s3cret";
+
+#[tokio::test]
+async fn checks_the_token_and_writes_discovery_file() {
+    let dir = std::env::temp_dir().join(format!("iracehud-ws-auth-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let port = common::start_server(
+        WebSocketServer::new()
+            .with_token(TOKEN)
+            .with_discovery_file(dir.join("discovery.json")),
+        "127.0.0.1:0",
+    )
+    .await;
+
+    let discovery: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(dir.join("discovery.json")).unwrap())
+            .unwrap();
+    let url = format!("ws://127.0.0.1:{}/?token={}", port, TOKEN);
+    assert_eq!(discovery["port"], port);
+    assert_eq!(discovery["token"], TOKEN);
+    assert_eq!(discovery["url"], url);
+
+    for url in [
+        format!("ws://127.0.0.1:{}/", port),
+        format!("ws://127.0.0.1:{}/?token=wrong", port),
+    ] {
+        match connect_async(url).await {
+            Err(Error::Http(response)) => assert_eq!(response.status(), StatusCode::UNAUTHORIZED),
+            other => panic!("Connection without a valid token: {:?}", other.map(|_| ())),
+        }
+    }
+
+    assert!(connect_async(url).await.is_ok());
+    let mut request = format!("ws://127.0.0.1:{}/", port)
+        .into_client_request()
+        .unwrap();
+    request.headers_mut().insert(
+        "Authorization",
+        format!("Bearer {}", TOKEN).parse().unwrap(),
+    );
+    assert!(connect_async(request).await.is_ok());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,34 @@
+//! A configured address that can't be bound must not leave the app without a server.
+
+use tokio::net::TcpListener;
+use tokio_tungstenite::connect_async;
+
+use iracehud_lib::websocket::server::WebSocketServer;
+
+mod common;
+
+#[tokio::test]
+async fn falls_back_to_loopback_when_the_address_is_taken() {
+    let taken = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let taken_addr = taken.local_addr().unwrap();
+
+    let port = common::start_server(
+        WebSocketServer::new().with_token("s3cret"),
+        &taken_addr.to_string(),
+    )
+    .await;
+    assert_ne!(port, taken_addr.port());
+
+    let discovery = WebSocketServer::get_discovery().unwrap();
+    assert_eq!(discovery.host, "127.0.0.1");
+    assert!(
+        discovery
+            .bind_error
+            .as_deref()
+            .unwrap()
+            .starts_with(&format!("Failed to listen on {}", taken_addr))
+    );
+
+    let url = format!("ws://127.0.0.1:{}/?token=s3cret", port);
+    assert!(connect_async(url).await.is_ok());
+}
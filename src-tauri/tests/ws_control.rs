@@ -0,0 +1,117 @@
+//! Requests sent by WebSocket clients are answered on the same socket.
+
+use std::sync::Arc;
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{Value, json};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async};
+
+use iracehud_lib::pipeline::Pipeline;
+use iracehud_lib::settings::file_settings_provider::FileSettingsProvider;
+use iracehud_lib::settings::settings_provider::SETTINGS_PROVIDER;
+use iracehud_lib::websocket::control_handler::ControlHandler;
+use iracehud_lib::websocket::server::WebSocketServer;
+
+mod common;
+
+type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn connect() -> Client {
+    let dir = std::env::temp_dir().join(format!("iracehud-ws-tests-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let _ = SETTINGS_PROVIDER.set(Box::new(FileSettingsProvider::new(dir)));
+
+    let port = common::start_server(
+        WebSocketServer::new()
+            .with_control_handler(ControlHandler::new(Arc::new(Pipeline::default()))),
+        "127.0.0.1:0",
+    )
+    .await;
+    let (client, _) = connect_async(format!("ws://127.0.0.1:{}", port))
+        .await
+        .unwrap();
+    client
+}
+
+/// Sends the request as JSON and waits for the `response` event, skipping broadcasts
+async fn request(client: &mut Client, request: Value) -> Value {
+    client
+        .send(Message::text(request.to_string()))
+        .await
+        .unwrap();
+    loop {
+        let message = client.next().await.unwrap().unwrap();
+        let (event, data): (String, Value) = rmp_serde::from_slice(&message.into_data()).unwrap();
+        if event == "response" {
+            return data;
+        }
+    }
+}
+
+#[tokio::test]
+async fn answers_control_requests() {
+    let mut client = connect().await;
+
+    let response = request(
+        &mut client,
+        json!({"id": 1, "command": "subscribe", "event": "relative"}),
+    )
+    .await;
+    assert_eq!(response, json!({"id": 1, "result": null, "error": null}));
+
+    let response = request(
+        &mut client,
+        json!({"id": 2, "command": "get_settings", "overlay": "timer"}),
+    )
+    .await;
+    let mut settings = response["result"].clone();
+    assert_eq!(response["id"], 2);
+    assert!(settings["common_settings"].is_object());
+
+    settings["delta_width"] = json!(123);
+    let response = request(
+        &mut client,
+        json!({"id": 3, "command": "set_settings", "overlay": "timer", "settings": settings}),
+    )
+    .await;
+    assert_eq!(response["error"], Value::Null);
+    let response = request(
+        &mut client,
+        json!({"id": 4, "command": "get_settings", "overlay": "timer"}),
+    )
+    .await;
+    assert_eq!(response["result"]["delta_width"], 123);
+
+    // the headless server has no overlay windows
+    let response = request(
+        &mut client,
+        json!({"id": 5, "command": "lock_unlock_overlays"}),
+    )
+    .await;
+    assert_eq!(response["error"], "Overlays are not available");
+
+    let response = request(
+        &mut client,
+        json!({"id": 6, "command": "set_settings", "overlay": "timer", "settings": {"delta_width": "wide"}}),
+    )
+    .await;
+    assert_eq!(response["id"], 6);
+    assert!(response["error"].is_string());
+
+    let response = request(&mut client, json!({"id": 7, "command": "restart"})).await;
+    assert_eq!(response["id"], Value::Null);
+    assert!(
+        response["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Invalid request")
+    );
+
+    let response = request(&mut client, json!({"id": 8, "command": "get_client_stats"})).await;
+    let stats = response["result"].as_array().unwrap();
+    assert_eq!(stats.len(), 1);
+    assert!(stats[0]["sent"].as_u64().unwrap() >= 6);
+    assert_eq!(stats[0]["queued"], 0);
+}
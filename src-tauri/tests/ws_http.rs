@@ -0,0 +1,75 @@
+//! The built frontend is served over plain HTTP on the WebSocket port, so overlays can be added
+//! to OBS as browser sources.
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_tungstenite::connect_async;
+
+use iracehud_lib::websocket::server::WebSocketServer;
+use iracehud_lib::websocket::static_files::DirStaticFiles;
+
+mod common;
+
+const TOKEN: &str = "s3cret";
+const STREAM_PAGE: &str = "<html><body>relative</body></html>";
+
+async fn http_request(port: u16, request: &str) -> String {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+#[tokio::test]
+async fn serves_frontend_next_to_the_websocket() {
+    let dir = std::env::temp_dir().join(format!("iracehud-ws-http-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("frontend/stream")).unwrap();
+    std::fs::write(dir.join("frontend/stream/relative.html"), STREAM_PAGE).unwrap();
+    std::fs::write(dir.join("network.json"), "{}").unwrap();
+
+    let port = common::start_server(
+        WebSocketServer::new()
+            .with_token(TOKEN)
+            .with_static_files(DirStaticFiles::new(dir.join("frontend"))),
+        "127.0.0.1:0",
+    )
+    .await;
+
+    let discovery = WebSocketServer::get_discovery().unwrap();
+    assert_eq!(
+        discovery.info().stream_urls["relative"],
+        format!("http://127.0.0.1:{}/stream/relative?token={}", port, TOKEN)
+    );
+
+    let response = http_request(
+        port,
+        "GET /stream/relative?token=s3cret HTTP/1.1\r\nHost: localhost\r\n\r\n",
+    )
+    .await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+    assert!(response.contains("Content-Type: text/html; charset=utf-8\r\n"));
+    assert!(response.ends_with(STREAM_PAGE));
+
+    for path in ["/stream/missing", "/../network.json"] {
+        let response = http_request(
+            port,
+            &format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path),
+        )
+        .await;
+        assert!(
+            response.starts_with("HTTP/1.1 404 Not Found\r\n"),
+            "{}",
+            response
+        );
+    }
+
+    let response = http_request(port, "POST / HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+    assert!(response.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+
+    let url = format!("ws://127.0.0.1:{}/?token={}", port, TOKEN);
+    assert!(connect_async(url).await.is_ok());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,104 @@
+//! Every WebSocket client only receives the events it subscribed to.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{Value, json};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async};
+
+use iracehud_lib::pipeline::Pipeline;
+use iracehud_lib::session::session_data::SessionData;
+use iracehud_lib::websocket::control_handler::ControlHandler;
+use iracehud_lib::websocket::server::WebSocketServer;
+
+mod common;
+
+type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn connect(port: u16) -> Client {
+    let (client, _) = connect_async(format!("ws://127.0.0.1:{}", port))
+        .await
+        .unwrap();
+    client
+}
+
+/// Next event sent to the client, `None` if nothing arrives shortly
+async fn next_event(client: &mut Client) -> Option<(String, Value)> {
+    let message = tokio::time::timeout(Duration::from_millis(200), client.next())
+        .await
+        .ok()??
+        .unwrap();
+    Some(rmp_serde::from_slice(&message.into_data()).unwrap())
+}
+
+async fn send(client: &mut Client, command: &str, event: &str) {
+    client
+        .send(Message::text(
+            json!({"command": command, "event": event}).to_string(),
+        ))
+        .await
+        .unwrap();
+    let (name, response) = next_event(client).await.unwrap();
+    assert_eq!(name, "response");
+    assert_eq!(response["error"], Value::Null);
+}
+
+async fn emit(pipeline: &Pipeline, session: &SessionData) {
+    pipeline
+        .emitter
+        .lock()
+        .await
+        .emit_all(session)
+        .await
+        .unwrap();
+}
+
+async fn received_events(client: &mut Client) -> Vec<String> {
+    let mut events = Vec::new();
+    while let Some((event, _)) = next_event(client).await {
+        events.push(event);
+    }
+    events.sort();
+    events
+}
+
+#[tokio::test]
+async fn delivers_events_per_client() {
+    let pipeline = Arc::new(Pipeline::default());
+    let port = common::start_server(
+        WebSocketServer::new().with_control_handler(ControlHandler::new(pipeline.clone())),
+        "127.0.0.1:0",
+    )
+    .await;
+    let mut first = connect(port).await;
+    let mut second = connect(port).await;
+    let session = SessionData::default();
+
+    send(&mut first, "subscribe", "active").await;
+    send(&mut first, "subscribe", "spectating").await;
+    send(&mut second, "subscribe", "active").await;
+    emit(&pipeline, &session).await;
+    assert_eq!(received_events(&mut first).await, ["active", "spectating"]);
+    assert_eq!(received_events(&mut second).await, ["active"]);
+
+    // unchanged values are not sent again
+    emit(&pipeline, &session).await;
+    assert!(received_events(&mut first).await.is_empty());
+
+    // the second client doesn't break the first one by unsubscribing, a new session resends
+    send(&mut second, "unsubscribe", "active").await;
+    send(&mut second, "subscribe", "spectating").await;
+    pipeline.emitter.lock().await.reset();
+    emit(&pipeline, &session).await;
+    assert_eq!(received_events(&mut first).await, ["active", "spectating"]);
+    assert_eq!(received_events(&mut second).await, ["spectating"]);
+
+    // subscribing again resends the current value to that client only
+    send(&mut second, "subscribe", "active").await;
+    emit(&pipeline, &session).await;
+    assert!(received_events(&mut first).await.is_empty());
+    assert_eq!(received_events(&mut second).await, ["active"]);
+}
@@ -1,10 +1,9 @@
-import { invoke } from "@tauri-apps/api/core";
 import { readable } from "svelte/store";
 import { wsClient } from "./ws_client";
 
 export const isLocked = readable<boolean>(false, (set) => {
-    invoke(`get_overlays_locked`).then((isLocked) => {
-        set(isLocked as boolean);
+    wsClient.request<boolean>("get_overlays_locked").then((isLocked) => {
+        set(isLocked);
     });
 
     // Subscribe to WebSocket updates for this overlay's settings
@@ -1,18 +1,17 @@
-import type { LapTimesOverlaySettings, MainOverlaySettings, ProximityOverlaySettings, RelativeOverlaySettings, StandingsOverlaySettings, SubTimerOverlaySettings, TelemetryOverlaySettings, TelemetryReferenceOverlaySettings, TimerOverlaySettings, TrackMapOverlaySettings } from "$lib/types/telemetry";
-import { invoke } from "@tauri-apps/api/core";
+import type { FuelOverlaySettings, LapTimesOverlaySettings, MainOverlaySettings, ProximityOverlaySettings, RelativeOverlaySettings, StandingsOverlaySettings, SubTimerOverlaySettings, TelemetryOverlaySettings, TelemetryReferenceOverlaySettings, TimerOverlaySettings, TrackMapOverlaySettings } from "$lib/types/telemetry";
 import { readable } from "svelte/store";
 import { wsClient } from './ws_client';
 
 function createSettingsStore<T>(overlay: string) {
     return readable<T>(undefined, (set) => {
-        invoke(`get_${overlay}_overlay_settings`).then((settings) => {
-            set(settings as T);
+        wsClient.request<T>("get_settings", { overlay }).then((settings) => {
+            set(settings);
         });
 
         // Subscribe to WebSocket updates for this overlay's settings
         wsClient.subscribe(`${overlay}_overlay_settings_changed`, (_data: T) => {
-            invoke(`get_${overlay}_overlay_settings`).then((settings) => {
-                set(settings as T);
+            wsClient.request<T>("get_settings", { overlay }).then((settings) => {
+                set(settings);
             });
         });
 
@@ -23,6 +22,7 @@ function createSettingsStore<T>(overlay: string) {
     });
 }
 
+export const fuelOverlaySettings = createSettingsStore<FuelOverlaySettings>("fuel");
 export const lapTimesOverlaySettings = createSettingsStore<LapTimesOverlaySettings>("lap_times");
 export const mainOverlaySettings = createSettingsStore<MainOverlaySettings>("main");
 export const proximityOverlaySettings = createSettingsStore<ProximityOverlaySettings>("proximity");
@@ -3,25 +3,21 @@ import type {
     LapTime, DeltaBestTime, DeltaLastTime, TelemetryGraph, SessionState, GapNext, GapPrev,
     TrackId, TrackMap, Gear, Speed, Rpm, Active, GearShiftRpm, GearBlinkRpm, Incidents, RaceLaps,
     LapsTotal, PlayerCarClass, FastestLap, SessionType,
-    TelemetryReference
+    TelemetryReference, SectorTimes, Fuel, Flags, Spotter, ClassStandings, QualifyStandings, RatingProjection, PositionHistory, Weather, Spectating
 } from "$lib/types/telemetry";
-import { invoke } from "@tauri-apps/api/core";
 import { readable } from 'svelte/store';
 import { wsClient } from './ws_client';
 
 function createTelemetryStore<T>(event: string, initialValue: T) {
     return readable<T>(initialValue, (set) => {
-        invoke("register_event_emitter", { event });
-
         // Set up WebSocket subscription
-        wsClient.subscribe(event, (message: T) => {
+        wsClient.subscribeEvent(event, (message: T) => {
             set(message);
         });
 
         return () => {
             // Cleanup
-            invoke("unregister_event_emitter", { event });
-            wsClient.unsubscribe(event);
+            wsClient.unsubscribeEvent(event);
         };
     });
 }
@@ -30,10 +26,14 @@ export const active = createTelemetryStore<Active>("active", false);
 export const currentTime = createTelemetryStore<CurrentTime>("current_time", "--:--");
 export const lapTimes = createTelemetryStore<PlayerLapTimes>("player_lap_times", []);
 export const standings = createTelemetryStore<Standings>("standings", []);
+export const classStandings = createTelemetryStore<ClassStandings>("class_standings", []);
+export const qualifyStandings = createTelemetryStore<QualifyStandings>("qualify_standings", []);
+export const ratingProjection = createTelemetryStore<RatingProjection>("rating_projection", { irating: 0, irating_change: 0, projected_irating: 0, safety_rating: 0, safety_rating_change: 0, projected_safety_rating: 0, incidents: 0, corners: 0 });
+export const positionHistory = createTelemetryStore<PositionHistory>("position_history", []);
 export const strengthOfField = createTelemetryStore<StrengthOfField>("strength_of_field", 0);
 export const positionsTotal = createTelemetryStore<Position>("positions_total", 0);
 export const raceLaps = createTelemetryStore<RaceLaps>("race_laps", 0);
-export const proximity = createTelemetryStore<Proximity>("proximity", { is_left: false, is_right: false });
+export const proximity = createTelemetryStore<Proximity>("proximity", { is_left: false, is_right: false, state: "off", overlap_pct: 0, is_ahead: false });
 export const relative = createTelemetryStore<Relative>("relative", []);
 export const lapTime = createTelemetryStore<LapTime>("lap_time", 0);
 export const deltaBestTime = createTelemetryStore<DeltaBestTime>("delta_best_time", "–");
@@ -58,3 +58,9 @@ export const incidentLimit = createTelemetryStore<Incidents>("incident_limit", 0
 export const playerCarClass = createTelemetryStore<PlayerCarClass>("player_car_class", "");
 export const fastestLap = createTelemetryStore<FastestLap>("fastest_lap", "-:--:--");
 export const sessionType = createTelemetryStore<SessionType>("session_type", "Unknown");
+export const sectorTimes = createTelemetryStore<SectorTimes>("sector_times", []);
+export const fuel = createTelemetryStore<Fuel>("fuel", { fuel_level: "–", fuel_level_pct: 0, last_lap_usage: "–", average_usage: "–", laps_remaining: "–", laps_to_finish: "–", fuel_to_finish: "–", fuel_to_add: "–", pit_stops_needed: 0, is_enough: false });
+export const flags = createTelemetryStore<Flags>("flags", { flag: "none", previous_flag: "none", session_flags: [], player_flags: [] });
+export const spotter = createTelemetryStore<Spotter>("spotter", { state: "off", previous_state: "off", changed_at: 0 });
+export const weather = createTelemetryStore<Weather>("weather", { weather_type: "", air_temp: 0, track_temp: 0, track_wetness: "unknown", precipitation_pct: 0, wind_vel: 0, wind_dir: 0, skies: "clear", fog_level_pct: 0, wetness_trend: "steady", precipitation_trend: "steady", track_temp_trend: "steady", weekend_skies: "", weekend_air_temp: 0, weekend_track_temp: 0, relative_humidity_pct: 0 });
+export const spectating = createTelemetryStore<Spectating>("spectating", { is_spectating: false, car_id: null, user_name: "", car_number: "" });
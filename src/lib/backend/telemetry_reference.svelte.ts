@@ -1,5 +1,5 @@
 import type { TelemetryReferenceOutput } from "$lib/types/telemetry";
-import { invoke } from "@tauri-apps/api/core";
+import { wsClient } from "./ws_client";
 import { readable } from "svelte/store";
 import { trackID } from "./telemetry.svelte";
 
@@ -7,8 +7,8 @@ export const telemetryReferencePoints = readable<TelemetryReferenceOutput>(
     { reference: [], brake_points: [] },
     (set) => {
         const unsubscribe = trackID.subscribe((trackId) => {
-            invoke("get_telemetry_reference_points", { trackId }).then((points) => {
-                set(points as TelemetryReferenceOutput);
+            wsClient.request<TelemetryReferenceOutput>("get_telemetry_reference_points", { track_id: trackId }).then((points) => {
+                set(points);
             });
         });
 
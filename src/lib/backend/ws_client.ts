@@ -1,5 +1,6 @@
 import { invoke } from '@tauri-apps/api/core';
 import { decode } from '@msgpack/msgpack';
+import type { DiscoveryInfo } from '$lib/types/telemetry';
 
 export interface WsMessageHandler<T> {
   (data: T): void;
@@ -7,20 +8,42 @@ export interface WsMessageHandler<T> {
 
 type WsEvent = [string, any];
 
+type WsResponse = { id: number | null; result: any; error: string | null };
+
+type PendingRequest = { resolve: (result: any) => void; reject: (error: Error) => void };
+
+// Overlays opened as OBS browser sources are served by the telemetry server itself
+const isTauri = '__TAURI_INTERNALS__' in window;
+
 export class WebSocketClient {
   private ws: WebSocket | null = null;
   private messageHandlers: Map<string, WsMessageHandler<any>> = new Map();
+  private subscribedEvents: Set<string> = new Set();
   private isConnected = false;
   private reconnectTimer: number | null = null;
+  private host = '127.0.0.1';
   private port: number | null = null;
+  private token: string | null = null;
+  private nextRequestId = 1;
+  private pendingRequests: Map<number, PendingRequest> = new Map();
+  private queuedRequests: object[] = [];
 
   constructor() {
     this.initialize();
   }
 
   private async initialize() {
+    if (!isTauri) {
+      this.host = window.location.hostname;
+      this.port = parseInt(window.location.port) || 80;
+      this.token = new URLSearchParams(window.location.search).get('token');
+      this.connect();
+      return;
+    }
     try {
-      this.port = await invoke<number>('get_ws_port');
+      const discovery = await invoke<DiscoveryInfo | null>('get_ws_discovery');
+      this.port = discovery?.port ?? null;
+      this.token = discovery?.token ?? null;
       if (this.port) {
         this.connect();
       } else {
@@ -40,7 +63,8 @@ export class WebSocketClient {
     }
 
     try {
-      this.ws = new WebSocket(`ws://127.0.0.1:${this.port}`);
+      const query = this.token ? `?token=${encodeURIComponent(this.token)}` : '';
+      this.ws = new WebSocket(`ws://${this.host}:${this.port}/${query}`);
       this.ws.binaryType = 'arraybuffer';
 
       this.ws.onopen = () => {
@@ -48,6 +72,13 @@ export class WebSocketClient {
         this.isConnected = true;
         if (this.reconnectTimer) {
         }
+        // The server forgets subscriptions of a closed connection
+        for (const event of this.subscribedEvents) {
+          this.send({ command: 'subscribe', event });
+        }
+        for (const request of this.queuedRequests.splice(0)) {
+          this.send(request);
+        }
       };
 
       this.ws.onclose = () => {
@@ -88,10 +119,64 @@ export class WebSocketClient {
     this.messageHandlers.delete(event);
   }
 
+  // Telemetry events are only emitted by the server while someone is subscribed to them
+  subscribeEvent<T>(event: string, handler: WsMessageHandler<T>) {
+    this.subscribe(event, handler);
+    this.subscribedEvents.add(event);
+    this.send({ command: 'subscribe', event });
+  }
+
+  unsubscribeEvent(event: string) {
+    this.unsubscribe(event);
+    this.subscribedEvents.delete(event);
+    this.send({ command: 'unsubscribe', event });
+  }
+
+  // Answered by the server with a `response` event carrying the same id
+  request<T>(command: string, params: object = {}): Promise<T> {
+    const id = this.nextRequestId++;
+    const message = { id, command, ...params };
+    return new Promise<T>((resolve, reject) => {
+      this.pendingRequests.set(id, { resolve, reject });
+      if (this.ws && this.isConnected) {
+        this.send(message);
+      } else {
+        this.queuedRequests.push(message);
+      }
+    });
+  }
+
+  private handleResponse(response: WsResponse) {
+    if (response.id === null) {
+      console.error('Invalid WebSocket request:', response.error);
+      return;
+    }
+    const pending = this.pendingRequests.get(response.id);
+    if (!pending) {
+      return;
+    }
+    this.pendingRequests.delete(response.id);
+    if (response.error !== null) {
+      pending.reject(new Error(response.error));
+    } else {
+      pending.resolve(response.result);
+    }
+  }
+
+  private send(message: object) {
+    if (this.ws && this.isConnected) {
+      this.ws.send(JSON.stringify(message));
+    }
+  }
+
   private handleMessage<T>(event: MessageEvent) {
     try {
       const data = new Uint8Array(event.data);
       const [eventName, eventData] = decode(data) as WsEvent;
+      if (eventName === 'response') {
+        this.handleResponse(eventData as WsResponse);
+        return;
+      }
       const handler = this.messageHandlers.get(eventName) as WsMessageHandler<T> | undefined;
 
       if (handler) {
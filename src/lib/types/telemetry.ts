@@ -1,104 +1,165 @@
 // This file has been generated by Specta. DO NOT EDIT.
 
-export type TelemetryReferenceOverlaySettings = { common_settings: CommonSettings; show_throttle: boolean; show_steering: boolean; brake_que_0_enabled: boolean; brake_que_1_enabled: boolean; brake_que_1_distance: number; brake_que_2_enabled: boolean; brake_que_2_distance: number; brake_que_3_enabled: boolean; brake_que_3_distance: number }
+export type RelativeDriver = { car_id: number; position: number; user_name: string; car_number: string; irating: string; irating_change: string; license: string; player_relative_gap: string; is_player: boolean; is_in_pits: boolean; is_off_track: boolean; is_off_world: boolean; is_player_car_class: boolean; car_class_color: number; is_ahead: boolean; is_behind: boolean; laps_since_pit: number; pit_stops: number }
 
-export type PlayerLapTimes = PlayerLapTimesData[]
+export type Relative = RelativeDriver[]
 
-export type DeltaLastTime = string
+export type RelativeOverlaySettings = { common_settings: CommonSettings; show_irating_change?: boolean }
 
-export type PlayerLapTimesData = { lap: number; lap_time: string }
+/**
+ * Lap by lap positions of the player class for the position chart
+ */
+export type PositionHistory = PositionHistoryDriver[]
 
-export type FastestLap = string
+export type TelemetryReferenceOverlaySettings = { common_settings: CommonSettings; show_throttle: boolean; show_steering: boolean; brake_que_0_enabled: boolean; brake_que_1_enabled: boolean; brake_que_1_distance: number; brake_que_2_enabled: boolean; brake_que_2_distance: number; brake_que_3_enabled: boolean; brake_que_3_distance: number }
+
+export type SessionType = string
 
 export type StrengthOfField = number
 
-export type TelemetryOverlaySettings = { common_settings: CommonSettings }
+export type PlayerCarClass = string
+
+export type PlayerLapTimesData = { lap: number; lap_time: string }
+
+export type MainOverlaySettings = { common_settings: CommonSettings }
+
+/**
+ * Where the WebSocket server listens and the token clients need, applied on restart
+ */
+export type NetworkSettings = { bind_address: string; port: number; token: string }
 
 export type LapTimesOverlaySettings = { common_settings: CommonSettings }
 
-export type TrackMapOverlaySettings = { common_settings: CommonSettings }
+export type PlayerLapTimes = PlayerLapTimesData[]
 
-export type SessionType = string
+export type SectorTimes = SectorTime[]
 
-export type TelemetryReference = { lap_dist: number; throttle: number; brake: number; steering_angle: number; gear: number }
+export type GearShiftRpm = number
 
-export type PlayerCarClass = string
+export type BrakePoint = { lap_dist: number }
 
-export type GearBlinkRpm = number
+export type Rpm = number
 
-export type CommonSettings = { enabled: boolean; width: number; height: number; opacity: number; scale: number; x: number; y: number }
+export type Active = boolean
+
+export type TelemetryReference = { lap_dist: number; throttle: number; brake: number; steering_angle: number; gear: number }
 
 export type DeltaBestTime = string
 
+/**
+ * Best lap standings of the player class for qualifying and practice sessions
+ */
+export type QualifyStandings = QualifyStandingsDriver[]
+
+export type StandingsDriver = { car_id: number; position: number; user_name: string; car_number: string; irating: string; irating_change: string; license: string; leader_gap: string; best_lap: string; last_lap: string; is_player: boolean; is_leader: boolean; is_in_pits: boolean; split_after: boolean; is_off_world: boolean; is_off_track: boolean; laps_since_pit: number; pit_stops: number; start_position: number | null; positions_gained: number; stint_positions_gained: number }
+
+export type SessionTime = string
+
+export type Position = number
+
+export type TimerOverlaySettings = { common_settings: CommonSettings; delta_enabled: boolean; delta_width: number }
+
 export type TelemetryGraph = { ts: number; throttle: number; brake: number; abs_active: boolean }
 
-export type Speed = number
+export type SessionState = string
 
-export type StandingsDriver = { car_id: number; position: number; user_name: string; car_number: string; irating: string; license: string; leader_gap: string; best_lap: string; last_lap: string; is_player: boolean; is_leader: boolean; is_in_pits: boolean; split_after: boolean; is_off_world: boolean; is_off_track: boolean }
+export type PositionHistoryDriver = { car_id: number; user_name: string; car_number: string; is_player: boolean; start_position: number | null; position: number; positions_gained: number; lap_positions: number[] }
 
-export type Standings = StandingsDriver[]
+export type TrackMapDriver = { car_id: number; position: number; class_position: number | null; is_leader: boolean; is_player: boolean; is_player_class: boolean; lap_dist_pct: number; is_in_pits: boolean; is_off_track: boolean; is_off_world: boolean; car_class_color: number }
 
-export type GearShiftRpm = number
+export type CurrentTime = string
 
-export type SubTimerOverlaySettings = { common_settings: CommonSettings; gap_enabled: boolean; gap_width: number }
+export type GapNext = string
 
-export type TelemetryReferenceOutput = { reference: TelemetryReference[]; brake_points: BrakePoint[] }
+export type Proximity = { is_left: boolean; is_right: boolean; state: string; overlap_pct: number; is_ahead: boolean }
 
-export type Lap = number
+export type Speed = number
 
-export type LapsTotal = number
+/**
+ * Whether the overlays follow the camera car instead of the player
+ */
+export type Spectating = { is_spectating: boolean; car_id: number | null; user_name: string; car_number: string }
 
-export type StandingsOverlaySettings = { common_settings: CommonSettings; max_drivers: number; top_drivers: number }
+export type GapPrev = string
 
-export type Active = boolean
+export type ProximityOverlaySettings = { common_settings: CommonSettings }
 
-export type TrackId = number
+export type SubTimerOverlaySettings = { common_settings: CommonSettings; gap_enabled: boolean; gap_width: number }
 
-export type PositionsTotal = number
+export type TrackMap = TrackMapDriver[]
 
-export type Rpm = number
+export type TelemetryOverlaySettings = { common_settings: CommonSettings }
 
-export type CurrentTime = string
+export type QualifyStandingsDriver = { car_id: number; position: number; user_name: string; car_number: string; irating: string; license: string; best_lap: string; pole_gap: string; laps_run: number; is_on_hot_lap: boolean; provisional_position: number | null; is_player: boolean; is_in_pits: boolean; split_after: boolean }
 
-export type Relative = RelativeDriver[]
+export type LapTime = number
 
-export type SessionState = string
+export type FuelOverlaySettings = { common_settings: CommonSettings; average_laps: number; safety_margin_laps: number }
 
-export type BrakePoint = { lap_dist: number }
+export type SessionTimeTotal = string
 
-export type RaceLaps = number
+/**
+ * Standings of every class, fastest class first
+ */
+export type ClassStandings = ClassStandingsBlock[]
+
+export type CommonSettings = { enabled: boolean; width: number; height: number; opacity: number; scale: number; x: number; y: number }
+
+export type Standings = StandingsDriver[]
+
+export type FastestLap = string
+
+export type Weather = { weather_type: string; air_temp: number; track_temp: number; track_wetness: string; precipitation_pct: number; wind_vel: number; wind_dir: number; skies: string; fog_level_pct: number; wetness_trend: string; precipitation_trend: string; track_temp_trend: string; weekend_skies: string; weekend_air_temp: number; weekend_track_temp: number; relative_humidity_pct: number }
 
 export type Gear = string
 
-export type TrackMapDriver = { car_id: number; position: number; class_position: number | null; is_leader: boolean; is_player: boolean; is_player_class: boolean; lap_dist_pct: number; is_in_pits: boolean; is_off_track: boolean; is_off_world: boolean; car_class_color: number }
+/**
+ * Only changes on flag transitions, so every emit is a transition
+ */
+export type Flags = { flag: string; previous_flag: string; session_flags: string[]; player_flags: string[] }
 
-export type MainOverlaySettings = { common_settings: CommonSettings }
+export type PositionsTotal = number
 
-export type TimerOverlaySettings = { common_settings: CommonSettings; delta_enabled: boolean; delta_width: number }
+/**
+ * Only changes on spotter transitions, the overlap lives in `Proximity`
+ */
+export type Spotter = { state: string; previous_state: string; changed_at: number }
 
-export type SessionTime = string
+export type DeltaLastTime = string
 
-export type GapNext = string
+export type TrackId = number
 
-export type TrackMap = TrackMapDriver[]
+export type LapsTotal = number
 
 export type IncidentLimit = number
 
-export type RelativeOverlaySettings = { common_settings: CommonSettings }
+export type RaceLaps = number
 
-export type Incidents = number
+export type GearBlinkRpm = number
 
-export type LapTime = number
+export type Fuel = { fuel_level: string; fuel_level_pct: number; last_lap_usage: string; average_usage: string; laps_remaining: string; laps_to_finish: string; fuel_to_finish: string; fuel_to_add: string; pit_stops_needed: number; is_enough: boolean }
 
-export type Position = number
+export type Lap = number
 
-export type Proximity = { is_left: boolean; is_right: boolean }
+/**
+ * What is written to `discovery.json` and shown in the settings, the URLs are the QR code payload
+ */
+export type DiscoveryInfo = { host: string; port: number; token: string | null; url: string; stream_urls: { [key: string]: string }; bind_error: string | null }
 
-export type GapPrev = string
+export type Incidents = number
 
-export type RelativeDriver = { car_id: number; position: number; user_name: string; car_number: string; irating: string; license: string; player_relative_gap: string; is_player: boolean; is_in_pits: boolean; is_off_track: boolean; is_off_world: boolean; is_player_car_class: boolean; car_class_color: number; is_ahead: boolean; is_behind: boolean }
+export type StandingsOverlaySettings = { common_settings: CommonSettings; max_drivers: number; top_drivers: number; rows_per_class?: number; shown_classes?: string[]; show_irating_change?: boolean }
 
-export type ProximityOverlaySettings = { common_settings: CommonSettings }
+/**
+ * Player's ratings if the race finished in the current order
+ */
+export type RatingProjection = { irating: number; irating_change: number; projected_irating: number; safety_rating: number; safety_rating_change: number; projected_safety_rating: number; incidents: number; corners: number }
 
-export type SessionTimeTotal = string
+export type ClassStandingsBlock = { car_class_id: number; class_name: string; class_color: number; strength_of_field: number; drivers_count: number; leader: string; best_lap: string; is_player_class: boolean; drivers: StandingsDriver[] }
+
+export type SectorTime = { sector_num: number; is_current: boolean; current: string; last: string; best: string; status: string }
+
+export type TelemetryReferenceOutput = { reference: TelemetryReference[]; brake_points: BrakePoint[] }
+
+export type TrackMapOverlaySettings = { common_settings: CommonSettings }
 